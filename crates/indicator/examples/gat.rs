@@ -20,7 +20,10 @@ impl GatOperator<usize> for Collect {
 struct Mux;
 
 impl<'a> GatOperator<&'a [usize]> for Mux {
-    type Output<'out> = Option<usize> where 'a: 'out;
+    type Output<'out>
+        = Option<usize>
+    where
+        'a: 'out;
 
     fn next<'out>(&'out mut self, input: &'a [usize]) -> Self::Output<'out>
     where
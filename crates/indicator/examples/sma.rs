@@ -10,7 +10,7 @@ fn main() {
     let mut op = cache.map(|w| {
         assert!(w.is_inline());
         let outdated = w.change().outdated().map(|t| t.value).unwrap_or_default();
-        debug_assert!(w.len() != 0);
+        debug_assert!(!w.is_empty());
         sms += w[0].value - outdated;
         let l = Decimal::new(w.len() as i64, 0);
         sms / l
@@ -41,7 +41,12 @@ where
 {
     type Output = P2::Output;
     type Error = E;
-    type Future<'a> = AndThenFuture<'a, P1::Future<'a>, P2> where I: 'a, P1: 'a, P2: 'a;
+    type Future<'a>
+        = AndThenFuture<'a, P1::Future<'a>, P2>
+    where
+        I: 'a,
+        P1: 'a,
+        P2: 'a;
 
     fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
         match ready!(self.0.poll_ready(cx)) {
@@ -15,7 +15,11 @@ where
 {
     type Output = P::Output;
     type Error = E;
-    type Future<'a> = futures::future::MapErr<P::Future<'a>, &'a mut F> where P: 'a, F: 'a;
+    type Future<'a>
+        = futures::future::MapErr<P::Future<'a>, &'a mut F>
+    where
+        P: 'a,
+        F: 'a;
 
     fn poll_ready(
         &mut self,
@@ -18,7 +18,10 @@ where
 
     type Error = Infallible;
 
-    type Future<'a> = Ready<Result<Self::Output, Self::Error>> where P: 'a;
+    type Future<'a>
+        = Ready<Result<Self::Output, Self::Error>>
+    where
+        P: 'a;
 
     fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
         Poll::Ready(Ok(()))
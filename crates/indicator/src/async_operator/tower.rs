@@ -27,7 +27,10 @@ where
 
     type Error = S::Error;
 
-    type Future<'a> = S::Future where S: 'a;
+    type Future<'a>
+        = S::Future
+    where
+        S: 'a;
 
     fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
         self.inner.poll_ready(cx)
@@ -0,0 +1,139 @@
+use core::ops::{Add, Div};
+
+use crate::{
+    reset::{Reset, Seed},
+    Operator, TickValue, Tickable, TumblingWindow,
+};
+
+use super::{Bars, Ohlc};
+
+/// Operator that transforms OHLC bars into Heikin-Ashi candles.
+///
+/// Created by [`heikin_ashi`].
+#[derive(Debug, Clone)]
+pub struct HeikinAshi<M, T> {
+    bars: Bars<M, T>,
+    prev: Option<Ohlc<T>>,
+}
+
+impl<M, T> HeikinAshi<M, T>
+where
+    T: Copy + PartialOrd + Add<Output = T> + Div<Output = T> + From<u8>,
+{
+    fn candle(&self, bar: &Ohlc<T>) -> Ohlc<T> {
+        let close = (bar.open + bar.high + bar.low + bar.close) / T::from(4);
+        let open = match &self.prev {
+            Some(prev) => (prev.open + prev.close) / T::from(2),
+            None => (bar.open + bar.close) / T::from(2),
+        };
+        // The close is always within the range of the bar.
+        let mut candle = Ohlc::flat(open);
+        candle.merge(&Ohlc {
+            open: bar.open,
+            high: bar.high,
+            low: bar.low,
+            close,
+        });
+        candle
+    }
+}
+
+impl<M, I, T> Operator<I> for HeikinAshi<M, T>
+where
+    M: TumblingWindow,
+    I: Tickable<Value = Ohlc<T>>,
+    T: Copy + PartialOrd + Add<Output = T> + Div<Output = T> + From<u8>,
+{
+    type Output = TickValue<Ohlc<T>>;
+
+    fn next(&mut self, input: I) -> Self::Output {
+        let TickValue { tick, value } = input.into_tick_value();
+        if let Some(confirmed) = self.bars.push(tick, value) {
+            self.prev = Some(self.candle(&confirmed));
+        }
+        let current = self.candle(self.bars.current().expect("just pushed"));
+        TickValue {
+            tick,
+            value: current,
+        }
+    }
+}
+
+impl<M: TumblingWindow, T: Copy + PartialOrd> Reset for HeikinAshi<M, T> {
+    fn reset(&mut self) {
+        self.bars.reset();
+        self.prev = None;
+    }
+}
+
+/// Seed with the last confirmed Heikin-Ashi candle.
+impl<M: TumblingWindow, T: Copy + PartialOrd> Seed<Ohlc<T>> for HeikinAshi<M, T> {
+    fn seed(&mut self, seed: Ohlc<T>) {
+        self.reset();
+        self.prev = Some(seed);
+    }
+}
+
+/// Create an operator that transforms OHLC bars into Heikin-Ashi candles.
+///
+/// The inputs of the same window (defined by `mode`) are merged into one bar,
+/// so both a stream of bars and a stream of bar updates are accepted.
+/// Use `map_t(Ohlc::from)` to feed a tick stream.
+/// The output is the candle of the current window.
+/// ```
+/// use indicator::{bar::{heikin_ashi, ohlc, Ohlc}, *};
+/// use rust_decimal::Decimal;
+///
+/// fn ha<I>(period: Period) -> impl Operator<I, Output = TickValue<Ohlc<Decimal>>>
+/// where
+///     I: Tickable<Value = Decimal>,
+/// {
+///     ohlc(period).then(heikin_ashi(period))
+/// }
+/// ```
+pub fn heikin_ashi<M, T>(mode: M) -> HeikinAshi<M, T>
+where
+    M: TumblingWindow,
+    T: Copy + PartialOrd + Add<Output = T> + Div<Output = T> + From<u8>,
+{
+    HeikinAshi {
+        bars: Bars::new(mode),
+        prev: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndicatorIteratorExt, Period};
+    use time::macros::datetime;
+
+    #[test]
+    fn candles() {
+        let base = datetime!(2023-01-01 00:00:00 UTC);
+        let bar = |open, high, low, close| Ohlc {
+            open,
+            high,
+            low,
+            close,
+        };
+        let candles = [
+            bar(10.0, 14.0, 8.0, 12.0),
+            bar(12.0, 16.0, 12.0, 16.0),
+            bar(16.0, 16.0, 10.0, 11.0),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(idx, x)| TickValue::new(base + time::Duration::seconds(idx as i64), x))
+        .indicator(heikin_ashi(Period::secs(1)))
+        .map(|x| x.value);
+        let expected = [
+            bar(11.0, 14.0, 8.0, 11.0),
+            bar(11.0, 16.0, 11.0, 14.0),
+            bar(12.5, 16.0, 10.0, 13.25),
+        ];
+        for (candle, expected) in candles.zip(expected) {
+            assert_eq!(candle, expected);
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use core::ops::Sub;
+
+use crate::{
+    reset::{Reset, Seed},
+    Operator, TickValue, Tickable,
+};
+
+use super::{Direction, Ohlc};
+
+/// A Kagi line.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KagiLine<T> {
+    /// Direction.
+    pub direction: Direction,
+    /// Start price.
+    pub start: T,
+    /// End price.
+    pub end: T,
+}
+
+/// Operator that transforms OHLC bars into Kagi lines.
+///
+/// Created by [`kagi`].
+#[derive(Debug, Clone)]
+pub struct Kagi<T> {
+    reversal: T,
+    base: Option<T>,
+    line: Option<KagiLine<T>>,
+}
+
+impl<T> Kagi<T>
+where
+    T: Copy + PartialOrd + Sub<Output = T>,
+{
+    fn update(&mut self, price: T) -> Option<KagiLine<T>> {
+        let Some(line) = self.line else {
+            match self.base {
+                Some(base) if price != base => {
+                    let direction = if price > base {
+                        Direction::Up
+                    } else {
+                        Direction::Down
+                    };
+                    self.line = Some(KagiLine {
+                        direction,
+                        start: base,
+                        end: price,
+                    });
+                }
+                Some(_) => {}
+                None => self.base = Some(price),
+            }
+            return None;
+        };
+        let (extended, reversed) = match line.direction {
+            Direction::Up => (price > line.end, line.end - price >= self.reversal),
+            Direction::Down => (price < line.end, price - line.end >= self.reversal),
+        };
+        if extended {
+            self.line = Some(KagiLine { end: price, ..line });
+            None
+        } else if reversed {
+            let direction = match line.direction {
+                Direction::Up => Direction::Down,
+                Direction::Down => Direction::Up,
+            };
+            self.line.replace(KagiLine {
+                direction,
+                start: line.end,
+                end: price,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl<I, T> Operator<I> for Kagi<T>
+where
+    I: Tickable<Value = Ohlc<T>>,
+    T: Copy + PartialOrd + Sub<Output = T>,
+{
+    type Output = TickValue<Option<KagiLine<T>>>;
+
+    fn next(&mut self, input: I) -> Self::Output {
+        input.into_tick_value().map(|bar| self.update(bar.close))
+    }
+}
+
+impl<T> Reset for Kagi<T> {
+    fn reset(&mut self) {
+        self.base = None;
+        self.line = None;
+    }
+}
+
+/// Seed with the current (unfinished) line.
+impl<T: Copy> Seed<KagiLine<T>> for Kagi<T> {
+    fn seed(&mut self, seed: KagiLine<T>) {
+        self.base = Some(seed.start);
+        self.line = Some(seed);
+    }
+}
+
+/// Create an operator that transforms OHLC bars into Kagi lines,
+/// using the close prices.
+///
+/// The line changes its direction when the price reverses by at least `reversal`.
+/// The output is the finished line (if any).
+pub fn kagi<T>(reversal: T) -> Kagi<T>
+where
+    T: Copy + PartialOrd + Sub<Output = T>,
+{
+    Kagi {
+        reversal,
+        base: None,
+        line: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IndicatorIteratorExt;
+
+    #[test]
+    fn lines() {
+        let line = |direction, start, end| {
+            Some(KagiLine {
+                direction,
+                start,
+                end,
+            })
+        };
+        let lines = [10, 12, 15, 14, 12, 11, 13, 14, 9]
+            .into_iter()
+            .map(|x| TickValue::big_bang(Ohlc::flat(x)))
+            .indicator(kagi(3))
+            .map(|x| x.value);
+        let expected = [
+            None,
+            None,
+            None,
+            None,
+            line(Direction::Up, 10, 15),
+            None,
+            None,
+            line(Direction::Down, 15, 11),
+            line(Direction::Up, 11, 14),
+        ];
+        for (line, expected) in lines.zip(expected) {
+            assert_eq!(line, expected);
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use crate::{reset::Reset, Operator, Tick, TickValue, Tickable, TumblingWindow};
+
+/// Heikin-Ashi candles.
+pub mod heikin_ashi;
+
+/// Kagi lines.
+pub mod kagi;
+
+/// Renko bricks.
+#[cfg(feature = "alloc")]
+pub mod renko;
+
+/// Point-and-Figure columns.
+#[cfg(feature = "alloc")]
+pub mod pnf;
+
+pub use heikin_ashi::{heikin_ashi, HeikinAshi};
+pub use kagi::{kagi, Kagi, KagiLine};
+#[cfg(feature = "alloc")]
+pub use pnf::{point_and_figure, PnfBox, PointAndFigure};
+#[cfg(feature = "alloc")]
+pub use renko::{renko, renko_atr, Atr, Brick, BrickSize, Fixed, Renko};
+
+/// OHLC bar.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ohlc<T> {
+    /// Open.
+    pub open: T,
+    /// High.
+    pub high: T,
+    /// Low.
+    pub low: T,
+    /// Close.
+    pub close: T,
+}
+
+impl<T: Copy> Ohlc<T> {
+    /// Create a bar with all prices equal to `price`.
+    pub fn flat(price: T) -> Self {
+        Self {
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+        }
+    }
+}
+
+impl<T: Copy + PartialOrd> Ohlc<T> {
+    /// Merge a later bar into this bar.
+    pub fn merge(&mut self, later: &Self) {
+        if later.high > self.high {
+            self.high = later.high;
+        }
+        if later.low < self.low {
+            self.low = later.low;
+        }
+        self.close = later.close;
+    }
+}
+
+impl<T: Copy> From<T> for Ohlc<T> {
+    fn from(price: T) -> Self {
+        Self::flat(price)
+    }
+}
+
+/// Direction of a brick, a line or a column.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Up.
+    Up,
+    /// Down.
+    Down,
+}
+
+/// Aggregate the inputs into the bars of the windows.
+#[derive(Debug, Clone)]
+pub(crate) struct Bars<M, T> {
+    mode: M,
+    current: Option<(Tick, Ohlc<T>)>,
+}
+
+impl<M: TumblingWindow, T: Copy + PartialOrd> Bars<M, T> {
+    pub(crate) fn new(mode: M) -> Self {
+        Self {
+            mode,
+            current: None,
+        }
+    }
+
+    /// Push a new bar (or price update), return the last bar if it is confirmed.
+    pub(crate) fn push(&mut self, tick: Tick, bar: Ohlc<T>) -> Option<Ohlc<T>> {
+        match &mut self.current {
+            Some((last, current)) if self.mode.same_window(last, &tick) => {
+                *last = tick;
+                current.merge(&bar);
+                None
+            }
+            current => current.replace((tick, bar)).map(|(_, bar)| bar),
+        }
+    }
+
+    /// Drop the bar of the current window.
+    pub(crate) fn reset(&mut self) {
+        self.current = None;
+    }
+
+    /// The bar of the current window.
+    pub(crate) fn current(&self) -> Option<&Ohlc<T>> {
+        self.current.as_ref().map(|(_, bar)| bar)
+    }
+}
+
+/// Operator that aggregates a tick stream into OHLC bars.
+///
+/// Created by [`ohlc`].
+#[derive(Debug, Clone)]
+pub struct OhlcOperator<M, T> {
+    bars: Bars<M, T>,
+}
+
+impl<M, I, T> Operator<I> for OhlcOperator<M, T>
+where
+    M: TumblingWindow,
+    I: Tickable<Value = T>,
+    T: Copy + PartialOrd,
+{
+    type Output = TickValue<Ohlc<T>>;
+
+    fn next(&mut self, input: I) -> Self::Output {
+        let TickValue { tick, value } = input.into_tick_value();
+        self.bars.push(tick, Ohlc::flat(value));
+        TickValue {
+            tick,
+            value: *self.bars.current().expect("just pushed"),
+        }
+    }
+}
+
+impl<M: TumblingWindow, T: Copy + PartialOrd> Reset for OhlcOperator<M, T> {
+    fn reset(&mut self) {
+        self.bars.reset();
+    }
+}
+
+/// Create an operator that aggregates a tick stream into the OHLC bars
+/// of the windows defined by `mode`.
+///
+/// The output is the bar of the current window.
+pub fn ohlc<M, T>(mode: M) -> OhlcOperator<M, T>
+where
+    M: TumblingWindow,
+    T: Copy + PartialOrd,
+{
+    OhlcOperator {
+        bars: Bars::new(mode),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndicatorIteratorExt, Period};
+    use time::{macros::datetime, UtcOffset};
+
+    #[test]
+    fn aggregate() {
+        let bars = [
+            (datetime!(2023-01-01 00:00:00 UTC), 2),
+            (datetime!(2023-01-01 00:00:30 UTC), 4),
+            (datetime!(2023-01-01 00:00:59 UTC), 1),
+            (datetime!(2023-01-01 00:01:00 UTC), 3),
+        ]
+        .into_iter()
+        .map(|(ts, x)| TickValue::new(ts, x))
+        .indicator(ohlc(Period::minutes(UtcOffset::UTC, 1)))
+        .map(|x| x.value);
+        let expected = [
+            Ohlc::flat(2),
+            Ohlc {
+                open: 2,
+                high: 4,
+                low: 2,
+                close: 4,
+            },
+            Ohlc {
+                open: 2,
+                high: 4,
+                low: 1,
+                close: 1,
+            },
+            Ohlc::flat(3),
+        ];
+        for (bar, expected) in bars.zip(expected) {
+            assert_eq!(bar, expected);
+        }
+    }
+}
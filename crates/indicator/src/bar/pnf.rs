@@ -0,0 +1,212 @@
+use alloc::vec::Vec;
+use core::ops::{Add, Sub};
+
+use crate::{
+    reset::{Reset, Seed},
+    Operator, TickValue, Tickable,
+};
+
+use super::{Direction, Ohlc};
+
+/// A box of a Point-and-Figure chart.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PnfBox<T> {
+    /// The index of the column.
+    pub column: usize,
+    /// The direction of the column (`X` for up, `O` for down).
+    pub direction: Direction,
+    /// The price of the box.
+    pub price: T,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Column<T> {
+    index: usize,
+    direction: Direction,
+    last: T,
+}
+
+/// Operator that transforms OHLC bars into Point-and-Figure columns.
+///
+/// Created by [`point_and_figure`].
+#[derive(Debug, Clone)]
+pub struct PointAndFigure<T> {
+    size: T,
+    reversal: T,
+    base: Option<T>,
+    column: Option<Column<T>>,
+}
+
+impl<T> PointAndFigure<T>
+where
+    T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>,
+{
+    fn fill(&mut self, column: Column<T>, target: T, boxes: &mut Vec<PnfBox<T>>) {
+        let mut column = column;
+        loop {
+            let next = match column.direction {
+                Direction::Up if column.last + self.size <= target => column.last + self.size,
+                Direction::Down if column.last - self.size >= target => column.last - self.size,
+                _ => break,
+            };
+            column.last = next;
+            boxes.push(PnfBox {
+                column: column.index,
+                direction: column.direction,
+                price: next,
+            });
+        }
+        self.column = Some(column);
+    }
+
+    fn update(&mut self, bar: &Ohlc<T>) -> Vec<PnfBox<T>> {
+        let mut boxes = Vec::new();
+        let Some(column) = self.column else {
+            let Some(base) = self.base else {
+                self.base = Some(bar.close);
+                return boxes;
+            };
+            let (direction, target) = if bar.high >= base + self.size {
+                (Direction::Up, bar.high)
+            } else if bar.low <= base - self.size {
+                (Direction::Down, bar.low)
+            } else {
+                return boxes;
+            };
+            let column = Column {
+                index: 0,
+                direction,
+                last: base,
+            };
+            self.fill(column, target, &mut boxes);
+            return boxes;
+        };
+        // Extend the current column first, then check for a reversal.
+        let (target, reversed) = match column.direction {
+            Direction::Up => (bar.high, bar.low <= column.last - self.reversal),
+            Direction::Down => (bar.low, bar.high >= column.last + self.reversal),
+        };
+        self.fill(column, target, &mut boxes);
+        if boxes.is_empty() && reversed {
+            let (direction, target) = match column.direction {
+                Direction::Up => (Direction::Down, bar.low),
+                Direction::Down => (Direction::Up, bar.high),
+            };
+            let column = Column {
+                index: column.index + 1,
+                direction,
+                last: column.last,
+            };
+            self.fill(column, target, &mut boxes);
+        }
+        boxes
+    }
+}
+
+impl<I, T> Operator<I> for PointAndFigure<T>
+where
+    I: Tickable<Value = Ohlc<T>>,
+    T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>,
+{
+    type Output = TickValue<Vec<PnfBox<T>>>;
+
+    fn next(&mut self, input: I) -> Self::Output {
+        input.into_tick_value().map(|bar| self.update(&bar))
+    }
+}
+
+impl<T> Reset for PointAndFigure<T> {
+    fn reset(&mut self) {
+        self.base = None;
+        self.column = None;
+    }
+}
+
+/// Seed with the last box of the current column.
+impl<T: Copy> Seed<PnfBox<T>> for PointAndFigure<T> {
+    fn seed(&mut self, seed: PnfBox<T>) {
+        self.base = Some(seed.price);
+        self.column = Some(Column {
+            index: seed.column,
+            direction: seed.direction,
+            last: seed.price,
+        });
+    }
+}
+
+/// Create an operator that transforms OHLC bars into Point-and-Figure columns
+/// of boxes of `size`, using the high-low method.
+///
+/// A new column is started when the price reverses by at least `reversal` boxes.
+/// The output is the boxes filled by the input, which may be empty.
+/// Use [`OperatorExt::flatten`](crate::OperatorExt::flatten) to emit them one by one.
+///
+/// # Panics
+/// Panic if `reversal` is zero.
+pub fn point_and_figure<T>(size: T, reversal: usize) -> PointAndFigure<T>
+where
+    T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>,
+{
+    assert!(reversal > 0, "`reversal` must be positive");
+    let mut distance = size;
+    for _ in 1..reversal {
+        distance = distance + size;
+    }
+    PointAndFigure {
+        size,
+        reversal: distance,
+        base: None,
+        column: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IndicatorIteratorExt;
+    use alloc::vec;
+
+    #[test]
+    fn columns() {
+        let x = |column, price| PnfBox {
+            column,
+            direction: Direction::Up,
+            price,
+        };
+        let o = |column, price| PnfBox {
+            column,
+            direction: Direction::Down,
+            price,
+        };
+        let bar = |high, low| Ohlc {
+            open: low,
+            high,
+            low,
+            close: high,
+        };
+        let boxes = [
+            Ohlc::flat(10),
+            bar(12, 10),
+            bar(13, 11),
+            bar(12, 9),
+            bar(11, 7),
+            bar(11, 9),
+        ]
+        .into_iter()
+        .map(TickValue::big_bang)
+        .indicator(point_and_figure(1, 3))
+        .map(|x| x.value);
+        let expected = [
+            vec![],
+            vec![x(0, 11), x(0, 12)],
+            vec![x(0, 13)],
+            vec![o(1, 12), o(1, 11), o(1, 10), o(1, 9)],
+            vec![o(1, 8), o(1, 7)],
+            vec![x(2, 8), x(2, 9), x(2, 10), x(2, 11)],
+        ];
+        for (boxes, expected) in boxes.zip(expected) {
+            assert_eq!(boxes, expected);
+        }
+    }
+}
@@ -0,0 +1,288 @@
+use alloc::{collections::VecDeque, vec::Vec};
+use core::ops::{Add, Div, Sub};
+
+use crate::{
+    reset::{Reset, Seed},
+    Operator, Tick, TickValue, Tickable, TumblingWindow,
+};
+
+use super::{Bars, Direction, Ohlc};
+
+/// A Renko brick.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Brick<T> {
+    /// Direction.
+    pub direction: Direction,
+    /// Open price.
+    pub open: T,
+    /// Close price.
+    pub close: T,
+}
+
+/// The size of the bricks.
+pub trait BrickSize<T> {
+    /// Observe a new bar and return the current size (if it is available).
+    fn update(&mut self, tick: Tick, bar: &Ohlc<T>) -> Option<T>;
+}
+
+/// Fixed brick size.
+#[derive(Debug, Clone, Copy)]
+pub struct Fixed<T>(pub T);
+
+impl<T: Copy> BrickSize<T> for Fixed<T> {
+    fn update(&mut self, _tick: Tick, _bar: &Ohlc<T>) -> Option<T> {
+        Some(self.0)
+    }
+}
+
+impl<T> Reset for Fixed<T> {
+    fn reset(&mut self) {}
+}
+
+/// Brick size given by the average true range of the last confirmed bars.
+#[derive(Debug, Clone)]
+pub struct Atr<M, T> {
+    bars: Bars<M, T>,
+    length: usize,
+    prev_close: Option<T>,
+    ranges: VecDeque<T>,
+}
+
+impl<M, T> BrickSize<T> for Atr<M, T>
+where
+    M: TumblingWindow,
+    T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T> + Div<Output = T> + From<u8>,
+{
+    fn update(&mut self, tick: Tick, bar: &Ohlc<T>) -> Option<T> {
+        if let Some(confirmed) = self.bars.push(tick, *bar) {
+            let mut range = confirmed.high - confirmed.low;
+            if let Some(prev) = self.prev_close.replace(confirmed.close) {
+                for gap in [confirmed.high - prev, prev - confirmed.low] {
+                    if gap > range {
+                        range = gap;
+                    }
+                }
+            }
+            self.ranges.push_back(range);
+            if self.ranges.len() > self.length {
+                self.ranges.pop_front();
+            }
+        }
+        if self.ranges.len() < self.length {
+            return None;
+        }
+        let mut sum = T::from(0);
+        let mut n = T::from(0);
+        for range in self.ranges.iter() {
+            sum = sum + *range;
+            n = n + T::from(1);
+        }
+        Some(sum / n)
+    }
+}
+
+impl<M: TumblingWindow, T: Copy + PartialOrd> Reset for Atr<M, T> {
+    fn reset(&mut self) {
+        self.bars.reset();
+        self.prev_close = None;
+        self.ranges.clear();
+    }
+}
+
+/// Operator that transforms OHLC bars into Renko bricks.
+///
+/// Created by [`renko`] or [`renko_atr`].
+#[derive(Debug, Clone)]
+pub struct Renko<S, T> {
+    size: S,
+    base: Option<T>,
+    last: Option<Brick<T>>,
+}
+
+impl<S, T> Renko<S, T>
+where
+    T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T> + From<u8>,
+{
+    fn update(&mut self, price: T, size: Option<T>) -> Vec<Brick<T>> {
+        let mut bricks = Vec::new();
+        let Some(base) = self.base else {
+            self.base = Some(price);
+            return bricks;
+        };
+        let Some(size) = size.filter(|size| *size > T::from(0)) else {
+            return bricks;
+        };
+        loop {
+            let (up, down) = match &self.last {
+                None => (base, base),
+                Some(last) if last.direction == Direction::Up => (last.close, last.open),
+                Some(last) => (last.open, last.close),
+            };
+            let brick = if price >= up + size {
+                Brick {
+                    direction: Direction::Up,
+                    open: up,
+                    close: up + size,
+                }
+            } else if price <= down - size {
+                Brick {
+                    direction: Direction::Down,
+                    open: down,
+                    close: down - size,
+                }
+            } else {
+                break;
+            };
+            self.last = Some(brick);
+            bricks.push(brick);
+        }
+        bricks
+    }
+}
+
+impl<S, I, T> Operator<I> for Renko<S, T>
+where
+    S: BrickSize<T>,
+    I: Tickable<Value = Ohlc<T>>,
+    T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T> + From<u8>,
+{
+    type Output = TickValue<Vec<Brick<T>>>;
+
+    fn next(&mut self, input: I) -> Self::Output {
+        let TickValue { tick, value } = input.into_tick_value();
+        let size = self.size.update(tick, &value);
+        TickValue {
+            tick,
+            value: self.update(value.close, size),
+        }
+    }
+}
+
+impl<S: Reset, T> Reset for Renko<S, T> {
+    fn reset(&mut self) {
+        self.size.reset();
+        self.base = None;
+        self.last = None;
+    }
+}
+
+/// Seed with the last brick.
+impl<S: Reset, T: Copy> Seed<Brick<T>> for Renko<S, T> {
+    fn seed(&mut self, seed: Brick<T>) {
+        self.reset();
+        self.base = Some(seed.open);
+        self.last = Some(seed);
+    }
+}
+
+/// Create an operator that transforms OHLC bars into Renko bricks of fixed `size`,
+/// using the close prices.
+///
+/// A reversal takes two bricks of price movement.
+/// The output is the bricks finished by the input, which may be empty.
+/// Use [`OperatorExt::flatten`](crate::OperatorExt::flatten) to emit them one by one.
+pub fn renko<T>(size: T) -> Renko<Fixed<T>, T>
+where
+    T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T> + From<u8>,
+{
+    Renko {
+        size: Fixed(size),
+        base: None,
+        last: None,
+    }
+}
+
+/// Create an operator that transforms OHLC bars into Renko bricks,
+/// sized by the average true range of the last `length` confirmed bars
+/// of the windows defined by `mode`.
+///
+/// No bricks are emitted until the average true range is available.
+pub fn renko_atr<M, T>(mode: M, length: usize) -> Renko<Atr<M, T>, T>
+where
+    M: TumblingWindow,
+    T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T> + Div<Output = T> + From<u8>,
+{
+    let length = length.max(1);
+    Renko {
+        size: Atr {
+            bars: Bars::new(mode),
+            length,
+            prev_close: None,
+            ranges: VecDeque::with_capacity(length + 1),
+        },
+        base: None,
+        last: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndicatorIteratorExt, Period};
+    use alloc::vec;
+    use time::macros::datetime;
+
+    fn up(open: i64, close: i64) -> Brick<i64> {
+        Brick {
+            direction: Direction::Up,
+            open,
+            close,
+        }
+    }
+
+    fn down(open: i64, close: i64) -> Brick<i64> {
+        Brick {
+            direction: Direction::Down,
+            open,
+            close,
+        }
+    }
+
+    #[test]
+    fn fixed() {
+        let bricks = [10, 12, 15, 13, 11, 10, 16]
+            .into_iter()
+            .map(|x| TickValue::big_bang(Ohlc::flat(x)))
+            .indicator(renko(2))
+            .map(|x| x.value);
+        let expected = [
+            vec![],
+            vec![up(10, 12)],
+            vec![up(12, 14)],
+            vec![],
+            vec![],
+            vec![down(12, 10)],
+            vec![up(12, 14), up(14, 16)],
+        ];
+        for (bricks, expected) in bricks.zip(expected) {
+            assert_eq!(bricks, expected);
+        }
+    }
+
+    #[test]
+    fn atr() {
+        let base = datetime!(2023-01-01 00:00:00 UTC);
+        let bar = |open, high, low, close| Ohlc {
+            open,
+            high,
+            low,
+            close,
+        };
+        let bricks = [
+            bar(10, 12, 8, 10),
+            bar(10, 14, 10, 12),
+            bar(12, 13, 11, 12),
+            bar(12, 16, 12, 16),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(idx, x)| TickValue::new(base + time::Duration::seconds(idx as i64), x))
+        .indicator(renko_atr(Period::secs(1), 2))
+        .map(|x| x.value);
+        let expected = [vec![], vec![], vec![], vec![up(10, 13), up(13, 16)]];
+        for (bricks, expected) in bricks.zip(expected) {
+            assert_eq!(bricks, expected);
+        }
+    }
+}
@@ -0,0 +1,310 @@
+use alloc::vec::Vec;
+use time::OffsetDateTime;
+
+use crate::{Operator, Tick, TickValue, Tickable};
+
+use super::{Bitmap, Column, Primitive};
+
+/// Value that can be stored in a row of [`Columns`].
+pub trait Columnar: Sized {
+    /// The columns storing the values.
+    type Columns: Columns;
+
+    /// Read the value of the given row, `None` if it is null.
+    fn read(columns: &Self::Columns, row: usize) -> Option<Self>;
+
+    /// Append the value as a new row.
+    fn write(self, columns: &mut Self::Columns);
+}
+
+/// A group of columns of the same length.
+pub trait Columns: Default {
+    /// The number of rows.
+    fn len(&self) -> usize;
+
+    /// Is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Append a null row.
+    fn push_null(&mut self);
+
+    /// Export the columns as Arrow arrays (with their formats), in order.
+    fn export(self, arrays: &mut Vec<(super::ArrowArray, &'static str)>);
+}
+
+impl<T: Primitive> Columns for Column<T> {
+    fn len(&self) -> usize {
+        Column::len(self)
+    }
+
+    fn push_null(&mut self) {
+        self.push(None);
+    }
+
+    fn export(self, arrays: &mut Vec<(super::ArrowArray, &'static str)>) {
+        arrays.push((super::ffi::primitive(self), T::FORMAT));
+    }
+}
+
+impl<T: Primitive> Columnar for T {
+    type Columns = Column<T>;
+
+    fn read(columns: &Self::Columns, row: usize) -> Option<Self> {
+        columns.get(row)
+    }
+
+    fn write(self, columns: &mut Self::Columns) {
+        columns.push(Some(self));
+    }
+}
+
+/// Nullable value, a `None` is stored as a null but is still a valid row.
+impl<T: Primitive> Columnar for Option<T> {
+    type Columns = Column<T>;
+
+    fn read(columns: &Self::Columns, row: usize) -> Option<Self> {
+        (row < columns.len()).then(|| columns.get(row))
+    }
+
+    fn write(self, columns: &mut Self::Columns) {
+        columns.push(self);
+    }
+}
+
+macro_rules! tuple {
+    ($($ty:ident => $idx:tt),+) => {
+        impl<$($ty: Columns),+> Columns for ($($ty,)+) {
+            fn len(&self) -> usize {
+                self.0.len()
+            }
+
+            fn push_null(&mut self) {
+                $(self.$idx.push_null();)+
+            }
+
+            fn export(self, arrays: &mut Vec<(super::ArrowArray, &'static str)>) {
+                $(self.$idx.export(arrays);)+
+            }
+        }
+
+        impl<$($ty: Columnar),+> Columnar for ($($ty,)+) {
+            type Columns = ($($ty::Columns,)+);
+
+            fn read(columns: &Self::Columns, row: usize) -> Option<Self> {
+                Some(($($ty::read(&columns.$idx, row)?,)+))
+            }
+
+            fn write(self, columns: &mut Self::Columns) {
+                $(self.$idx.write(&mut columns.$idx);)+
+            }
+        }
+    };
+}
+
+tuple!(A => 0);
+tuple!(A => 0, B => 1);
+tuple!(A => 0, B => 1, C => 2);
+tuple!(A => 0, B => 1, C => 2, D => 3);
+tuple!(A => 0, B => 1, C => 2, D => 3, E => 4);
+tuple!(A => 0, B => 1, C => 2, D => 3, E => 4, F => 5);
+
+/// Convert a tick to a timestamp in nanoseconds since the Unix epoch,
+/// `None` for [`Tick::BIG_BANG`] and for ticks out of the range of `i64`.
+pub fn timestamp(tick: Tick) -> Option<i64> {
+    tick.ts()
+        .and_then(|ts| i64::try_from(ts.unix_timestamp_nanos()).ok())
+}
+
+/// Convert a timestamp in nanoseconds since the Unix epoch to a tick,
+/// `None` for [`Tick::BIG_BANG`].
+pub fn tick(timestamp: Option<i64>) -> Tick {
+    timestamp
+        .and_then(|ts| OffsetDateTime::from_unix_timestamp_nanos(ts.into()).ok())
+        .map_or(Tick::BIG_BANG, Tick::new)
+}
+
+/// A batch of rows in the columnar layout:
+/// a timestamp column (nanoseconds since the Unix epoch, UTC, null for [`Tick::BIG_BANG`])
+/// and the value columns.
+#[derive(Debug, Clone)]
+pub struct Batch<T: Columnar> {
+    timestamps: Column<i64>,
+    columns: T::Columns,
+}
+
+impl<T: Columnar> Default for Batch<T> {
+    fn default() -> Self {
+        Self {
+            timestamps: Column::new(),
+            columns: T::Columns::default(),
+        }
+    }
+}
+
+impl<T: Columnar> Batch<T> {
+    /// Create a batch from the timestamp column and the value columns.
+    /// # Panic
+    /// Panic if the columns are not of the same length.
+    pub fn new(timestamps: Column<i64>, columns: T::Columns) -> Self {
+        assert_eq!(
+            timestamps.len(),
+            columns.len(),
+            "columns must be of the same length"
+        );
+        Self {
+            timestamps,
+            columns,
+        }
+    }
+
+    /// The number of rows.
+    pub fn len(&self) -> usize {
+        self.timestamps.len()
+    }
+
+    /// Is empty.
+    pub fn is_empty(&self) -> bool {
+        self.timestamps.is_empty()
+    }
+
+    /// The timestamp column.
+    pub fn timestamps(&self) -> &Column<i64> {
+        &self.timestamps
+    }
+
+    /// The value columns.
+    pub fn columns(&self) -> &T::Columns {
+        &self.columns
+    }
+
+    /// Split into the timestamp column and the value columns.
+    pub fn into_parts(self) -> (Column<i64>, T::Columns) {
+        (self.timestamps, self.columns)
+    }
+
+    /// Append a row.
+    pub fn push(&mut self, row: TickValue<T>) {
+        self.timestamps.push(timestamp(row.tick));
+        row.value.write(&mut self.columns);
+    }
+
+    /// Append a null row.
+    pub fn push_null(&mut self, tick: Tick) {
+        self.timestamps.push(timestamp(tick));
+        self.columns.push_null();
+    }
+
+    /// Get the row at `idx`, `None` if it is null or out of range.
+    pub fn get(&self, idx: usize) -> Option<TickValue<T>> {
+        let value = T::read(&self.columns, idx)?;
+        Some(TickValue {
+            tick: tick(self.timestamps.get(idx)),
+            value,
+        })
+    }
+
+    /// Iterate over the rows, `None` for the null rows.
+    pub fn rows(&self) -> impl Iterator<Item = Option<TickValue<T>>> + '_ {
+        (0..self.len()).map(|idx| self.get(idx))
+    }
+
+    /// Run the operator over the non-null rows of the batch with [`Operator::drain_batch`],
+    /// and collect the outputs into a new batch.
+    ///
+    /// The output batch is aligned with the input: the null rows stay null
+    /// (keeping their timestamps), and the others are filled with the outputs.
+    pub fn evaluate<P, O>(&self, mut op: P) -> Batch<O>
+    where
+        P: Operator<TickValue<T>>,
+        P::Output: Tickable<Value = O>,
+        O: Columnar,
+    {
+        let mut valid = Bitmap::new();
+        let mut inputs = Vec::with_capacity(self.len());
+        for row in self.rows() {
+            valid.push(row.is_some());
+            inputs.extend(row);
+        }
+        let mut outputs = Vec::with_capacity(inputs.len());
+        op.drain_batch(&mut inputs, &mut outputs);
+        let mut outputs = outputs.into_iter().map(Tickable::into_tick_value);
+        let mut batch = Batch::default();
+        for idx in 0..self.len() {
+            match valid.get(idx).then(|| outputs.next()).flatten() {
+                Some(output) => batch.push(output),
+                None => batch.push_null(tick(self.timestamps.get(idx))),
+            }
+        }
+        batch
+    }
+}
+
+impl<T: Columnar> FromIterator<TickValue<T>> for Batch<T> {
+    fn from_iter<It: IntoIterator<Item = TickValue<T>>>(iter: It) -> Self {
+        let mut batch = Self::default();
+        for row in iter {
+            batch.push(row);
+        }
+        batch
+    }
+}
+
+impl<T: Columnar> Extend<TickValue<T>> for Batch<T> {
+    fn extend<It: IntoIterator<Item = TickValue<T>>>(&mut self, iter: It) {
+        for row in iter {
+            self.push(row);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{map_t, rolling::rolling_sum};
+    use alloc::vec::Vec;
+    use time::{macros::datetime, Duration};
+
+    fn batch() -> Batch<(f64, i64)> {
+        let base = datetime!(2023-01-01 00:00:00 UTC);
+        let mut batch = Batch::default();
+        for (idx, (x, y)) in [(1.0, 1), (2.0, 2), (3.0, 3), (4.0, 4)]
+            .into_iter()
+            .enumerate()
+        {
+            let ts = base + Duration::seconds(idx as i64);
+            if idx == 1 {
+                batch.push_null(Tick::new(ts));
+            } else {
+                batch.push(TickValue::new(ts, (x, y)));
+            }
+        }
+        batch
+    }
+
+    #[test]
+    fn evaluate() {
+        let batch = batch();
+        assert_eq!(batch.columns().0.null_count(), 1);
+        let mut sum = rolling_sum(2);
+        let output = batch.evaluate(map_t(move |(x, y): (f64, i64)| sum.next(x * y as f64)));
+        assert_eq!(output.len(), 4);
+        assert_eq!(output.timestamps(), batch.timestamps());
+        assert_eq!(
+            output.columns().iter().collect::<Vec<_>>(),
+            [Some(1.0), None, Some(10.0), Some(25.0)]
+        );
+    }
+
+    #[test]
+    fn nullable() {
+        let batch: Batch<Option<i64>> = [Some(1), None, Some(3)]
+            .into_iter()
+            .map(TickValue::big_bang)
+            .collect();
+        assert!(batch.timestamps().iter().all(|ts| ts.is_none()));
+        let output = batch.evaluate(map_t(|x: Option<i64>| x.is_some() as u8));
+        assert_eq!(output.columns().values(), [1, 0, 1]);
+    }
+}
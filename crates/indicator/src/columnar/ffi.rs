@@ -0,0 +1,312 @@
+use alloc::{boxed::Box, ffi::CString, vec, vec::Vec};
+use core::{
+    any::Any,
+    ffi::{c_char, c_void},
+    ptr,
+};
+
+use super::{Batch, Column, Columnar, Columns, Primitive};
+
+/// The field is nullable.
+pub const ARROW_FLAG_NULLABLE: i64 = 2;
+
+/// The `ArrowSchema` struct of the [Arrow C data interface].
+///
+/// The exported schema owns its strings and children,
+/// they are freed by calling `release` (which is done on drop if it is still set).
+/// To move the struct to a consumer, write it to the consumer-provided location
+/// with [`ptr::write`] (so that it is not dropped).
+///
+/// [Arrow C data interface]: https://arrow.apache.org/docs/format/CDataInterface.html
+#[repr(C)]
+#[derive(Debug)]
+pub struct ArrowSchema {
+    /// Format string.
+    pub format: *const c_char,
+    /// Field name.
+    pub name: *const c_char,
+    /// Metadata.
+    pub metadata: *const c_char,
+    /// Flags.
+    pub flags: i64,
+    /// The number of children.
+    pub n_children: i64,
+    /// Children.
+    pub children: *mut *mut ArrowSchema,
+    /// Dictionary.
+    pub dictionary: *mut ArrowSchema,
+    /// Release callback, `None` if released.
+    pub release: Option<unsafe extern "C" fn(*mut ArrowSchema)>,
+    /// Producer-specific data.
+    pub private_data: *mut c_void,
+}
+
+/// The `ArrowArray` struct of the [Arrow C data interface].
+///
+/// The exported array owns its buffers and children,
+/// they are freed by calling `release` (which is done on drop if it is still set).
+/// To move the struct to a consumer, write it to the consumer-provided location
+/// with [`ptr::write`] (so that it is not dropped).
+///
+/// [Arrow C data interface]: https://arrow.apache.org/docs/format/CDataInterface.html
+#[repr(C)]
+#[derive(Debug)]
+pub struct ArrowArray {
+    /// The number of rows.
+    pub length: i64,
+    /// The number of nulls.
+    pub null_count: i64,
+    /// The logical offset.
+    pub offset: i64,
+    /// The number of buffers.
+    pub n_buffers: i64,
+    /// The number of children.
+    pub n_children: i64,
+    /// Buffers.
+    pub buffers: *mut *const c_void,
+    /// Children.
+    pub children: *mut *mut ArrowArray,
+    /// Dictionary.
+    pub dictionary: *mut ArrowArray,
+    /// Release callback, `None` if released.
+    pub release: Option<unsafe extern "C" fn(*mut ArrowArray)>,
+    /// Producer-specific data.
+    pub private_data: *mut c_void,
+}
+
+impl ArrowSchema {
+    /// Is released.
+    pub fn is_released(&self) -> bool {
+        self.release.is_none()
+    }
+}
+
+impl ArrowArray {
+    /// Is released.
+    pub fn is_released(&self) -> bool {
+        self.release.is_none()
+    }
+}
+
+impl Drop for ArrowSchema {
+    fn drop(&mut self) {
+        if let Some(release) = self.release {
+            // SAFETY: the schema is valid until released.
+            unsafe { release(self) }
+        }
+    }
+}
+
+impl Drop for ArrowArray {
+    fn drop(&mut self) {
+        if let Some(release) = self.release {
+            // SAFETY: the array is valid until released.
+            unsafe { release(self) }
+        }
+    }
+}
+
+struct SchemaData {
+    _format: CString,
+    _name: CString,
+    children: Vec<*mut ArrowSchema>,
+}
+
+struct ArrayData {
+    _owner: Box<dyn Any>,
+    _buffers: Vec<*const c_void>,
+    children: Vec<*mut ArrowArray>,
+}
+
+unsafe extern "C" fn release_schema(schema: *mut ArrowSchema) {
+    let Some(schema) = schema.as_mut() else {
+        return;
+    };
+    if schema.release.is_some() {
+        let data = Box::from_raw(schema.private_data as *mut SchemaData);
+        for child in data.children {
+            drop(Box::from_raw(child));
+        }
+        schema.private_data = ptr::null_mut();
+        schema.release = None;
+    }
+}
+
+unsafe extern "C" fn release_array(array: *mut ArrowArray) {
+    let Some(array) = array.as_mut() else {
+        return;
+    };
+    if array.release.is_some() {
+        let data = Box::from_raw(array.private_data as *mut ArrayData);
+        for child in data.children {
+            drop(Box::from_raw(child));
+        }
+        array.private_data = ptr::null_mut();
+        array.release = None;
+    }
+}
+
+fn schema(format: &str, name: &str, flags: i64, children: Vec<ArrowSchema>) -> ArrowSchema {
+    let format = CString::new(format).expect("format cannot contain NUL");
+    let name = CString::new(name).expect("name cannot contain NUL");
+    let mut data = Box::new(SchemaData {
+        children: children
+            .into_iter()
+            .map(|c| Box::into_raw(Box::new(c)))
+            .collect(),
+        _format: format,
+        _name: name,
+    });
+    ArrowSchema {
+        format: data._format.as_ptr(),
+        name: data._name.as_ptr(),
+        metadata: ptr::null(),
+        flags,
+        n_children: data.children.len() as i64,
+        children: data.children.as_mut_ptr(),
+        dictionary: ptr::null_mut(),
+        release: Some(release_schema),
+        private_data: Box::into_raw(data) as *mut c_void,
+    }
+}
+
+fn array(
+    length: usize,
+    null_count: usize,
+    owner: Box<dyn Any>,
+    buffers: Vec<*const c_void>,
+    children: Vec<ArrowArray>,
+) -> ArrowArray {
+    let mut data = Box::new(ArrayData {
+        _owner: owner,
+        _buffers: buffers,
+        children: children
+            .into_iter()
+            .map(|c| Box::into_raw(Box::new(c)))
+            .collect(),
+    });
+    ArrowArray {
+        length: length as i64,
+        null_count: null_count as i64,
+        offset: 0,
+        n_buffers: data._buffers.len() as i64,
+        n_children: data.children.len() as i64,
+        buffers: data._buffers.as_mut_ptr(),
+        children: data.children.as_mut_ptr(),
+        dictionary: ptr::null_mut(),
+        release: Some(release_array),
+        private_data: Box::into_raw(data) as *mut c_void,
+    }
+}
+
+/// Export a primitive column, moving its buffers without copying.
+pub(crate) fn primitive<T: Primitive>(column: Column<T>) -> ArrowArray {
+    let length = column.len();
+    let null_count = column.null_count();
+    let (values, validity) = column.into_parts();
+    let validity = validity.filter(|_| null_count > 0);
+    let buffers = vec![
+        validity.as_ref().map_or(ptr::null(), |bitmap| {
+            bitmap.as_bytes().as_ptr() as *const c_void
+        }),
+        values.as_ptr() as *const c_void,
+    ];
+    array(
+        length,
+        null_count,
+        Box::new((values, validity)),
+        buffers,
+        Vec::new(),
+    )
+}
+
+impl<T: Columnar> Batch<T> {
+    /// Export the batch as a struct array with the [Arrow C data interface].
+    ///
+    /// The first child is the timestamp column (format `tsn:UTC`) named `timestamp`,
+    /// followed by the value columns named by `names`
+    /// (the unnamed columns are named by their positions).
+    /// The buffers are moved into the exported array without copying.
+    ///
+    /// # Panic
+    /// Panic if a name contains a NUL byte.
+    ///
+    /// [Arrow C data interface]: https://arrow.apache.org/docs/format/CDataInterface.html
+    pub fn into_arrow(self, names: &[&str]) -> (ArrowArray, ArrowSchema) {
+        let length = self.len();
+        let (timestamps, columns) = self.into_parts();
+        let mut children = vec![(primitive(timestamps), "tsn:UTC")];
+        columns.export(&mut children);
+        let mut schemas = Vec::with_capacity(children.len());
+        let mut arrays = Vec::with_capacity(children.len());
+        for (idx, (array, format)) in children.into_iter().enumerate() {
+            let name = match idx {
+                0 => "timestamp".into(),
+                idx => names
+                    .get(idx - 1)
+                    .map_or_else(|| alloc::format!("{}", idx - 1), |name| (*name).into()),
+            };
+            schemas.push(schema(format, &name, ARROW_FLAG_NULLABLE, Vec::new()));
+            arrays.push(array);
+        }
+        (
+            array(length, 0, Box::new(()), vec![ptr::null()], arrays),
+            schema("+s", "", 0, schemas),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Tick, TickValue};
+    use alloc::string::String;
+    use core::ffi::CStr;
+
+    unsafe fn str(ptr: *const c_char) -> String {
+        CStr::from_ptr(ptr).to_str().unwrap().into()
+    }
+
+    #[test]
+    fn export() {
+        let mut batch: Batch<(i64, f64)> = [(1, 1.5), (2, 2.5)]
+            .into_iter()
+            .map(TickValue::big_bang)
+            .collect();
+        batch.push_null(Tick::BIG_BANG);
+        let (array, schema) = batch.into_arrow(&["x"]);
+        assert_eq!(array.length, 3);
+        assert_eq!(array.n_children, 3);
+        assert_eq!(schema.n_children, 3);
+        unsafe {
+            assert_eq!(str(schema.format), "+s");
+            let children = core::slice::from_raw_parts(schema.children, 3);
+            let fields = children
+                .iter()
+                .map(|child| (str((**child).format), str((**child).name)))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                fields,
+                [
+                    ("tsn:UTC".into(), "timestamp".into()),
+                    ("l".into(), "x".into()),
+                    ("g".into(), "1".into())
+                ]
+            );
+            let children = core::slice::from_raw_parts(array.children, 3);
+            let timestamps = &*children[0];
+            assert_eq!(timestamps.null_count, 3);
+            let x = &*children[1];
+            assert_eq!(x.null_count, 1);
+            let buffers = core::slice::from_raw_parts(x.buffers, 2);
+            assert_eq!(*(buffers[0] as *const u8), 0b011);
+            assert_eq!(
+                core::slice::from_raw_parts(buffers[1] as *const i64, 3),
+                [1, 2, 0]
+            );
+        }
+        let mut array = array;
+        unsafe { release_array(&mut array) };
+        assert!(array.is_released());
+    }
+}
@@ -0,0 +1,232 @@
+use alloc::vec::Vec;
+
+/// Batches of rows.
+pub mod batch;
+
+/// Arrow C data interface.
+pub mod ffi;
+
+pub use batch::{Batch, Columnar, Columns};
+pub use ffi::{ArrowArray, ArrowSchema};
+
+/// Primitive type that can be stored in a [`Column`].
+pub trait Primitive: Copy + Default + 'static {
+    /// The format string of the type in the Arrow C data interface.
+    const FORMAT: &'static str;
+}
+
+macro_rules! primitive {
+    ($($ty:ty => $format:literal),*) => {
+        $(
+            impl Primitive for $ty {
+                const FORMAT: &'static str = $format;
+            }
+        )*
+    };
+}
+
+primitive!(
+    i8 => "c", i16 => "s", i32 => "i", i64 => "l",
+    u8 => "C", u16 => "S", u32 => "I", u64 => "L",
+    f32 => "f", f64 => "g"
+);
+
+/// Validity bitmap, in the Arrow layout (least significant bit first, `1` for valid).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Bitmap {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl Bitmap {
+    /// Create an empty bitmap.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a bitmap with `len` bits set to `bit`.
+    pub fn with_len(len: usize, bit: bool) -> Self {
+        let mut bitmap = Self {
+            bytes: Vec::with_capacity((len + 7) / 8),
+            len: 0,
+        };
+        for _ in 0..len {
+            bitmap.push(bit);
+        }
+        bitmap
+    }
+
+    /// The number of bits.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Push a bit.
+    pub fn push(&mut self, bit: bool) {
+        if self.len % 8 == 0 {
+            self.bytes.push(0);
+        }
+        if bit {
+            self.bytes[self.len / 8] |= 1 << (self.len % 8);
+        }
+        self.len += 1;
+    }
+
+    /// Get the bit at `idx`.
+    /// # Panic
+    /// Panic if `idx` is out of range.
+    pub fn get(&self, idx: usize) -> bool {
+        assert!(idx < self.len, "index out of range");
+        self.bytes[idx / 8] & (1 << (idx % 8)) != 0
+    }
+
+    /// The number of unset bits.
+    pub fn count_unset(&self) -> usize {
+        (0..self.len).filter(|idx| !self.get(*idx)).count()
+    }
+
+    /// The underlying bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+/// A column of primitive values, in the Arrow layout:
+/// a contiguous buffer of values and an optional validity bitmap.
+///
+/// The slots of the null values are filled with `T::default()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column<T> {
+    values: Vec<T>,
+    validity: Option<Bitmap>,
+}
+
+impl<T> Default for Column<T> {
+    fn default() -> Self {
+        Self {
+            values: Vec::new(),
+            validity: None,
+        }
+    }
+}
+
+impl<T: Primitive> Column<T> {
+    /// Create an empty column.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty column with the given capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            values: Vec::with_capacity(capacity),
+            validity: None,
+        }
+    }
+
+    /// The number of rows.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Is empty.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// The number of nulls.
+    pub fn null_count(&self) -> usize {
+        self.validity.as_ref().map_or(0, Bitmap::count_unset)
+    }
+
+    /// Push a value, `None` for null.
+    pub fn push(&mut self, value: Option<T>) {
+        match value {
+            Some(value) => {
+                self.values.push(value);
+                if let Some(validity) = self.validity.as_mut() {
+                    validity.push(true);
+                }
+            }
+            None => {
+                self.validity
+                    .get_or_insert_with(|| Bitmap::with_len(self.values.len(), true))
+                    .push(false);
+                self.values.push(T::default());
+            }
+        }
+    }
+
+    /// Get the value of the given row, `None` if it is null or out of range.
+    pub fn get(&self, row: usize) -> Option<T> {
+        let value = self.values.get(row)?;
+        match &self.validity {
+            Some(validity) if !validity.get(row) => None,
+            _ => Some(*value),
+        }
+    }
+
+    /// The values buffer (including the slots of nulls).
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// The validity bitmap, `None` if there is no null.
+    pub fn validity(&self) -> Option<&Bitmap> {
+        self.validity.as_ref()
+    }
+
+    /// Iterate over the rows.
+    pub fn iter(&self) -> impl Iterator<Item = Option<T>> + '_ {
+        (0..self.len()).map(|row| self.get(row))
+    }
+
+    pub(crate) fn into_parts(self) -> (Vec<T>, Option<Bitmap>) {
+        (self.values, self.validity)
+    }
+}
+
+impl<T: Primitive> From<Vec<T>> for Column<T> {
+    fn from(values: Vec<T>) -> Self {
+        Self {
+            values,
+            validity: None,
+        }
+    }
+}
+
+impl<T: Primitive> FromIterator<Option<T>> for Column<T> {
+    fn from_iter<It: IntoIterator<Item = Option<T>>>(iter: It) -> Self {
+        let mut column = Self::new();
+        for value in iter {
+            column.push(value);
+        }
+        column
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn column() {
+        let mut column = Column::from(alloc::vec![1i64, 2, 3]);
+        assert!(column.validity().is_none());
+        column.push(None);
+        column.push(Some(5));
+        assert_eq!(column.len(), 5);
+        assert_eq!(column.null_count(), 1);
+        assert_eq!(column.values(), [1, 2, 3, 0, 5]);
+        assert_eq!(column.validity().unwrap().as_bytes(), [0b10111]);
+        assert_eq!(
+            column.iter().collect::<Vec<_>>(),
+            [Some(1), Some(2), Some(3), None, Some(5)]
+        );
+    }
+}
@@ -31,6 +31,8 @@
 // DEALINGS IN THE SOFTWARE.
 
 use alloc::boxed::Box;
+#[cfg(feature = "profile")]
+use core::any::type_name;
 use core::{
     any::{Any, TypeId},
     fmt,
@@ -38,7 +40,38 @@ use core::{
 };
 use hashbrown::HashMap;
 
-type AnyMap = HashMap<TypeId, Box<dyn Any + Send + Sync>, BuildHasherDefault<IdHasher>>;
+use super::key::{Key, Keyed};
+
+type AnyMap = HashMap<TypeId, Entry, BuildHasherDefault<IdHasher>>;
+
+// The name of the type is kept for the profiler.
+struct Entry {
+    #[cfg(feature = "profile")]
+    name: &'static str,
+    value: Box<dyn Any + Send + Sync>,
+}
+
+impl Entry {
+    fn new<T: Send + Sync + 'static>(value: T) -> Self {
+        Self::boxed(Box::new(value))
+    }
+
+    fn boxed<T: Send + Sync + 'static>(value: Box<T>) -> Self {
+        Self {
+            #[cfg(feature = "profile")]
+            name: type_name::<T>(),
+            value,
+        }
+    }
+
+    fn into_value<T: Send + Sync + 'static>(self) -> Option<T> {
+        self.into_boxed().map(|value| *value)
+    }
+
+    fn into_boxed<T: Send + Sync + 'static>(self) -> Option<Box<T>> {
+        self.value.downcast().ok()
+    }
+}
 
 // With TypeIds as keys, there's no need to hash them. They are already hashes
 // themselves, coming from the compiler. The IdHasher just holds the u64 of
@@ -69,7 +102,7 @@ pub struct Map(Option<Box<AnyMap>>);
 impl Map {
     /// Create an empty `Context`.
     #[inline]
-    pub fn new() -> Self {
+    pub const fn new() -> Self {
         Self(None)
     }
 
@@ -77,8 +110,8 @@ impl Map {
     pub fn insert<T: Send + Sync + 'static>(&mut self, value: T) -> Option<T> {
         self.0
             .get_or_insert_with(Default::default)
-            .insert(TypeId::of::<T>(), Box::new(value))
-            .and_then(|boxed| boxed.downcast().ok().map(|v| *v))
+            .insert(TypeId::of::<T>(), Entry::new(value))
+            .and_then(Entry::into_value)
     }
 
     /// Remove a type from the `Context`.
@@ -86,7 +119,22 @@ impl Map {
         self.0
             .as_mut()
             .and_then(|map| map.remove(&TypeId::of::<T>()))
-            .and_then(|boxed| boxed.downcast().ok().map(|v| *v))
+            .and_then(Entry::into_value)
+    }
+
+    /// Insert a boxed value, reusing its allocation.
+    pub(crate) fn insert_boxed<T: Send + Sync + 'static>(&mut self, value: Box<T>) {
+        self.0
+            .get_or_insert_with(Default::default)
+            .insert(TypeId::of::<T>(), Entry::boxed(value));
+    }
+
+    /// Remove a type, keeping it boxed.
+    pub(crate) fn remove_boxed<T: Send + Sync + 'static>(&mut self) -> Option<Box<T>> {
+        self.0
+            .as_mut()
+            .and_then(|map| map.remove(&TypeId::of::<T>()))
+            .and_then(Entry::into_boxed)
     }
 
     /// Get a reference to a type from the `Context`.
@@ -94,7 +142,7 @@ impl Map {
         self.0
             .as_ref()
             .and_then(|map| map.get(&TypeId::of::<T>()))
-            .and_then(|boxed| boxed.downcast_ref())
+            .and_then(|entry| entry.value.downcast_ref())
     }
 
     /// Get a mutable reference to a type from the `Context`.
@@ -102,7 +150,53 @@ impl Map {
         self.0
             .as_mut()
             .and_then(|map| map.get_mut(&TypeId::of::<T>()))
-            .and_then(|boxed| boxed.downcast_mut())
+            .and_then(|entry| entry.value.downcast_mut())
+    }
+
+    /// Insert a value under the given key into the `Context`.
+    pub fn insert_key<K: Key>(&mut self, _key: K, value: K::Value) -> Option<K::Value> {
+        self.insert(Keyed::<K>(value)).map(Keyed::into_inner)
+    }
+
+    /// Remove the value of the given key from the `Context`.
+    pub fn remove_key<K: Key>(&mut self, _key: K) -> Option<K::Value> {
+        self.remove::<Keyed<K>>().map(Keyed::into_inner)
+    }
+
+    /// Get a reference to the value of the given key from the `Context`.
+    pub fn get_key<K: Key>(&self, _key: K) -> Option<&K::Value> {
+        self.get::<Keyed<K>>().map(|keyed| &keyed.0)
+    }
+
+    /// Get a mutable reference to the value of the given key from the `Context`.
+    pub fn get_key_mut<K: Key>(&mut self, _key: K) -> Option<&mut K::Value> {
+        self.get_mut::<Keyed<K>>().map(|keyed| &mut keyed.0)
+    }
+
+    /// Check if the `Context` contains a value of the type with the given id.
+    pub(crate) fn contains_type_id(&self, id: TypeId) -> bool {
+        self.0.as_ref().map_or(false, |map| map.contains_key(&id))
+    }
+
+    /// Get an iterator over the ids of the types in the `Context`, in arbitrary order.
+    pub(crate) fn type_ids(&self) -> impl Iterator<Item = TypeId> + '_ {
+        self.0.iter().flat_map(|map| map.keys().copied())
+    }
+
+    /// Only keep the values of the types with the given ids.
+    pub(crate) fn retain_type_ids(&mut self, ids: &[TypeId]) {
+        if let Some(map) = self.0.as_mut() {
+            map.retain(|id, _| ids.contains(id));
+        }
+    }
+
+    /// Get an iterator over the ids and the names of the types in the `Context`, in arbitrary order.
+    #[cfg(feature = "profile")]
+    pub(crate) fn types(&self) -> impl Iterator<Item = (TypeId, &'static str)> + '_ {
+        self.0
+            .iter()
+            .flat_map(|map| map.iter())
+            .map(|(id, entry)| (*id, entry.name))
     }
 
     /// Clear the `Context`.
@@ -177,6 +271,28 @@ mod tests {
         assert!(context.is_empty());
         assert_eq!(context.len(), 0);
     }
+
+    #[test]
+    fn keyed() {
+        crate::key! {
+            Fast: f64;
+            Slow: f64;
+        }
+
+        let mut context = Map::new();
+        context.insert(0.5);
+        assert_eq!(context.insert_key(Fast, 1.0), None);
+        assert_eq!(context.insert_key(Slow, 2.0), None);
+        assert_eq!(context.len(), 3);
+        assert_eq!(context.get::<f64>(), Some(&0.5));
+        assert_eq!(context.get_key(Fast), Some(&1.0));
+        assert_eq!(context.get::<Keyed<Slow>>().map(|x| x.name()), Some("Slow"));
+        *context.get_key_mut(Slow).unwrap() += 1.0;
+        assert_eq!(context.insert_key(Slow, 4.0), Some(3.0));
+        assert_eq!(context.remove_key(Fast), Some(1.0));
+        assert_eq!(context.get_key(Fast), None);
+        assert_eq!(context.get_key(Slow), Some(&4.0));
+    }
 }
 
 /// Context for storing indicator datas.
@@ -0,0 +1,86 @@
+use alloc::boxed::Box;
+use futures::future::BoxFuture;
+
+use crate::{
+    context::{Dependencies, Dependent, Value},
+    reset::Reset,
+};
+
+use super::{AsyncContextOperator, AsyncLayer, AsyncRefOperator};
+
+/// Layer that awaits the output of an [`AsyncRefOperator`],
+/// and then inserts it into the env context.
+///
+/// The errors of the inner operator are converted into the errors of the [`AsyncRefOperator`].
+#[derive(Debug, Clone, Copy)]
+pub struct AsyncInsert<F>(pub F);
+
+impl<In, P, R, F> AsyncLayer<In, P> for AsyncInsert<F>
+where
+    P: AsyncContextOperator<In>,
+    F: Fn() -> R,
+    R: AsyncRefOperator<In>,
+    AsyncInsertOperator<P, R>: AsyncContextOperator<In, Out = P::Out>,
+{
+    type Operator = AsyncInsertOperator<P, R>;
+    type Out = P::Out;
+
+    #[inline]
+    fn layer(&self, operator: P) -> Self::Operator {
+        AsyncInsertOperator {
+            inner: operator,
+            insert: (self.0)(),
+        }
+    }
+}
+
+/// Operator for [`AsyncInsert`].
+pub struct AsyncInsertOperator<P, R> {
+    pub(super) inner: P,
+    pub(super) insert: R,
+}
+
+impl<In, P, R> AsyncContextOperator<In> for AsyncInsertOperator<P, R>
+where
+    In: Send + Sync + 'static,
+    P: AsyncContextOperator<In> + Send,
+    P::Out: Send + 'static,
+    P::Error: Into<R::Error>,
+    R: AsyncRefOperator<In> + Send,
+    R::Output: Send + Sync + 'static,
+    R::Error: Send + 'static,
+{
+    type Out = P::Out;
+    type Error = R::Error;
+
+    fn next(
+        &mut self,
+        mut input: Value<In>,
+    ) -> BoxFuture<'_, Result<Value<Self::Out>, Self::Error>> {
+        Box::pin(async move {
+            let output = self.insert.next(input.as_ref()).await?;
+            input.context_mut().env_mut().insert(output);
+            self.inner.next(input).await.map_err(Into::into)
+        })
+    }
+}
+
+impl<In, P, R> Dependent<In> for AsyncInsertOperator<P, R>
+where
+    P: Dependent<In>,
+    R: AsyncRefOperator<In> + Dependent<In>,
+    R::Output: 'static,
+{
+    fn dependencies(&self, deps: &mut Dependencies) {
+        self.insert.dependencies(deps);
+        deps.provide_env::<R::Output>();
+        self.inner.dependencies(deps);
+    }
+}
+
+impl<P: Reset, R: Reset> Reset for AsyncInsertOperator<P, R> {
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.insert.reset();
+    }
+}
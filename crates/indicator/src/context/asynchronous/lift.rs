@@ -0,0 +1,80 @@
+use futures::future::BoxFuture;
+
+use crate::{
+    context::{
+        layer::Layer,
+        value::{input, Input},
+        ContextOperator, Dependencies, Dependent, Value,
+    },
+    reset::Reset,
+};
+
+use super::{AsyncContextOperator, AsyncLayer};
+
+/// Layer that lifts a sync [`Layer`] into the async stack.
+///
+/// The sync layer is applied to an identity operator, which is evaluated
+/// before the inner [`AsyncContextOperator`]. So the layers that insert into
+/// or provide to the context (e.g. [`Insert`](crate::context::Insert) and
+/// [`AddData`](crate::context::AddData)) behave as in the sync stack, while the layers acting
+/// on the output of the inner operator (e.g. [`Cache`](crate::context::Cache))
+/// only see the context at this point of the stack.
+#[derive(Debug, Clone, Copy)]
+pub struct Lift<L>(pub L);
+
+impl<In, P, L> AsyncLayer<In, P> for Lift<L>
+where
+    P: AsyncContextOperator<In>,
+    L: Layer<In, Input<In>, Out = In>,
+    LiftOperator<L::Operator, P>: AsyncContextOperator<In, Out = P::Out>,
+{
+    type Operator = LiftOperator<L::Operator, P>;
+    type Out = P::Out;
+
+    #[inline]
+    fn layer(&self, operator: P) -> Self::Operator {
+        LiftOperator {
+            outer: self.0.layer(input()),
+            inner: operator,
+        }
+    }
+}
+
+/// Operator for [`Lift`].
+pub struct LiftOperator<S, P> {
+    outer: S,
+    inner: P,
+}
+
+impl<In, S, P> AsyncContextOperator<In> for LiftOperator<S, P>
+where
+    S: ContextOperator<In, Out = In>,
+    P: AsyncContextOperator<In>,
+{
+    type Out = P::Out;
+    type Error = P::Error;
+
+    #[inline]
+    fn next(&mut self, input: Value<In>) -> BoxFuture<'_, Result<Value<Self::Out>, Self::Error>> {
+        let value = self.outer.next(input);
+        self.inner.next(value)
+    }
+}
+
+impl<In, S, P> Dependent<In> for LiftOperator<S, P>
+where
+    S: Dependent<In>,
+    P: Dependent<In>,
+{
+    fn dependencies(&self, deps: &mut Dependencies) {
+        self.outer.dependencies(deps);
+        self.inner.dependencies(deps);
+    }
+}
+
+impl<S: Reset, P: Reset> Reset for LiftOperator<S, P> {
+    fn reset(&mut self) {
+        self.outer.reset();
+        self.inner.reset();
+    }
+}
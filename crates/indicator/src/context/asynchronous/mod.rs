@@ -0,0 +1,466 @@
+use core::{
+    convert::Infallible,
+    task::{Context, Poll},
+};
+
+use alloc::boxed::Box;
+use futures::future::{ready, BoxFuture};
+
+use crate::{
+    async_operator::{AsyncOperator, Next},
+    reset::Reset,
+};
+
+use super::{
+    layer::Layer,
+    value::{Input, Value, ValueRef},
+    ContextOperator, Dependencies, Dependent, InitData, Map, MissingDependencies,
+};
+
+/// Layer that inserts the output of an [`AsyncRefOperator`] into the context.
+pub mod insert;
+
+/// Layer that lifts a sync [`Layer`] into the async stack.
+pub mod lift;
+
+/// [`AsyncRefOperator`] sending a request to an [`AsyncOperator`].
+pub mod request;
+
+pub use self::{
+    insert::{AsyncInsert, AsyncInsertOperator},
+    lift::{Lift, LiftOperator},
+    request::{request, Request},
+};
+
+/// Async counterpart of [`ContextOperator`].
+///
+/// A sync [`ContextOperator`] can be lifted into the async stack
+/// with [`ContextOperatorExt::into_async`](super::ContextOperatorExt::into_async).
+pub trait AsyncContextOperator<In> {
+    /// The output type inside the [`Value`] wrapper.
+    type Out;
+
+    /// The error type.
+    type Error;
+
+    /// Apply the operator.
+    fn next(&mut self, input: Value<In>) -> BoxFuture<'_, Result<Value<Self::Out>, Self::Error>>;
+}
+
+/// A lifted sync [`ContextOperator`], which never fails.
+impl<In, P> AsyncContextOperator<In> for Next<P>
+where
+    P: ContextOperator<In> + Send,
+    P::Out: Send + 'static,
+{
+    type Out = P::Out;
+    type Error = Infallible;
+
+    #[inline]
+    fn next(&mut self, input: Value<In>) -> BoxFuture<'_, Result<Value<Self::Out>, Self::Error>> {
+        Box::pin(ready(Ok(self.inner.next(input))))
+    }
+}
+
+impl<In, P: Dependent<In>> Dependent<In> for Next<P> {
+    fn dependencies(&self, deps: &mut Dependencies) {
+        self.inner.dependencies(deps);
+    }
+}
+
+/// Async counterpart of [`RefOperator`](super::RefOperator).
+pub trait AsyncRefOperator<In> {
+    /// The output type.
+    type Output;
+
+    /// The error type.
+    type Error;
+
+    /// Apply the operator.
+    fn next<'a>(
+        &'a mut self,
+        input: ValueRef<'a, In>,
+    ) -> BoxFuture<'a, Result<Self::Output, Self::Error>>;
+}
+
+/// Async counterpart of [`Layer`].
+/// Convert an [`AsyncContextOperator`] to another [`AsyncContextOperator`].
+pub trait AsyncLayer<In, P>
+where
+    P: AsyncContextOperator<In>,
+{
+    /// The output operator.
+    type Operator: AsyncContextOperator<In, Out = Self::Out>;
+
+    /// The output type.
+    type Out;
+
+    /// Convert an `In`-operator to another `In`-operator.
+    fn layer(&self, operator: P) -> Self::Operator;
+}
+
+/// Extension trait for [`AsyncContextOperator`].
+pub trait AsyncContextOperatorExt<In>: AsyncContextOperator<In> {
+    /// Add an async layer.
+    fn with<L>(self, layer: L) -> L::Operator
+    where
+        L: AsyncLayer<In, Self>,
+        Self: Sized,
+    {
+        layer.layer(self)
+    }
+
+    /// Add a sync layer, see [`Lift`] for the details.
+    fn lift<L>(self, layer: L) -> LiftOperator<L::Operator, Self>
+    where
+        L: Layer<In, Input<In>, Out = In>,
+        Self: Sized,
+    {
+        self.with(Lift(layer))
+    }
+
+    /// Add an [`AsyncInsert`] layer with the given [`AsyncRefOperator`] constructor
+    /// (i.e. a function that returns an [`AsyncRefOperator`]).
+    ///
+    /// We use this method to add the output of the operator to the `env` context.
+    fn insert_env<R>(self, f: impl Fn() -> R) -> AsyncInsertOperator<Self, R>
+    where
+        R: AsyncRefOperator<In>,
+        Self: Sized,
+    {
+        AsyncInsertOperator {
+            inner: self,
+            insert: f(),
+        }
+    }
+
+    /// Build into an [`AsyncOperator`] without the `Value` wrapper.
+    #[inline]
+    fn finish(self) -> AsyncContextedOperator<Self>
+    where
+        Self: Sized,
+    {
+        self.finish_with_data(Map::default())
+    }
+
+    /// Build into an [`AsyncOperator`] without the `Value` wrapper with the given data context.
+    ///
+    /// On reset, the values of the types given here are kept and the others are removed.
+    #[inline]
+    fn finish_with_data(self, data: Map) -> AsyncContextedOperator<Self>
+    where
+        Self: Sized,
+    {
+        let init = InitData::given(&data);
+        AsyncContextedOperator(self, data, init)
+    }
+
+    /// Build into an [`AsyncOperator`] without the `Value` wrapper with the data context
+    /// built by the given factory, which builds it again on reset.
+    #[inline]
+    fn finish_with<F>(self, data: F) -> AsyncContextedOperator<Self>
+    where
+        F: Fn() -> Map + Send + Sync + 'static,
+        Self: Sized,
+    {
+        let init = InitData::factory(data);
+        AsyncContextedOperator(self, init.build(), init)
+    }
+
+    /// Build into an [`AsyncOperator`] without the `Value` wrapper,
+    /// after checking that the dependencies of the operator are satisfied.
+    ///
+    /// # Errors
+    /// Return the list of the missing types if any of the dependencies is not provided.
+    #[inline]
+    fn try_finish(self) -> Result<AsyncContextedOperator<Self>, MissingDependencies>
+    where
+        Self: Dependent<In> + Sized,
+    {
+        self.try_finish_with_data(Map::default())
+    }
+
+    /// Build into an [`AsyncOperator`] without the `Value` wrapper with the given data context,
+    /// after checking that the dependencies of the operator are satisfied.
+    ///
+    /// # Errors
+    /// Return the list of the missing types if any of the dependencies is not provided.
+    fn try_finish_with_data(
+        self,
+        data: Map,
+    ) -> Result<AsyncContextedOperator<Self>, MissingDependencies>
+    where
+        Self: Dependent<In> + Sized,
+    {
+        let mut deps = Dependencies::default();
+        self.dependencies(&mut deps);
+        deps.check(&data)?;
+        Ok(self.finish_with_data(data))
+    }
+
+    /// Build into an [`AsyncOperator`] without the `Value` wrapper with the data context
+    /// built by the given factory, after checking that the dependencies of the operator are satisfied.
+    ///
+    /// # Errors
+    /// Return the list of the missing types if any of the dependencies is not provided.
+    fn try_finish_with<F>(
+        self,
+        data: F,
+    ) -> Result<AsyncContextedOperator<Self>, MissingDependencies>
+    where
+        F: Fn() -> Map + Send + Sync + 'static,
+        Self: Dependent<In> + Sized,
+    {
+        let init = InitData::factory(data);
+        let data = init.build();
+        let mut deps = Dependencies::default();
+        self.dependencies(&mut deps);
+        deps.check(&data)?;
+        Ok(AsyncContextedOperator(self, data, init))
+    }
+}
+
+impl<In, P> AsyncContextOperatorExt<In> for P where P: AsyncContextOperator<In> {}
+
+/// An [`AsyncOperator`] without the `Value` wrapper.
+///
+/// The data context is moved into the evaluation and back when it succeeds.
+/// If the evaluation fails or its future is dropped, the data context is lost:
+/// it is built again by the factory given to [`finish_with`](AsyncContextOperatorExt::finish_with),
+/// otherwise it is left empty.
+pub struct AsyncContextedOperator<P>(P, Map, InitData);
+
+/// The data context is restored: it is built again if it was built by a factory
+/// (see [`AsyncContextOperatorExt::finish_with`]), otherwise only the values
+/// of the types given when it was built are kept.
+impl<P: Reset> Reset for AsyncContextedOperator<P> {
+    fn reset(&mut self) {
+        self.0.reset();
+        self.2.restore(&mut self.1);
+    }
+}
+
+impl<In, P> AsyncOperator<In> for AsyncContextedOperator<P>
+where
+    P: AsyncContextOperator<In> + Send,
+    P::Out: Send + 'static,
+    P::Error: Send + 'static,
+{
+    type Output = P::Out;
+
+    type Error = P::Error;
+
+    type Future<'a>
+        = BoxFuture<'a, Result<Self::Output, Self::Error>>
+    where
+        P: 'a;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn next(&mut self, input: In) -> Self::Future<'_> {
+        let Self(op, data, init) = self;
+        let fut = op.next(Value::with_data(input, core::mem::take(data)));
+        let mut guard = Rebuild {
+            data,
+            init,
+            done: false,
+        };
+        Box::pin(async move {
+            let mut value = fut.await?;
+            guard.complete(value.context_mut().data_mut());
+            Ok(value.into_inner())
+        })
+    }
+}
+
+/// Build the data context again if the evaluation has not completed,
+/// i.e. it has failed or its future has been dropped.
+struct Rebuild<'a> {
+    data: &'a mut Map,
+    init: &'a InitData,
+    done: bool,
+}
+
+impl Rebuild<'_> {
+    fn complete(&mut self, data: &mut Map) {
+        core::mem::swap(self.data, data);
+        self.done = true;
+    }
+}
+
+impl Drop for Rebuild<'_> {
+    fn drop(&mut self) {
+        if !self.done {
+            *self.data = self.init.build();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        context::{
+            insert_env_and_output, AddData, ContextOperatorExt, Data, Dependency, Env, In, Insert,
+            Scope,
+        },
+        operator::{map, map::Map},
+        prelude::operator,
+        OperatorExt,
+    };
+    use alloc::{string::String, vec::Vec};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Symbol(String);
+
+    #[derive(Debug, Clone, Copy)]
+    struct Scale(f64);
+
+    #[operator(input = f64)]
+    fn double(In(x): In<&f64>) -> i64 {
+        (x * 2.0) as i64
+    }
+
+    #[operator(input = f64)]
+    fn symbol(Data(symbol): Data<&Symbol>) -> Symbol {
+        symbol.clone()
+    }
+
+    #[operator(input = f64)]
+    fn scaled(Env(x): Env<&i64>, Env(scale): Env<&Scale>) -> f64 {
+        *x as f64 * scale.0
+    }
+
+    fn scale_of(symbol: Symbol) -> Scale {
+        Scale(if symbol.0 == "BTC" { 0.5 } else { 1.0 })
+    }
+
+    type LookupOp = Next<Map<fn(Symbol) -> Scale>>;
+
+    fn lookup() -> LookupOp {
+        map(scale_of as fn(Symbol) -> Scale).into_async_operator()
+    }
+
+    #[tokio::test]
+    async fn async_insert() {
+        let mut op = insert_env_and_output(scaled)
+            .into_async()
+            .insert_env(|| request(symbol(), lookup()))
+            .lift(Insert(double))
+            .lift(AddData::with_data(Symbol("BTC".into())))
+            .try_finish()
+            .unwrap();
+        let mut outputs = Vec::new();
+        for x in [1.0, 2.0, 4.0] {
+            outputs.push(op.next(x).await.unwrap());
+        }
+        assert_eq!(outputs, [1.0, 2.0, 4.0]);
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Failed;
+
+    impl From<Infallible> for Failed {
+        fn from(err: Infallible) -> Self {
+            match err {}
+        }
+    }
+
+    /// Fails on negative inputs.
+    struct Checked;
+
+    impl AsyncRefOperator<f64> for Checked {
+        type Output = Scale;
+        type Error = Failed;
+
+        fn next<'a>(
+            &'a mut self,
+            input: ValueRef<'a, f64>,
+        ) -> BoxFuture<'a, Result<Self::Output, Self::Error>> {
+            let symbol = input.context().data().get::<Symbol>().cloned();
+            let x = *input.value();
+            Box::pin(async move {
+                if x < 0.0 {
+                    return Err(Failed);
+                }
+                symbol.map(scale_of).ok_or(Failed)
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn failed() {
+        let mut op = insert_env_and_output(scaled)
+            .into_async()
+            .insert_env(|| Checked)
+            .lift(Insert(double))
+            .finish_with(|| {
+                let mut data = crate::context::Map::new();
+                data.insert(Symbol("BTC".into()));
+                data
+            });
+        assert_eq!(op.next(-1.0).await, Err(Failed));
+        // The data context is built again.
+        assert_eq!(op.next(2.0).await, Ok(2.0));
+        drop(op.next(2.0));
+        assert_eq!(op.next(4.0).await, Ok(4.0));
+    }
+
+    #[test]
+    fn missing() {
+        let Err(err) = insert_env_and_output(scaled)
+            .into_async()
+            .lift(Insert(double))
+            .insert_env(|| request(symbol(), lookup()))
+            .try_finish()
+        else {
+            panic!("the dependencies should be missing");
+        };
+        assert_eq!(err.dependencies(), [Dependency::of::<Symbol>(Scope::Data)]);
+    }
+
+    #[cfg(feature = "tower")]
+    #[tokio::test]
+    async fn service() {
+        use crate::async_operator::ServiceOperator;
+        use core::future::Ready;
+
+        type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+        struct Lookup;
+
+        impl tower_service::Service<Symbol> for Lookup {
+            type Response = Scale;
+            type Error = BoxError;
+            type Future = Ready<Result<Scale, BoxError>>;
+
+            fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+                Poll::Ready(Ok(()))
+            }
+
+            fn call(&mut self, symbol: Symbol) -> Self::Future {
+                core::future::ready(match symbol.0.as_str() {
+                    "BTC" => Ok(Scale(0.5)),
+                    _ => Err(alloc::format!("unknown symbol {}", symbol.0).into()),
+                })
+            }
+        }
+
+        let build = |name: &str| {
+            insert_env_and_output(scaled)
+                .into_async()
+                .insert_env(|| request(symbol(), Lookup.into_async_operator()))
+                .lift(Insert(double))
+                .lift(AddData::with_data(Symbol(name.into())))
+                .try_finish()
+                .unwrap()
+        };
+        assert_eq!(build("BTC").next(3.0).await.unwrap(), 3.0);
+        assert_eq!(
+            build("ETH").next(3.0).await.unwrap_err().to_string(),
+            "unknown symbol ETH"
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use alloc::boxed::Box;
+use futures::future::{poll_fn, BoxFuture};
+
+use crate::{
+    async_operator::AsyncOperator,
+    context::{Dependencies, Dependent, RefOperator, ValueRef},
+    reset::Reset,
+};
+
+use super::AsyncRefOperator;
+
+/// [`AsyncRefOperator`] that builds a request with a [`RefOperator`],
+/// and then sends it to an [`AsyncOperator`] once it is ready.
+///
+/// With the `tower` feature, a [`Service`](tower_service::Service) can be used
+/// by converting it with [`ServiceOperator::into_async_operator`](crate::async_operator::ServiceOperator::into_async_operator).
+#[derive(Debug, Clone, Copy)]
+pub struct Request<R, S> {
+    request: R,
+    service: S,
+}
+
+/// Create a [`Request`] from the [`RefOperator`] building the requests
+/// and the [`AsyncOperator`] handling them.
+pub fn request<R, S>(request: R, service: S) -> Request<R, S> {
+    Request { request, service }
+}
+
+impl<In, R, S, Req> AsyncRefOperator<In> for Request<R, S>
+where
+    R: for<'a> RefOperator<'a, In, Output = Req>,
+    S: AsyncOperator<Req> + Send,
+    for<'a> S::Future<'a>: Send,
+    S::Error: Send,
+    Req: Send + 'static,
+{
+    type Output = S::Output;
+    type Error = S::Error;
+
+    fn next<'a>(
+        &'a mut self,
+        input: ValueRef<'a, In>,
+    ) -> BoxFuture<'a, Result<Self::Output, Self::Error>> {
+        let request = self.request.next(input);
+        let service = &mut self.service;
+        Box::pin(async move {
+            poll_fn(|cx| service.poll_ready(cx)).await?;
+            service.next(request).await
+        })
+    }
+}
+
+impl<In, R: Dependent<In>, S> Dependent<In> for Request<R, S> {
+    fn dependencies(&self, deps: &mut Dependencies) {
+        self.request.dependencies(deps);
+    }
+}
+
+impl<R: Reset, S> Reset for Request<R, S> {
+    fn reset(&mut self) {
+        self.request.reset();
+    }
+}
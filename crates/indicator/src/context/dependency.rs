@@ -0,0 +1,248 @@
+use alloc::vec::Vec;
+use core::{
+    any::{type_name, TypeId},
+    fmt,
+};
+
+use super::Map;
+
+/// The part of the context where a dependency lives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scope {
+    /// The env context.
+    Env,
+    /// The data context.
+    Data,
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Env => write!(f, "env"),
+            Self::Data => write!(f, "data"),
+        }
+    }
+}
+
+/// A type required from the context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dependency {
+    scope: Scope,
+    id: TypeId,
+    name: &'static str,
+}
+
+impl Dependency {
+    /// Create a dependency on the type `T`.
+    pub fn of<T: 'static>(scope: Scope) -> Self {
+        Self {
+            scope,
+            id: TypeId::of::<T>(),
+            name: type_name::<T>(),
+        }
+    }
+
+    /// Get the scope.
+    pub fn scope(&self) -> Scope {
+        self.scope
+    }
+
+    /// Get the name of the type.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Get the [`TypeId`] of the type.
+    pub fn type_id(&self) -> TypeId {
+        self.id
+    }
+}
+
+impl fmt::Display for Dependency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` in {}", self.name, self.scope)
+    }
+}
+
+/// The types provided to and required from the context by a stack of layers.
+///
+/// The layers are visited from the outermost to the innermost,
+/// so an env requirement is only satisfied by the layers outside of it.
+/// The data context is kept between evaluations, so a data requirement
+/// is satisfied by any layer of the stack.
+#[derive(Debug, Default)]
+pub struct Dependencies {
+    env: Vec<TypeId>,
+    data: Vec<TypeId>,
+    missing: Vec<Dependency>,
+    required: Vec<Dependency>,
+}
+
+impl Dependencies {
+    /// Declare that the type `T` is provided to the env context.
+    pub fn provide_env<T: 'static>(&mut self) {
+        self.env.push(TypeId::of::<T>());
+    }
+
+    /// Declare that the type `T` is provided to the data context.
+    pub fn provide_data<T: 'static>(&mut self) {
+        self.data.push(TypeId::of::<T>());
+    }
+
+    /// Declare that the type `T` is required from the env context.
+    pub fn require_env<T: 'static>(&mut self) {
+        if !self.env.contains(&TypeId::of::<T>()) {
+            push(&mut self.missing, Dependency::of::<T>(Scope::Env));
+        }
+    }
+
+    /// Declare that the type `T` is required from the data context.
+    pub fn require_data<T: 'static>(&mut self) {
+        push(&mut self.required, Dependency::of::<T>(Scope::Data));
+    }
+
+    /// Check the requirements, with `data` the initial data context.
+    pub fn check(self, data: &Map) -> Result<(), MissingDependencies> {
+        let mut missing = self.missing;
+        for dep in self.required {
+            if !self.data.contains(&dep.id) && !data.contains_type_id(dep.id) {
+                push(&mut missing, dep);
+            }
+        }
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(MissingDependencies(missing))
+        }
+    }
+}
+
+fn push(deps: &mut Vec<Dependency>, dep: Dependency) {
+    if !deps.contains(&dep) {
+        deps.push(dep);
+    }
+}
+
+/// Error reporting the types missing from the context.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingDependencies(Vec<Dependency>);
+
+impl MissingDependencies {
+    /// Get the missing dependencies.
+    pub fn dependencies(&self) -> &[Dependency] {
+        &self.0
+    }
+}
+
+impl fmt::Display for MissingDependencies {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "missing dependencies: ")?;
+        for (idx, dep) in self.0.iter().enumerate() {
+            if idx > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{dep}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MissingDependencies {}
+
+/// Operator that declares the types it provides to and requires from the context.
+///
+/// It is implemented by the operators generated by `#[operator]`, by the insert and output
+/// operators whose [`RefOperator`](super::RefOperator)s implement it, and by the operators of the layers.
+/// Operators created from closures, like [`output`](super::output), are assumed to have no dependencies.
+pub trait Dependent<In> {
+    /// Declare the dependencies.
+    fn dependencies(&self, deps: &mut Dependencies);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        context::{input, insert_env_and_output, ContextOperatorExt, Data, Env, In, Prev},
+        prelude::operator,
+        IndicatorIteratorExt,
+    };
+    use alloc::vec::Vec;
+
+    #[derive(Debug, Clone, Copy)]
+    struct Scale(f64);
+
+    #[operator(input = f64)]
+    fn double(In(x): In<&f64>) -> i64 {
+        (x * 2.0) as i64
+    }
+
+    #[operator(input = f64)]
+    fn scaled(Env(x): Env<&i64>, Data(scale): Data<&Scale>) -> f64 {
+        *x as f64 * scale.0
+    }
+
+    #[operator(input = f64)]
+    fn delta(Env(x): Env<&i64>, Prev(prev): Prev<&i64>) -> i32 {
+        (x - prev.copied().unwrap_or(*x)) as i32
+    }
+
+    #[test]
+    fn satisfied() {
+        let op = insert_env_and_output(scaled)
+            .insert_env(double)
+            .provide(Scale(0.5))
+            .try_finish()
+            .unwrap();
+        assert_eq!(
+            [1.0, 2.0].into_iter().indicator(op).collect::<Vec<_>>(),
+            [1.0, 2.0]
+        );
+        let op = insert_env_and_output(delta)
+            .insert_env(double)
+            .cache(1)
+            .try_finish()
+            .unwrap();
+        assert_eq!(
+            [1.0, 2.0, 4.0]
+                .into_iter()
+                .indicator(op)
+                .collect::<Vec<_>>(),
+            [0, 2, 4]
+        );
+        let mut data = Map::new();
+        data.insert(Scale(2.0));
+        assert!(insert_env_and_output(scaled)
+            .insert_env(double)
+            .data_from_context::<Scale>()
+            .try_finish_with_data(data)
+            .is_ok());
+    }
+
+    #[test]
+    fn missing() {
+        let Err(err) = insert_env_and_output(delta).try_finish() else {
+            panic!("the dependencies should be missing");
+        };
+        assert_eq!(
+            err.dependencies(),
+            [
+                Dependency::of::<i64>(Scope::Env),
+                Dependency::of::<crate::context::layer::cache::Previous>(Scope::Env),
+            ]
+        );
+        // The env is only provided to the inner layers.
+        let Err(err) = input::<f64>()
+            .insert_env(double)
+            .insert_env(scaled)
+            .try_finish()
+        else {
+            panic!("the dependencies should be missing");
+        };
+        assert_eq!(
+            alloc::format!("{err}"),
+            "missing dependencies: `i64` in env, `indicator::context::dependency::tests::Scale` in data"
+        );
+    }
+}
@@ -1,8 +1,10 @@
-use crate::context::ValueRef;
+use crate::context::{Dependencies, ValueRef};
 
 use super::FromValueRef;
 
 /// Extract from the data context.
+///
+/// Use `Data<&Keyed<K>>` to select the entry of the key `K`.
 pub struct Data<T>(pub T);
 
 impl<'a, I, T> FromValueRef<'a, I> for Data<&'a T>
@@ -18,6 +20,10 @@ where
             )
         }))
     }
+
+    fn dependencies(deps: &mut Dependencies) {
+        deps.require_data::<T>();
+    }
 }
 
 impl<'a, I, T> FromValueRef<'a, I> for Data<Option<&'a T>>
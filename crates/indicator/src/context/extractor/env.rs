@@ -1,8 +1,10 @@
-use crate::context::ValueRef;
+use crate::context::{Dependencies, ValueRef};
 
 use super::FromValueRef;
 
 /// Extract from the context.
+///
+/// Use `Env<&Keyed<K>>` to select the entry of the key `K`.
 pub struct Env<T>(pub T);
 
 impl<'a, I, T> FromValueRef<'a, I> for Env<&'a T>
@@ -15,6 +17,10 @@ where
             panic!("`{}` not found in the context", core::any::type_name::<T>())
         }))
     }
+
+    fn dependencies(deps: &mut Dependencies) {
+        deps.require_env::<T>();
+    }
 }
 
 impl<'a, I, T> FromValueRef<'a, I> for Env<Option<&'a T>>
@@ -1,4 +1,4 @@
-use super::ValueRef;
+use super::{Dependencies, ValueRef};
 
 /// Input extractor.
 pub mod input;
@@ -12,10 +12,20 @@ pub mod prev;
 /// Extract from the data context.
 pub mod data;
 
-pub use self::{data::Data, env::Env, input::In, prev::Prev};
+pub use self::{
+    data::Data,
+    env::Env,
+    input::In,
+    prev::{History, Prev, PrevN},
+};
 
 /// Type that can extract from [`ValueRef`].
 pub trait FromValueRef<'a, T> {
     /// Extrace from [`ValueRef`].
     fn from_value_ref(value: &ValueRef<'a, T>) -> Self;
+
+    /// Declare the types required from the context.
+    ///
+    /// Optional extractors require nothing.
+    fn dependencies(_deps: &mut Dependencies) {}
 }
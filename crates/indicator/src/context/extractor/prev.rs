@@ -1,25 +1,121 @@
-use crate::context::{layer::cache::Previous, ValueRef};
+use core::marker::PhantomData;
+
+use crate::context::{layer::cache::Previous, Dependencies, ValueRef};
 
 use super::FromValueRef;
 
 /// Extract from previous context.
 pub struct Prev<T>(pub Option<T>);
 
+fn previous<'a, I>(value: &ValueRef<'a, I>) -> &'a Previous {
+    value
+        .context
+        .env()
+        .get::<Previous>()
+        .expect("`Previous` not found in the context. Perhaps you forgot to add `Cache` layer?")
+}
+
 impl<'a, I, T> FromValueRef<'a, I> for Prev<&'a T>
 where
     T: Send + Sync + 'static,
 {
     #[inline]
     fn from_value_ref(value: &ValueRef<'a, I>) -> Self {
-        Self(
-            value
-                .context
-                .env()
-                .get::<Previous>()
-                .expect(
-                    "`Previous` not found in the context. Perhaps you forgot to add `Cache` layer?",
-                )
-                .get::<T>(),
-        )
+        Self(previous(value).get::<T>())
+    }
+
+    /// The previous contexts are required, which are provided by the [`Cache`](crate::context::Cache) layer.
+    fn dependencies(deps: &mut Dependencies) {
+        deps.require_env::<Previous>();
+    }
+}
+
+/// Extract from the `N`-th previous context.
+///
+/// `PrevN<T, 1>` is the same as [`Prev<T>`]. The depth is limited by the length of the
+/// [`Cache`](crate::context::Cache) layer, and `PrevN<T, 0>` always extracts `None`.
+pub struct PrevN<T, const N: usize>(pub Option<T>);
+
+impl<'a, I, T, const N: usize> FromValueRef<'a, I> for PrevN<&'a T, N>
+where
+    T: Send + Sync + 'static,
+{
+    #[inline]
+    fn from_value_ref(value: &ValueRef<'a, I>) -> Self {
+        let prev = N
+            .checked_sub(1)
+            .and_then(|n| previous(value).nth(n))
+            .and_then(|map| map.get::<T>());
+        Self(prev)
+    }
+
+    fn dependencies(deps: &mut Dependencies) {
+        deps.require_env::<Previous>();
+    }
+}
+
+/// Extract the values of the type `T` from the last `N` previous contexts.
+///
+/// The values are iterated from the latest to the oldest,
+/// and the iteration stops at the first context without a `T`.
+pub struct History<'a, T, const N: usize>(pub Values<'a, T>);
+
+impl<'a, I, T, const N: usize> FromValueRef<'a, I> for History<'a, T, N>
+where
+    T: Send + Sync + 'static,
+{
+    #[inline]
+    fn from_value_ref(value: &ValueRef<'a, I>) -> Self {
+        Self(Values {
+            previous: previous(value),
+            next: 0,
+            len: N,
+            value: PhantomData,
+        })
+    }
+
+    fn dependencies(deps: &mut Dependencies) {
+        deps.require_env::<Previous>();
+    }
+}
+
+/// Iterator over the values of the previous contexts, created by [`History`].
+#[derive(Debug)]
+pub struct Values<'a, T> {
+    previous: &'a Previous,
+    next: usize,
+    len: usize,
+    value: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> Clone for Values<'a, T> {
+    fn clone(&self) -> Self {
+        Self {
+            previous: self.previous,
+            next: self.next,
+            len: self.len,
+            value: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Values<'a, T>
+where
+    T: Send + Sync + 'static,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.len {
+            return None;
+        }
+        let value = self.previous.nth(self.next)?.get::<T>();
+        // Stop at the first context without a `T`.
+        self.next = if value.is_some() {
+            self.next + 1
+        } else {
+            self.len
+        };
+        value
     }
 }
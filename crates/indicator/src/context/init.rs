@@ -0,0 +1,52 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::{any::TypeId, fmt};
+
+use super::Map;
+
+/// The initial data context of a built operator, to restore it on reset.
+pub(crate) enum InitData {
+    /// Only keep the values of the types given when built.
+    Given(Vec<TypeId>),
+    /// Rebuild the data context with the factory.
+    Factory(Box<dyn Fn() -> Map + Send + Sync>),
+}
+
+impl InitData {
+    pub(crate) fn given(data: &Map) -> Self {
+        Self::Given(data.type_ids().collect())
+    }
+
+    pub(crate) fn factory(f: impl Fn() -> Map + Send + Sync + 'static) -> Self {
+        Self::Factory(Box::new(f))
+    }
+
+    /// Build the data context from the factory, or an empty one.
+    pub(crate) fn build(&self) -> Map {
+        match self {
+            Self::Given(_) => Map::new(),
+            Self::Factory(f) => f(),
+        }
+    }
+
+    pub(crate) fn restore(&self, data: &mut Map) {
+        match self {
+            Self::Given(ids) => data.retain_type_ids(ids),
+            Self::Factory(f) => *data = f(),
+        }
+    }
+}
+
+impl Default for InitData {
+    fn default() -> Self {
+        Self::Given(Vec::new())
+    }
+}
+
+impl fmt::Debug for InitData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Given(ids) => f.debug_tuple("Given").field(ids).finish(),
+            Self::Factory(_) => f.write_str("Factory"),
+        }
+    }
+}
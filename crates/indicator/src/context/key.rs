@@ -0,0 +1,130 @@
+use core::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+/// A typed key of the context.
+///
+/// A key carries a name and the type of its value, so that
+/// several values of the same type can live in the same context.
+/// Use the [`key!`](crate::key) macro to declare one.
+pub trait Key: Send + Sync + 'static {
+    /// The type of the value.
+    type Value: Send + Sync + 'static;
+
+    /// The name of the key.
+    const NAME: &'static str;
+}
+
+/// Value stored under the key `K`.
+///
+/// It is an ordinary context entry, so it can be inserted by the insert layers
+/// and extracted with `Env<&Keyed<K>>` or `Data<&Keyed<K>>`.
+pub struct Keyed<K: Key>(pub K::Value);
+
+impl<K: Key> Keyed<K> {
+    /// Create a new keyed value.
+    #[inline]
+    pub fn new(value: K::Value) -> Self {
+        Self(value)
+    }
+
+    /// Get the name of the key.
+    #[inline]
+    pub fn name(&self) -> &'static str {
+        K::NAME
+    }
+
+    /// Convert into the inner value.
+    #[inline]
+    pub fn into_inner(self) -> K::Value {
+        self.0
+    }
+}
+
+impl<K: Key> Deref for Keyed<K> {
+    type Target = K::Value;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<K: Key> DerefMut for Keyed<K> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<K: Key> AsRef<K::Value> for Keyed<K> {
+    #[inline]
+    fn as_ref(&self) -> &K::Value {
+        &self.0
+    }
+}
+
+impl<K: Key> Clone for Keyed<K>
+where
+    K::Value: Clone,
+{
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<K: Key> fmt::Debug for Keyed<K>
+where
+    K::Value: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Keyed")
+            .field(&K::NAME)
+            .field(&self.0)
+            .finish()
+    }
+}
+
+impl<K: Key> PartialEq for Keyed<K>
+where
+    K::Value: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+/// Declare typed keys of the context.
+///
+/// ```
+/// use indicator::{context::Map, key};
+///
+/// key! {
+///     /// The fast moving average.
+///     pub Fast: f64;
+///     /// The slow moving average.
+///     pub Slow: f64;
+/// }
+///
+/// let mut env = Map::new();
+/// env.insert_key(Fast, 1.0);
+/// env.insert_key(Slow, 2.0);
+/// assert_eq!(env.get_key(Fast), Some(&1.0));
+/// assert_eq!(env.get_key(Slow), Some(&2.0));
+/// ```
+#[macro_export]
+macro_rules! key {
+    ($($(#[$attr:meta])* $vis:vis $name:ident: $ty:ty;)*) => {
+        $(
+            $(#[$attr])*
+            #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+            $vis struct $name;
+
+            impl $crate::context::Key for $name {
+                type Value = $ty;
+                const NAME: &'static str = stringify!($name);
+            }
+        )*
+    };
+}
@@ -1,30 +1,76 @@
-use core::{num::NonZeroUsize, ops::Deref};
+use alloc::{boxed::Box, collections::VecDeque};
+use core::{fmt, marker::PhantomData, num::NonZeroUsize, ops::Deref};
 
 use crate::{
-    context::{anymap::Map, ContextOperator, Value},
+    context::{anymap::Map, ContextOperator, Dependencies, Dependent, Value},
+    readiness::Readiness,
+    reset::{Reset, Seed},
     Operator,
 };
 
+#[cfg(feature = "snapshot")]
+use crate::snapshot::{Schema, Snapshot};
+#[cfg(feature = "snapshot")]
+use alloc::vec::Vec;
+#[cfg(feature = "snapshot")]
+use serde::{Deserialize, Serialize};
+
 use super::Layer;
 
 /// Layer that *caches* and *clears* the final context,
 /// and then provides it to the next evaluation.
-#[derive(Debug, Clone, Copy)]
-pub struct Cache {
+///
+/// The type parameter `S` is the [`Schema`](crate::snapshot::Schema) of the entries
+/// to include in the snapshots, see [`Cache::persist`].
+pub struct Cache<S = ()> {
     length: NonZeroUsize,
+    schema: PhantomData<fn() -> S>,
+}
+
+impl<S> fmt::Debug for Cache<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cache")
+            .field("length", &self.length)
+            .finish()
+    }
+}
+
+impl<S> Clone for Cache<S> {
+    fn clone(&self) -> Self {
+        *self
+    }
 }
 
+impl<S> Copy for Cache<S> {}
+
 impl Cache {
     /// Creates a new `Cache` layer with length set to `1`.
     pub fn new() -> Self {
         Self {
             length: NonZeroUsize::new(1).unwrap(),
+            schema: PhantomData,
         }
     }
 
     /// Creates a new `Cache` layer with the specified length.
     pub fn with_length(length: NonZeroUsize) -> Self {
-        Self { length }
+        Self {
+            length,
+            schema: PhantomData,
+        }
+    }
+}
+
+impl<S> Cache<S> {
+    /// Include the cached entries of the types listed in `S2` in the snapshots.
+    ///
+    /// The other entries are not saved.
+    #[cfg(feature = "snapshot")]
+    pub fn persist<S2: Schema>(self) -> Cache<S2> {
+        Cache {
+            length: self.length,
+            schema: PhantomData,
+        }
     }
 }
 
@@ -34,51 +80,63 @@ impl Default for Cache {
     }
 }
 
-impl<T, P> Layer<T, P> for Cache
+impl<T, P, S> Layer<T, P> for Cache<S>
 where
     P: ContextOperator<T>,
 {
-    type Operator = CacheOperator<P>;
+    type Operator = CacheOperator<P, S>;
     type Out = P::Out;
 
     fn layer(&self, inner: P) -> Self::Operator {
         CacheOperator {
             inner,
-            previous: Previous::default(),
-            limit: self.length.get() - 1,
+            previous: Some(Box::new(Previous(VecDeque::with_capacity(
+                self.length.get(),
+            )))),
+            length: self.length.get(),
+            steps: 0,
+            schema: PhantomData,
         }
     }
 }
 
-/// Wrapper for previous env context.
+static EMPTY: Map = Map::new();
+
+/// The previous env contexts, from the latest to the oldest.
+///
+/// The contexts are kept in a ring of at most the length of the [`Cache`] layer.
+/// It dereferences to the latest context, which is empty before the first evaluation.
 #[derive(Debug, Default)]
-pub struct Previous(Map);
+pub struct Previous(VecDeque<Map>);
 
 impl Previous {
-    fn take(&mut self) -> Self {
-        core::mem::take(self)
+    /// Push the latest context, evicting the oldest one if there are already `length` contexts.
+    fn push(&mut self, length: usize, map: Map) {
+        self.0.truncate(length - 1);
+        self.0.push_front(map);
     }
 
     /// Iterates over the previous context.
-    pub fn backward<F>(&self, mut f: F)
+    pub fn backward<F>(&self, f: F)
     where
         F: FnMut(&Map),
     {
-        f(&self.0);
-        if let Some(prev) = self.0.get::<Previous>() {
-            prev.backward(f);
-        }
+        self.0.iter().for_each(f);
     }
 
-    /// Iterates over the previous context mutably.
-    fn backward_mut<F>(&mut self, mut f: F)
-    where
-        F: FnMut(&mut Map),
-    {
-        f(&mut self.0);
-        if let Some(prev) = self.0.get_mut::<Previous>() {
-            prev.backward_mut(f);
-        }
+    /// Get an iterator over the previous contexts, from the latest to the oldest.
+    pub fn iter(&self) -> impl Iterator<Item = &Map> + '_ {
+        self.0.iter()
+    }
+
+    /// Get the `n`-th previous context, `0` being the latest.
+    pub fn nth(&self, n: usize) -> Option<&Map> {
+        self.0.get(n)
+    }
+
+    /// Get the number of the previous contexts.
+    pub fn depth(&self) -> usize {
+        self.0.len()
     }
 }
 
@@ -86,51 +144,139 @@ impl Deref for Previous {
     type Target = Map;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        self.0.front().unwrap_or(&EMPTY)
     }
 }
 
 /// Operator for `Cache`.
-#[derive(Debug)]
-pub struct CacheOperator<P> {
+pub struct CacheOperator<P, S = ()> {
     inner: P,
-    previous: Previous,
-    limit: usize,
+    /// Boxed to be moved in and out of the env without allocating,
+    /// only taken during the evaluation.
+    previous: Option<Box<Previous>>,
+    length: usize,
+    steps: usize,
+    schema: PhantomData<fn() -> S>,
+}
+
+impl<P: fmt::Debug, S> fmt::Debug for CacheOperator<P, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CacheOperator")
+            .field("inner", &self.inner)
+            .field("previous", &self.previous)
+            .field("length", &self.length)
+            .field("steps", &self.steps)
+            .finish()
+    }
+}
+
+/// The previous contexts are provided to the inner operator as [`Previous`].
+impl<T, P: Dependent<T>, S> Dependent<T> for CacheOperator<P, S> {
+    fn dependencies(&self, deps: &mut Dependencies) {
+        deps.provide_env::<Previous>();
+        self.inner.dependencies(deps);
+    }
+}
+
+/// The operator is ready when the cache is full and the inner operator is ready.
+impl<P, S> CacheOperator<P, S> {
+    fn previous_mut(&mut self) -> &mut Previous {
+        self.previous.get_or_insert_with(Default::default)
+    }
+}
+
+impl<P: Readiness, S> Readiness for CacheOperator<P, S> {
+    fn warm_up(&self) -> usize {
+        self.length.max(self.inner.warm_up())
+    }
+
+    fn is_ready(&self) -> bool {
+        self.steps >= self.length && self.inner.is_ready()
+    }
+}
+
+impl<P: Reset, S> Reset for CacheOperator<P, S> {
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.previous_mut().0.clear();
+        self.steps = 0;
+    }
+}
+
+/// Seed with the env contexts of the previous evaluations, from the oldest to the latest.
+///
+/// Only the latest `length` contexts are kept, and the inner operator is reset.
+impl<P, S, H> Seed<H> for CacheOperator<P, S>
+where
+    P: Reset,
+    H: IntoIterator<Item = Map>,
+{
+    fn seed(&mut self, seed: H) {
+        self.reset();
+        let length = self.length;
+        for map in seed {
+            self.previous_mut().push(length, map);
+            self.steps = self.steps.saturating_add(1);
+        }
+    }
+}
+
+/// The state of a [`CacheOperator`].
+#[cfg(feature = "snapshot")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheState<T> {
+    /// The number of steps performed.
+    pub steps: usize,
+    /// The saved entries of the cached contexts, from the latest to the oldest.
+    pub previous: Vec<T>,
+}
+
+/// Only the cached entries listed in the schema `S` are saved,
+/// the inner operator is assumed stateless.
+#[cfg(feature = "snapshot")]
+impl<P, S: Schema> Snapshot for CacheOperator<P, S> {
+    type State = CacheState<S::State>;
+
+    fn snapshot(&self) -> Self::State {
+        let mut previous = Vec::new();
+        if let Some(cached) = &self.previous {
+            cached.backward(|map| previous.push(S::save(map)));
+        }
+        CacheState {
+            steps: self.steps,
+            previous,
+        }
+    }
+
+    fn restore(&mut self, state: Self::State) {
+        self.steps = state.steps;
+        let length = self.length;
+        let cached = self.previous_mut();
+        cached.0.clear();
+        for entries in state.previous.into_iter().rev() {
+            let mut map = Map::new();
+            S::load(&mut map, entries);
+            cached.push(length, map);
+        }
+    }
 }
 
-impl<T, P> Operator<Value<T>> for CacheOperator<P>
+impl<T, P, S> Operator<Value<T>> for CacheOperator<P, S>
 where
     P: ContextOperator<T>,
 {
     type Output = Value<P::Out>;
 
     fn next(&mut self, mut input: Value<T>) -> Self::Output {
-        input.context_mut().env_mut().insert(self.previous.take());
+        // The ring is moved into the env and back, keeping its allocation.
+        let previous = self.previous.take().unwrap_or_default();
+        input.context_mut().env_mut().insert_boxed(previous);
+        self.steps = self.steps.saturating_add(1);
         let mut output = self.inner.next(input);
-        // Remove the previous context if the limit is reached.
-        // FIXME: There may be a better way to do this,
-        // like using a `Vec` or slice to store the previous contexts
-        // to avoid the recursive call.
-        if self.limit > 0 {
-            let limit = self.limit;
-            let mut count = 1;
-            output
-                .context_mut()
-                .env_mut()
-                .get_mut::<Previous>()
-                .unwrap()
-                .backward_mut(|ctx| {
-                    if count >= limit {
-                        ctx.remove::<Previous>();
-                    }
-                    count += 1;
-                });
-        } else {
-            output.context_mut().env_mut().remove::<Previous>();
-        }
-        self.previous
-            .0
-            .extend(core::mem::take(output.context_mut().env_mut()));
+        let env = output.context_mut().env_mut();
+        let mut previous = env.remove_boxed::<Previous>().unwrap_or_default();
+        previous.push(self.length, core::mem::take(env));
+        self.previous = Some(previous);
         output
     }
 }
@@ -188,4 +334,158 @@ mod tests {
             println!("current: {v}");
         });
     }
+
+    #[test]
+    fn seed() {
+        let op = input()
+            .map(|input: Value<i32>| {
+                let mut sum = 0;
+                input
+                    .context()
+                    .env()
+                    .get::<Previous>()
+                    .unwrap()
+                    .backward(|ctx| sum += ctx.get::<i32>().copied().unwrap_or(0));
+                input.map(|v, ctx| {
+                    ctx.env_mut().insert(v);
+                    v + sum
+                })
+            })
+            .with(Cache::with_length(2.try_into().unwrap()));
+        let mut op = op.finish();
+        assert!(!op.is_ready());
+        op.seed((1..=3).map(|x| {
+            let mut map = Map::new();
+            map.insert(x);
+            map
+        }));
+        assert!(op.is_ready());
+        // Only the latest two contexts (`2` and `3`) are kept.
+        assert_eq!(op.next(10), 15);
+        assert_eq!(op.next(20), 33);
+        op.reset();
+        assert!(!op.is_ready());
+        assert_eq!(op.next(10), 10);
+    }
+
+    #[cfg(feature = "snapshot")]
+    #[test]
+    fn snapshot() {
+        struct Sum<P>(P);
+
+        impl<P> Operator<Value<i64>> for Sum<P>
+        where
+            P: ContextOperator<i64>,
+        {
+            type Output = Value<P::Out>;
+
+            fn next(&mut self, mut input: Value<i64>) -> Self::Output {
+                input.apply(|v, ctx| {
+                    let mut sum = *v;
+                    ctx.env()
+                        .get::<Previous>()
+                        .unwrap()
+                        .backward(|prev| sum += prev.get::<i64>().copied().unwrap_or(0));
+                    ctx.env_mut().insert(sum);
+                });
+                self.0.next(input)
+            }
+        }
+
+        let op = || {
+            input()
+                .map(|input: Value<i64>| input.map(|_, ctx| *ctx.env().get::<i64>().unwrap()))
+                .with(layer_fn(Sum))
+                .with(Cache::with_length(2.try_into().unwrap()).persist::<(i64,)>())
+                .finish()
+        };
+
+        let data = [1, 2, 3, 4, 5];
+        let expected = data.into_iter().indicator(op()).collect::<Vec<_>>();
+        let mut first = op();
+        let mut outputs = data[..3].iter().map(|x| first.next(*x)).collect::<Vec<_>>();
+        let json = serde_json::to_string(&first.snapshot()).unwrap();
+        let mut second = op();
+        second.restore(serde_json::from_str(&json).unwrap());
+        outputs.extend(data[3..].iter().copied().indicator(second));
+        assert_eq!(outputs, expected);
+    }
+
+    #[test]
+    fn history() {
+        use crate::{
+            context::{insert_env_and_output, In, PrevN},
+            prelude::operator,
+        };
+        use alloc::vec::Vec;
+
+        #[operator(input = i32)]
+        fn lag(In(x): In<&i32>, PrevN(lag): PrevN<&i32, 2>) -> i32 {
+            x - lag.copied().unwrap_or(*x)
+        }
+
+        #[operator(input = I)]
+        fn sum<T>(#[input] x: &T, #[history(len = 2)] prev: impl Iterator<Item = &T>) -> T
+        where
+            T: core::ops::Add<Output = T> + Copy,
+        {
+            prev.fold(*x, |acc, x| acc + *x)
+        }
+
+        let op = insert_env_and_output(sum::<i32, i32, i32>)
+            .cache(2)
+            .finish();
+        // The output is inserted into the env, so the history holds the previous sums.
+        let data = [1, 2, 4, 8, 16];
+        assert_eq!(
+            data.into_iter().indicator(op).collect::<Vec<_>>(),
+            [1, 3, 8, 19, 43]
+        );
+
+        let op = insert_env_and_output(lag).cache(2).finish();
+        assert_eq!(
+            data.into_iter().indicator(op).collect::<Vec<_>>(),
+            [0, 0, 4, 8, 12]
+        );
+    }
+
+    #[test]
+    fn ring() {
+        use crate::context::output;
+        use alloc::vec::Vec;
+
+        let op = output(|x: usize, ctx| {
+            let previous = ctx.env().get::<Previous>().unwrap();
+            let output = (
+                previous.depth(),
+                previous.nth(2).and_then(|map| map.get::<usize>().copied()),
+            );
+            ctx.env_mut().insert(x);
+            output
+        })
+        .cache(3)
+        .finish();
+        let outputs = (0..5).indicator(op).collect::<Vec<_>>();
+        assert_eq!(
+            outputs,
+            [(0, None), (1, None), (2, None), (3, Some(0)), (3, Some(1))]
+        );
+
+        let op = output(|x: usize, ctx| {
+            let oldest = ctx
+                .env()
+                .get::<Previous>()
+                .unwrap()
+                .nth(499)
+                .and_then(|map| map.get::<usize>().copied());
+            ctx.env_mut().insert(x);
+            oldest
+        })
+        .cache(500)
+        .finish();
+        let outputs = (0..1000).indicator(op).collect::<Vec<_>>();
+        assert_eq!(outputs[499], None);
+        assert_eq!(outputs[500], Some(0));
+        assert_eq!(outputs[999], Some(499));
+    }
 }
@@ -1,14 +1,16 @@
-use alloc::boxed::Box;
+use alloc::sync::Arc;
 
 use crate::{
-    context::{ContextOperator, Value},
+    context::{ContextOperator, Dependencies, Dependent, Value},
+    readiness::Readiness,
+    reset::{Reset, Seed},
     Operator,
 };
 
 use super::Layer;
 
 /// Layer that adds data to the context.
-pub struct AddData<T>(Box<dyn Fn() -> Option<T> + Send + Sync>);
+pub struct AddData<T>(Arc<dyn Fn() -> Option<T> + Send + Sync>);
 
 impl<T> AddData<T>
 where
@@ -16,7 +18,7 @@ where
 {
     /// Create [`AddData`] layer with the given data provider.
     pub fn new(provider: impl Fn() -> Option<T> + Send + Sync + 'static) -> Self {
-        Self(Box::new(provider))
+        Self(Arc::new(provider))
     }
 
     /// Create [`AddData`] layer with the given data.
@@ -45,6 +47,7 @@ where
     fn layer(&self, operator: P) -> Self::Operator {
         AddDataOperator {
             data: (self.0)(),
+            provider: self.0.clone(),
             inner: operator,
         }
     }
@@ -56,6 +59,7 @@ where
 /// Panic if the data is not in the context if it is not provided.
 pub struct AddDataOperator<T, P> {
     data: Option<T>,
+    provider: Arc<dyn Fn() -> Option<T> + Send + Sync>,
     inner: P,
 }
 
@@ -79,3 +83,107 @@ where
         self.inner.next(input)
     }
 }
+
+impl<T, P> Readiness for AddDataOperator<T, P>
+where
+    P: Readiness,
+{
+    fn warm_up(&self) -> usize {
+        self.inner.warm_up()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+}
+
+/// The data is provided again, since the data context is restored on reset.
+impl<T, P> Reset for AddDataOperator<T, P>
+where
+    P: Reset,
+{
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.data = (self.provider)();
+    }
+}
+
+impl<S, T, P> Seed<S> for AddDataOperator<T, P>
+where
+    P: Seed<S>,
+{
+    fn seed(&mut self, seed: S) {
+        self.inner.seed(seed);
+        self.data = (self.provider)();
+    }
+}
+
+/// The data is provided if it is given, or else it is required from the data context.
+impl<T, I, P> Dependent<I> for AddDataOperator<T, P>
+where
+    P: Dependent<I>,
+    T: 'static,
+{
+    fn dependencies(&self, deps: &mut Dependencies) {
+        if self.data.is_some() {
+            deps.provide_data::<T>();
+        } else {
+            deps.require_data::<T>();
+        }
+        self.inner.dependencies(deps);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        context::Map,
+        context::{insert_and_output, ContextOperatorExt, Data},
+        prelude::operator,
+        IndicatorIteratorExt, Operator, Reset,
+    };
+    use alloc::vec::Vec;
+
+    #[derive(Debug, Clone, Copy)]
+    struct Count(usize);
+
+    #[derive(Debug, Clone, Copy)]
+    struct Step(usize);
+
+    #[operator(input = i32)]
+    fn count(Data(count): Data<Option<&Count>>, Data(step): Data<&Step>) -> (usize, Option<Count>) {
+        let count = count.map_or(0, |count| count.0 + step.0);
+        (count, Some(Count(count)))
+    }
+
+    #[test]
+    fn reset() {
+        let mut op = insert_and_output(count).provide(Step(1)).finish();
+        let outputs = (0..3).map(|x| op.next(x)).collect::<Vec<_>>();
+        assert_eq!(outputs, [0, 1, 2]);
+        op.reset();
+        assert!((0..3).indicator(op).eq(outputs));
+    }
+
+    #[test]
+    fn reset_with_data() {
+        let mut data = Map::new();
+        data.insert(Step(2));
+        let mut op = insert_and_output(count).finish_with_data(data);
+        let outputs = (0..3).map(|x| op.next(x)).collect::<Vec<_>>();
+        assert_eq!(outputs, [0, 2, 4]);
+        op.reset();
+        assert!((0..3).indicator(op).eq(outputs));
+
+        let mut op = insert_and_output(count).finish_with(|| {
+            let mut data = Map::new();
+            data.insert(Step(1));
+            data.insert(Count(10));
+            data
+        });
+        let outputs = (0..3).map(|x| op.next(x)).collect::<Vec<_>>();
+        assert_eq!(outputs, [11, 12, 13]);
+        op.reset();
+        assert!((0..3).indicator(op).eq(outputs));
+    }
+}
@@ -0,0 +1,283 @@
+use crate::{
+    context::{ContextOperator, Dependencies, Dependent, Map, RefOperator, Value},
+    readiness::Readiness,
+    reset::{Reset, Seed},
+    Operator,
+};
+
+use super::Layer;
+
+/// Value whose fields can be inserted into the env context as separate entries,
+/// so that they can be extracted without the whole value.
+///
+/// Use `#[derive(Entries)]` to implement it.
+pub trait Entries: Send + Sync + 'static {
+    /// Insert the entries into the map.
+    fn insert_entries(&self, map: &mut Map);
+
+    /// Declare the types of the entries provided to the env context.
+    fn provide_entries(deps: &mut Dependencies);
+}
+
+/// Layer that inserts a value and its [`Entries`] into the env context
+/// with a [`RefOperator`].
+pub struct InsertEnvEntries<F>(pub F);
+
+impl<T, P, R, Out, F> Layer<T, P> for InsertEnvEntries<F>
+where
+    P: ContextOperator<T>,
+    R: for<'a> RefOperator<'a, T, Output = Out>,
+    Out: Entries,
+    F: Fn() -> R,
+{
+    type Operator = InsertEnvEntriesOperator<P, R>;
+    type Out = P::Out;
+
+    #[inline]
+    fn layer(&self, operator: P) -> Self::Operator {
+        InsertEnvEntriesOperator {
+            inner: operator,
+            insert: (self.0)(),
+        }
+    }
+}
+
+/// Operator that inserts a value and its entries into the env context.
+pub struct InsertEnvEntriesOperator<P, R> {
+    inner: P,
+    insert: R,
+}
+
+impl<T, P, R, Out> Operator<Value<T>> for InsertEnvEntriesOperator<P, R>
+where
+    P: ContextOperator<T>,
+    R: for<'a> RefOperator<'a, T, Output = Out>,
+    Out: Entries,
+{
+    type Output = Value<P::Out>;
+
+    #[inline]
+    fn next(&mut self, mut input: Value<T>) -> Self::Output {
+        let value = self.insert.next(input.as_ref());
+        let env = input.context_mut().env_mut();
+        value.insert_entries(env);
+        env.insert(value);
+        self.inner.next(input)
+    }
+}
+
+/// Layer that inserts a value and its [`Entries`] into the env context,
+/// and a value into the data context at the same time, with a [`RefOperator`].
+pub struct InsertEntries<F>(pub F);
+
+impl<T, P, R, Env, Data, F> Layer<T, P> for InsertEntries<F>
+where
+    P: ContextOperator<T>,
+    F: Fn() -> R,
+    R: for<'a> RefOperator<'a, T, Output = (Env, Option<Data>)>,
+    Env: Entries,
+    Data: Send + Sync + 'static,
+{
+    type Operator = InsertEntriesOperator<P, R>;
+    type Out = P::Out;
+
+    #[inline]
+    fn layer(&self, operator: P) -> Self::Operator {
+        InsertEntriesOperator {
+            inner: operator,
+            insert: (self.0)(),
+        }
+    }
+}
+
+/// Operator that inserts a value and its entries into the env context,
+/// and a value into the data context.
+pub struct InsertEntriesOperator<P, R> {
+    inner: P,
+    insert: R,
+}
+
+impl<T, P, R, Env, Data> Operator<Value<T>> for InsertEntriesOperator<P, R>
+where
+    P: ContextOperator<T>,
+    R: for<'a> RefOperator<'a, T, Output = (Env, Option<Data>)>,
+    Env: Entries,
+    Data: Send + Sync + 'static,
+{
+    type Output = Value<P::Out>;
+
+    #[inline]
+    fn next(&mut self, mut input: Value<T>) -> Self::Output {
+        let (value, data) = self.insert.next(input.as_ref());
+        let env = input.context_mut().env_mut();
+        value.insert_entries(env);
+        env.insert(value);
+        let mut out = self.inner.next(input);
+        // The data is inserted after the inner operator is called, as `insert` does.
+        if let Some(data) = data {
+            out.context_mut().data_mut().insert(data);
+        }
+        out
+    }
+}
+
+macro_rules! delegate {
+    ($op:ident) => {
+        impl<P, R> Readiness for $op<P, R>
+        where
+            P: Readiness,
+        {
+            fn warm_up(&self) -> usize {
+                self.inner.warm_up()
+            }
+
+            fn is_ready(&self) -> bool {
+                self.inner.is_ready()
+            }
+        }
+
+        impl<P, R> Reset for $op<P, R>
+        where
+            P: Reset,
+            R: Reset,
+        {
+            fn reset(&mut self) {
+                self.inner.reset();
+                self.insert.reset();
+            }
+        }
+
+        impl<S, P, R> Seed<S> for $op<P, R>
+        where
+            P: Seed<S>,
+            R: Reset,
+        {
+            /// Seed the inner operator.
+            ///
+            /// The insert operator is not seeded but reset,
+            /// since the seed is of the inner operator.
+            fn seed(&mut self, seed: S) {
+                self.inner.seed(seed);
+                self.insert.reset();
+            }
+        }
+    };
+}
+
+delegate!(InsertEnvEntriesOperator);
+delegate!(InsertEntriesOperator);
+
+impl<T, P, R, Out> Dependent<T> for InsertEnvEntriesOperator<P, R>
+where
+    P: Dependent<T>,
+    R: for<'a> RefOperator<'a, T, Output = Out> + Dependent<T>,
+    Out: Entries,
+{
+    fn dependencies(&self, deps: &mut Dependencies) {
+        self.insert.dependencies(deps);
+        deps.provide_env::<Out>();
+        Out::provide_entries(deps);
+        self.inner.dependencies(deps);
+    }
+}
+
+impl<T, P, R, Env, Data> Dependent<T> for InsertEntriesOperator<P, R>
+where
+    P: Dependent<T>,
+    R: for<'a> RefOperator<'a, T, Output = (Env, Option<Data>)> + Dependent<T>,
+    Env: Entries,
+    Data: 'static,
+{
+    fn dependencies(&self, deps: &mut Dependencies) {
+        self.insert.dependencies(deps);
+        deps.provide_env::<Env>();
+        Env::provide_entries(deps);
+        deps.provide_data::<Data>();
+        self.inner.dependencies(deps);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        context::{input, insert_env_and_output, ContextOperatorExt, Env, In},
+        key,
+        prelude::{operator, Entries},
+        IndicatorIteratorExt,
+    };
+    use alloc::vec::Vec;
+
+    key! {
+        Upper: f64;
+        Lower: f64;
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Width(f64);
+
+    #[derive(Clone, Entries)]
+    struct Bands {
+        #[entry(key = Upper)]
+        upper: f64,
+        #[entry(key = Lower)]
+        lower: f64,
+        width: Width,
+        #[entry(skip)]
+        _count: usize,
+    }
+
+    fn make_bands(x: f64) -> Bands {
+        Bands {
+            upper: x + 1.0,
+            lower: x - 1.0,
+            width: Width(2.0),
+            _count: 1,
+        }
+    }
+
+    #[operator(input = f64)]
+    fn bands(In(x): In<&f64>) -> Bands {
+        make_bands(*x)
+    }
+
+    #[operator(input = f64)]
+    fn with_data(In(x): In<&f64>) -> (Bands, Option<usize>) {
+        (make_bands(*x), Some(1))
+    }
+
+    #[operator(input = f64)]
+    fn position(In(x): In<&f64>, #[env(key = Lower)] lower: &f64, Env(width): Env<&Width>) -> f64 {
+        (x - lower) / width.0
+    }
+
+    #[test]
+    fn entries() {
+        let op = insert_env_and_output(position)
+            .insert_env_entries(bands)
+            .try_finish()
+            .unwrap();
+        assert_eq!(
+            [1.0, 2.0].into_iter().indicator(op).collect::<Vec<_>>(),
+            [0.5, 0.5]
+        );
+        let op = insert_env_and_output(position)
+            .insert_entries(with_data)
+            .try_finish()
+            .unwrap();
+        assert_eq!([1.0].into_iter().indicator(op).collect::<Vec<_>>(), [0.5]);
+    }
+
+    #[test]
+    fn missing() {
+        // The entries are only provided to the inner layers.
+        let Err(err) = input::<f64>()
+            .insert_env_entries(bands)
+            .insert_env(position)
+            .try_finish()
+        else {
+            panic!("the dependencies should be missing");
+        };
+        assert_eq!(err.dependencies().len(), 2);
+        assert!(err.dependencies()[0].name().ends_with("Lower>"));
+    }
+}
@@ -1,5 +1,7 @@
 use crate::{
-    context::{ContextOperator, RefOperator, Value},
+    context::{ContextOperator, Dependencies, Dependent, RefOperator, Value},
+    readiness::Readiness,
+    reset::{Reset, Seed},
     Operator,
 };
 
@@ -152,6 +154,164 @@ where
     }
 }
 
+impl<P, R> Readiness for InsertOperator<P, R>
+where
+    P: Readiness,
+{
+    fn warm_up(&self) -> usize {
+        self.inner.warm_up()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+}
+
+impl<P, R> Reset for InsertOperator<P, R>
+where
+    P: Reset,
+    R: Reset,
+{
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.insert.reset();
+    }
+}
+
+impl<S, P, R> Seed<S> for InsertOperator<P, R>
+where
+    P: Seed<S>,
+    R: Reset,
+{
+    /// Seed the inner operator.
+    ///
+    /// The insert operator is not seeded but reset,
+    /// since the seed is of the inner operator.
+    fn seed(&mut self, seed: S) {
+        self.inner.seed(seed);
+        self.insert.reset();
+    }
+}
+
+impl<P, R> Readiness for InsertDataOperator<P, R>
+where
+    P: Readiness,
+{
+    fn warm_up(&self) -> usize {
+        self.inner.warm_up()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+}
+
+impl<P, R> Reset for InsertDataOperator<P, R>
+where
+    P: Reset,
+    R: Reset,
+{
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.insert.reset();
+    }
+}
+
+impl<S, P, R> Seed<S> for InsertDataOperator<P, R>
+where
+    P: Seed<S>,
+    R: Reset,
+{
+    /// Seed the inner operator.
+    ///
+    /// The insert operator is not seeded but reset,
+    /// since the seed is of the inner operator.
+    fn seed(&mut self, seed: S) {
+        self.inner.seed(seed);
+        self.insert.reset();
+    }
+}
+
+impl<P, R> Readiness for InsertWithDataOperator<P, R>
+where
+    P: Readiness,
+{
+    fn warm_up(&self) -> usize {
+        self.inner.warm_up()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+}
+
+impl<P, R> Reset for InsertWithDataOperator<P, R>
+where
+    P: Reset,
+    R: Reset,
+{
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.insert.reset();
+    }
+}
+
+impl<S, P, R> Seed<S> for InsertWithDataOperator<P, R>
+where
+    P: Seed<S>,
+    R: Reset,
+{
+    /// Seed the inner operator.
+    ///
+    /// The insert operator is not seeded but reset,
+    /// since the seed is of the inner operator.
+    fn seed(&mut self, seed: S) {
+        self.inner.seed(seed);
+        self.insert.reset();
+    }
+}
+
+impl<T, P, R, Out> Dependent<T> for InsertOperator<P, R>
+where
+    P: Dependent<T>,
+    R: for<'a> RefOperator<'a, T, Output = Out> + Dependent<T>,
+    Out: 'static,
+{
+    fn dependencies(&self, deps: &mut Dependencies) {
+        self.insert.dependencies(deps);
+        deps.provide_env::<Out>();
+        self.inner.dependencies(deps);
+    }
+}
+
+impl<T, P, R, Out> Dependent<T> for InsertDataOperator<P, R>
+where
+    P: Dependent<T>,
+    R: for<'a> RefOperator<'a, T, Output = Option<Out>> + Dependent<T>,
+    Out: 'static,
+{
+    fn dependencies(&self, deps: &mut Dependencies) {
+        self.insert.dependencies(deps);
+        deps.provide_data::<Out>();
+        self.inner.dependencies(deps);
+    }
+}
+
+impl<T, P, R, Env, Data> Dependent<T> for InsertWithDataOperator<P, R>
+where
+    P: Dependent<T>,
+    R: for<'a> RefOperator<'a, T, Output = (Env, Option<Data>)> + Dependent<T>,
+    Env: 'static,
+    Data: 'static,
+{
+    fn dependencies(&self, deps: &mut Dependencies) {
+        self.insert.dependencies(deps);
+        deps.provide_env::<Env>();
+        deps.provide_data::<Data>();
+        self.inner.dependencies(deps);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -240,4 +400,42 @@ mod tests {
             [None, Some(0), Some(1), Some(1), Some(2)]
         );
     }
+
+    #[test]
+    fn insert_keyed() {
+        use crate::{
+            context::{Env, In, Keyed},
+            key,
+            prelude::operator,
+        };
+
+        key! {
+            Double: f64;
+            Triple: f64;
+        }
+
+        #[operator(input = f64)]
+        fn double(In(x): In<&f64>) -> Keyed<Double> {
+            Keyed::new(x * 2.0)
+        }
+
+        #[operator(input = f64)]
+        fn triple(In(x): In<&f64>) -> Keyed<Triple> {
+            Keyed::new(x * 3.0)
+        }
+
+        #[operator(input = f64)]
+        fn sum(Env(a): Env<&Keyed<Double>>, Env(b): Env<&Keyed<Triple>>) -> f64 {
+            **a + **b
+        }
+
+        let op = insert_env_and_output(sum)
+            .insert_env(triple)
+            .insert_env(double)
+            .finish();
+        assert_eq!(
+            [1.0, 2.0].into_iter().indicator(op).collect::<Vec<_>>(),
+            [5.0, 10.0]
+        );
+    }
 }
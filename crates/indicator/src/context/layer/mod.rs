@@ -14,6 +14,9 @@ pub mod cache;
 /// Layer that inserts a value into the context.
 pub mod insert;
 
+/// Layer that inserts the entries of a value into the context.
+pub mod entries;
+
 /// Layer that used to inspect the context.
 pub mod inspect;
 
@@ -29,6 +32,14 @@ pub mod then;
 /// Optional layer.
 pub mod optional;
 
+/// Layer that evaluates inserts concurrently.
+#[cfg(feature = "parallel")]
+pub mod parallel;
+
+/// Layer that profiles the evaluations.
+#[cfg(feature = "profile")]
+pub mod profile;
+
 /// Layer.
 /// Convert an [`ContextOperator`] to another [`ContextOperator`]
 pub trait Layer<In, P>
@@ -1,5 +1,7 @@
 use crate::{
-    context::{ContextOperator, Value},
+    context::{ContextOperator, Dependencies, Dependent, Value},
+    readiness::Readiness,
+    reset::{Reset, Seed},
     Operator,
 };
 
@@ -51,3 +53,62 @@ where
         }
     }
 }
+
+impl<In, A, B> Dependent<In> for Either<A, B>
+where
+    A: Dependent<In>,
+    B: Dependent<In>,
+{
+    fn dependencies(&self, deps: &mut Dependencies) {
+        match self {
+            Either::Left(a) => a.dependencies(deps),
+            Either::Right(b) => b.dependencies(deps),
+        }
+    }
+}
+
+impl<A, B> Readiness for Either<A, B>
+where
+    A: Readiness,
+    B: Readiness,
+{
+    fn warm_up(&self) -> usize {
+        match self {
+            Either::Left(a) => a.warm_up(),
+            Either::Right(b) => b.warm_up(),
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        match self {
+            Either::Left(a) => a.is_ready(),
+            Either::Right(b) => b.is_ready(),
+        }
+    }
+}
+
+impl<A, B> Reset for Either<A, B>
+where
+    A: Reset,
+    B: Reset,
+{
+    fn reset(&mut self) {
+        match self {
+            Either::Left(a) => a.reset(),
+            Either::Right(b) => b.reset(),
+        }
+    }
+}
+
+impl<S, A, B> Seed<S> for Either<A, B>
+where
+    A: Seed<S>,
+    B: Seed<S>,
+{
+    fn seed(&mut self, seed: S) {
+        match self {
+            Either::Left(a) => a.seed(seed),
+            Either::Right(b) => b.seed(seed),
+        }
+    }
+}
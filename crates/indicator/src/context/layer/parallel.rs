@@ -0,0 +1,223 @@
+use crate::{
+    context::{ContextOperator, Dependencies, Dependent, RefOperator, Value},
+    readiness::Readiness,
+    reset::{Reset, Seed},
+    Operator,
+};
+
+use super::Layer;
+
+/// Layer that evaluates a tuple of independent [`RefOperator`]s concurrently
+/// with `rayon`, and then inserts their outputs into the env context.
+///
+/// The operators are evaluated over the same context, so they cannot see the outputs of each other.
+/// Tuples of 2 to 6 operators are supported.
+pub struct ParInsertEnv<F>(pub F);
+
+/// Operator that evaluates [`RefOperator`]s concurrently and inserts their outputs into the env context.
+pub struct ParInsertEnvOperator<P, R> {
+    inner: P,
+    insert: R,
+}
+
+/// Tuple of [`RefOperator`]s that can be evaluated concurrently.
+pub trait ParRefOperators<T> {
+    /// Evaluate the operators concurrently and insert their outputs into the env context.
+    fn next_and_insert(&mut self, input: &mut Value<T>);
+}
+
+impl<T, P, F, R> Layer<T, P> for ParInsertEnv<F>
+where
+    P: ContextOperator<T>,
+    F: Fn() -> R,
+    R: ParRefOperators<T>,
+{
+    type Operator = ParInsertEnvOperator<P, R>;
+    type Out = P::Out;
+
+    #[inline]
+    fn layer(&self, operator: P) -> Self::Operator {
+        ParInsertEnvOperator {
+            inner: operator,
+            insert: (self.0)(),
+        }
+    }
+}
+
+impl<T, P, R> Operator<Value<T>> for ParInsertEnvOperator<P, R>
+where
+    P: ContextOperator<T>,
+    R: ParRefOperators<T>,
+{
+    type Output = Value<P::Out>;
+
+    #[inline]
+    fn next(&mut self, mut input: Value<T>) -> Self::Output {
+        self.insert.next_and_insert(&mut input);
+        self.inner.next(input)
+    }
+}
+
+/// Evaluate the operators with nested [`rayon::join`]s,
+/// into nested pairs of outputs, e.g. `(o1, (o2, o3))`.
+macro_rules! join {
+    ($value:ident; $op:ident) => {
+        $op.next($value)
+    };
+    ($value:ident; $op:ident, $($rest:ident),+) => {
+        rayon::join(move || $op.next($value), move || join!($value; $($rest),+))
+    };
+}
+
+/// The pattern of the nested pairs of outputs.
+macro_rules! nested {
+    ($v:ident) => {
+        $v
+    };
+    ($v:ident, $($rest:ident),+) => {
+        ($v, nested!($($rest),+))
+    };
+}
+
+macro_rules! tuple {
+    ($($r:ident $o:ident $op:ident $v:ident),+) => {
+        impl<T, $($r, $o),+> ParRefOperators<T> for ($($r,)+)
+        where
+            T: Sync,
+            $(
+                $r: for<'a> RefOperator<'a, T, Output = $o> + Send,
+                $o: Send + Sync + 'static,
+            )+
+        {
+            fn next_and_insert(&mut self, input: &mut Value<T>) {
+                let nested!($($v),+) = {
+                    let value = input.as_ref();
+                    let ($($op,)+) = self;
+                    join!(value; $($op),+)
+                };
+                let env = input.context_mut().env_mut();
+                $(env.insert($v);)+
+            }
+        }
+
+        /// The operators cannot see the outputs of each other.
+        impl<T, P, $($r, $o),+> Dependent<T> for ParInsertEnvOperator<P, ($($r,)+)>
+        where
+            P: Dependent<T>,
+            $(
+                $r: for<'a> RefOperator<'a, T, Output = $o> + Dependent<T>,
+                $o: 'static,
+            )+
+        {
+            fn dependencies(&self, deps: &mut Dependencies) {
+                let ($($op,)+) = &self.insert;
+                $($op.dependencies(deps);)+
+                $(deps.provide_env::<$o>();)+
+                self.inner.dependencies(deps);
+            }
+        }
+
+        impl<P, $($r),+> Reset for ParInsertEnvOperator<P, ($($r,)+)>
+        where
+            P: Reset,
+            $($r: Reset,)+
+        {
+            fn reset(&mut self) {
+                self.inner.reset();
+                let ($($op,)+) = &mut self.insert;
+                $($op.reset();)+
+            }
+        }
+
+        impl<S, P, $($r),+> Seed<S> for ParInsertEnvOperator<P, ($($r,)+)>
+        where
+            P: Seed<S>,
+            $($r: Reset,)+
+        {
+            /// Seed the inner operator.
+            ///
+            /// The insert operators are not seeded but reset,
+            /// since the seed is of the inner operator.
+            fn seed(&mut self, seed: S) {
+                self.inner.seed(seed);
+                let ($($op,)+) = &mut self.insert;
+                $($op.reset();)+
+            }
+        }
+    };
+}
+
+tuple!(R1 O1 op1 v1, R2 O2 op2 v2);
+tuple!(R1 O1 op1 v1, R2 O2 op2 v2, R3 O3 op3 v3);
+tuple!(R1 O1 op1 v1, R2 O2 op2 v2, R3 O3 op3 v3, R4 O4 op4 v4);
+tuple!(R1 O1 op1 v1, R2 O2 op2 v2, R3 O3 op3 v3, R4 O4 op4 v4, R5 O5 op5 v5);
+tuple!(R1 O1 op1 v1, R2 O2 op2 v2, R3 O3 op3 v3, R4 O4 op4 v4, R5 O5 op5 v5, R6 O6 op6 v6);
+
+impl<P, R> Readiness for ParInsertEnvOperator<P, R>
+where
+    P: Readiness,
+{
+    fn warm_up(&self) -> usize {
+        self.inner.warm_up()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        context::{insert_env_and_output, ContextOperatorExt, Dependency, Env, In, Scope},
+        prelude::operator,
+        IndicatorIteratorExt,
+    };
+    use alloc::vec::Vec;
+
+    #[operator(input = f64)]
+    fn double(In(x): In<&f64>) -> i64 {
+        (x * 2.0) as i64
+    }
+
+    #[operator(input = f64)]
+    fn half(In(x): In<&f64>) -> f32 {
+        (x / 2.0) as f32
+    }
+
+    #[operator(input = f64)]
+    fn sum(Env(x): Env<&i64>, Env(y): Env<&f32>) -> f64 {
+        *x as f64 + *y as f64
+    }
+
+    #[operator(input = f64)]
+    fn scaled(Env(x): Env<&i64>) -> f32 {
+        *x as f32 * 0.5
+    }
+
+    #[test]
+    fn par_insert_env() {
+        let op = insert_env_and_output(sum)
+            .par_insert_env(|| (double(), half()))
+            .try_finish()
+            .unwrap();
+        assert_eq!(
+            [1.0, 2.0, 4.0]
+                .into_iter()
+                .indicator(op)
+                .collect::<Vec<_>>(),
+            [2.5, 5.0, 10.0]
+        );
+    }
+
+    #[test]
+    fn independent() {
+        let Err(err) = insert_env_and_output(sum)
+            .par_insert_env(|| (double(), scaled()))
+            .try_finish()
+        else {
+            panic!("the operators should not see the outputs of each other");
+        };
+        assert_eq!(err.dependencies(), [Dependency::of::<i64>(Scope::Env)]);
+    }
+}
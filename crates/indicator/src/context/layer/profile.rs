@@ -0,0 +1,378 @@
+use core::{
+    any::{type_name, TypeId},
+    fmt,
+};
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use alloc::vec::Vec;
+
+use crate::{
+    context::{Context, ContextOperator, Dependencies, Dependent, RefOperator, Value, ValueRef},
+    readiness::Readiness,
+    reset::{Reset, Seed},
+    Operator,
+};
+
+use super::Layer;
+
+/// The statistics of a profiled step.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// The number of evaluations.
+    pub calls: u64,
+    /// The total time spent in the evaluations.
+    pub total: Duration,
+    /// The longest evaluation.
+    pub max: Duration,
+    /// The number of entries in the env context at the last evaluation.
+    pub env: usize,
+    /// The number of entries in the data context at the last evaluation.
+    pub data: usize,
+    /// The names of the types inserted into the env context.
+    pub inserted: Vec<&'static str>,
+}
+
+impl Stats {
+    /// The mean time of the evaluations.
+    pub fn mean(&self) -> Duration {
+        match u32::try_from(self.calls) {
+            Ok(0) => Duration::ZERO,
+            Ok(calls) => self.total / calls,
+            Err(_) => Duration::from_secs_f64(self.total.as_secs_f64() / self.calls as f64),
+        }
+    }
+
+    fn record(
+        &mut self,
+        elapsed: Duration,
+        context: &Context,
+        inserted: impl IntoIterator<Item = &'static str>,
+    ) {
+        self.calls += 1;
+        self.total += elapsed;
+        self.max = self.max.max(elapsed);
+        self.env = context.env().len();
+        self.data = context.data().len();
+        for name in inserted {
+            if !self.inserted.contains(&name) {
+                self.inserted.push(name);
+            }
+        }
+    }
+}
+
+/// The in-memory profile of the steps, in the order of their first evaluation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report(Vec<(&'static str, Stats)>);
+
+impl Report {
+    /// Get the statistics of the step with the given name.
+    pub fn get(&self, name: &str) -> Option<&Stats> {
+        self.0
+            .iter()
+            .find_map(|(step, stats)| (*step == name).then_some(stats))
+    }
+
+    /// Get an iterator over the steps and their statistics.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &Stats)> {
+        self.0.iter().map(|(name, stats)| (*name, stats))
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<24} {:>8} {:>12} {:>12} {:>12} {:>5} {:>5}  inserted",
+            "step", "calls", "total", "mean", "max", "env", "data"
+        )?;
+        for (name, stats) in self.iter() {
+            writeln!(
+                f,
+                "{:<24} {:>8} {:>12?} {:>12?} {:>12?} {:>5} {:>5}  {}",
+                name,
+                stats.calls,
+                stats.total,
+                stats.mean(),
+                stats.max,
+                stats.env,
+                stats.data,
+                stats.inserted.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Profiler collecting the statistics of the profiled steps.
+///
+/// The clones share the same profile. Each evaluation is also emitted
+/// as a `tracing` span named `profile` with a `DEBUG` event of the statistics.
+#[derive(Debug, Clone, Default)]
+pub struct Profiler(Arc<Mutex<Report>>);
+
+impl Profiler {
+    /// Create a new profiler.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the report of the profile.
+    pub fn report(&self) -> Report {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Clear the profile.
+    pub fn clear(&self) {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .0
+            .clear();
+    }
+
+    /// Create a [`Profile`] layer recording the evaluations of the operator
+    /// it wraps (including the inner layers) as the step `name`.
+    pub fn layer(&self, name: &'static str) -> Profile {
+        Profile {
+            name,
+            profiler: self.clone(),
+        }
+    }
+
+    /// Wrap the constructor of a [`RefOperator`] to record its evaluations as the step `name`,
+    /// e.g. `insert_env(profiler.step("ema", ema))`.
+    ///
+    /// Only the time of the [`RefOperator`] itself is recorded,
+    /// and its output type is recorded as the inserted type.
+    pub fn step<R>(&self, name: &'static str, f: impl Fn() -> R) -> impl Fn() -> Profiled<R> {
+        let profiler = self.clone();
+        move || Profiled {
+            name,
+            profiler: profiler.clone(),
+            inner: f(),
+        }
+    }
+
+    fn record(
+        &self,
+        name: &'static str,
+        elapsed: Duration,
+        context: &Context,
+        inserted: impl IntoIterator<Item = &'static str>,
+    ) {
+        let mut report = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        let idx = match report.0.iter().position(|(step, _)| *step == name) {
+            Some(idx) => idx,
+            None => {
+                report.0.push((name, Stats::default()));
+                report.0.len() - 1
+            }
+        };
+        let stats = &mut report.0[idx].1;
+        stats.record(elapsed, context, inserted);
+        tracing::debug!(
+            step = name,
+            elapsed = ?elapsed,
+            env = stats.env,
+            data = stats.data,
+            "evaluated"
+        );
+    }
+}
+
+/// [`RefOperator`] recording its evaluations, created by [`Profiler::step`].
+pub struct Profiled<R> {
+    name: &'static str,
+    profiler: Profiler,
+    inner: R,
+}
+
+impl<'a, In, R> Operator<ValueRef<'a, In>> for Profiled<R>
+where
+    R: RefOperator<'a, In>,
+{
+    type Output = R::Output;
+
+    fn next(&mut self, input: ValueRef<'a, In>) -> Self::Output {
+        let context = input.context();
+        let span = tracing::debug_span!("profile", step = self.name).entered();
+        let start = Instant::now();
+        let output = self.inner.next(input);
+        let elapsed = start.elapsed();
+        self.profiler
+            .record(self.name, elapsed, context, [type_name::<R::Output>()]);
+        drop(span);
+        output
+    }
+}
+
+impl<In, R: Dependent<In>> Dependent<In> for Profiled<R> {
+    fn dependencies(&self, deps: &mut Dependencies) {
+        self.inner.dependencies(deps);
+    }
+}
+
+impl<R: Reset> Reset for Profiled<R> {
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+impl<S, R: Seed<S>> Seed<S> for Profiled<R> {
+    fn seed(&mut self, seed: S) {
+        self.inner.seed(seed);
+    }
+}
+
+/// Layer that records the evaluations of the operator it wraps, created by [`Profiler::layer`].
+///
+/// The inserted types are the types found in the env context after the evaluation
+/// but not before, so the types taken by an inner [`Cache`](super::cache::Cache) layer are not included.
+#[derive(Debug, Clone)]
+pub struct Profile {
+    name: &'static str,
+    profiler: Profiler,
+}
+
+impl<T, P> Layer<T, P> for Profile
+where
+    P: ContextOperator<T>,
+{
+    type Operator = ProfileOperator<P>;
+    type Out = P::Out;
+
+    fn layer(&self, inner: P) -> Self::Operator {
+        ProfileOperator {
+            name: self.name,
+            profiler: self.profiler.clone(),
+            before: HashSet::new(),
+            inner,
+        }
+    }
+}
+
+/// Operator for [`Profile`].
+pub struct ProfileOperator<P> {
+    name: &'static str,
+    profiler: Profiler,
+    // The types in the env context before the evaluation, kept to reuse the allocation.
+    before: HashSet<TypeId>,
+    inner: P,
+}
+
+impl<T, P> Operator<Value<T>> for ProfileOperator<P>
+where
+    P: ContextOperator<T>,
+{
+    type Output = Value<P::Out>;
+
+    fn next(&mut self, input: Value<T>) -> Self::Output {
+        self.before.clear();
+        self.before
+            .extend(input.context().env().types().map(|(id, _)| id));
+        let span = tracing::debug_span!("profile", step = self.name).entered();
+        let start = Instant::now();
+        let output = self.inner.next(input);
+        let elapsed = start.elapsed();
+        let env = output.context().env();
+        self.profiler.record(
+            self.name,
+            elapsed,
+            output.context(),
+            env.types()
+                .filter(|(id, _)| !self.before.contains(id))
+                .map(|(_, name)| name),
+        );
+        drop(span);
+        output
+    }
+}
+
+impl<T, P: Dependent<T>> Dependent<T> for ProfileOperator<P> {
+    fn dependencies(&self, deps: &mut Dependencies) {
+        self.inner.dependencies(deps);
+    }
+}
+
+impl<P: Readiness> Readiness for ProfileOperator<P> {
+    fn warm_up(&self) -> usize {
+        self.inner.warm_up()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+}
+
+impl<P: Reset> Reset for ProfileOperator<P> {
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+impl<S, P: Seed<S>> Seed<S> for ProfileOperator<P> {
+    fn seed(&mut self, seed: S) {
+        self.inner.seed(seed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        context::{insert_env_and_output, ContextOperatorExt, Env, In},
+        prelude::operator,
+        IndicatorIteratorExt,
+    };
+
+    #[operator(input = f64)]
+    fn double(In(x): In<&f64>) -> i64 {
+        (x * 2.0) as i64
+    }
+
+    #[operator(input = f64)]
+    fn half(Env(x): Env<&i64>) -> f64 {
+        *x as f64 / 2.0
+    }
+
+    #[test]
+    fn report() {
+        let profiler = Profiler::new();
+        let op = insert_env_and_output(half)
+            .insert_env(profiler.step("double", double))
+            .with(profiler.layer("total"))
+            .try_finish()
+            .unwrap();
+        assert_eq!([1.0, 2.0, 3.0].into_iter().indicator(op).count(), 3);
+
+        let report = profiler.report();
+        let names = report.iter().map(|(name, _)| name).collect::<Vec<_>>();
+        assert_eq!(names, ["double", "total"]);
+        let step = report.get("double").unwrap();
+        assert_eq!(step.calls, 3);
+        assert_eq!(step.inserted, ["i64"]);
+        assert_eq!(step.env, 0);
+        let total = report.get("total").unwrap();
+        assert_eq!(total.calls, 3);
+        assert_eq!(total.env, 2);
+        assert!(total.total >= step.total);
+        assert!(total.inserted.contains(&"i64") && total.inserted.contains(&"f64"));
+        assert!(report
+            .to_string()
+            .lines()
+            .nth(1)
+            .unwrap()
+            .starts_with("double"));
+
+        profiler.clear();
+        assert!(profiler.report().iter().next().is_none());
+    }
+}
@@ -1,4 +1,8 @@
-use crate::context::{Context, ContextOperator, Value};
+use crate::{
+    context::{Context, ContextOperator, Dependencies, Dependent, Value},
+    readiness::Readiness,
+    reset::{Reset, Seed},
+};
 
 use super::Layer;
 
@@ -46,3 +50,40 @@ where
         }
     }
 }
+
+impl<T, P: Dependent<T>, F> Dependent<T> for ThenOperator<P, F> {
+    fn dependencies(&self, deps: &mut Dependencies) {
+        self.inner.dependencies(deps);
+    }
+}
+
+impl<P, F> Readiness for ThenOperator<P, F>
+where
+    P: Readiness,
+{
+    fn warm_up(&self) -> usize {
+        self.inner.warm_up()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.inner.is_ready()
+    }
+}
+
+impl<P, F> Reset for ThenOperator<P, F>
+where
+    P: Reset,
+{
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+impl<S, P, F> Seed<S> for ThenOperator<P, F>
+where
+    P: Seed<S>,
+{
+    fn seed(&mut self, seed: S) {
+        self.inner.seed(seed);
+    }
+}
@@ -1,4 +1,7 @@
 mod anymap;
+mod dependency;
+mod init;
+mod key;
 
 /// Value with context.
 pub mod value;
@@ -12,24 +15,42 @@ pub mod layer;
 /// Output operator.
 pub mod output;
 
-use crate::Operator;
+/// Async context operators and layers.
+#[cfg(feature = "async")]
+pub mod asynchronous;
+
+use crate::{
+    readiness::Readiness,
+    reset::{Reset, Seed},
+    Operator,
+};
 use alloc::boxed::Box;
 
+use self::init::InitData;
 use self::layer::{
     cache::CacheOperator,
     data::AddDataOperator,
+    entries::{InsertEntriesOperator, InsertEnvEntriesOperator},
     insert::{InsertDataOperator, InsertOperator, InsertWithDataOperator},
     inspect::InspectOperator,
     optional::Either,
     then::ThenOperator,
 };
 
+#[cfg(feature = "parallel")]
+pub use self::layer::parallel::ParInsertEnv;
+#[cfg(feature = "profile")]
+pub use self::layer::profile::{Profile, Profiler, Report};
+
 pub use self::{
     anymap::{Context, Map},
-    extractor::{Data, Env, In, Prev},
+    dependency::{Dependencies, Dependency, Dependent, MissingDependencies, Scope},
+    extractor::{Data, Env, History, In, Prev, PrevN},
+    key::{Key, Keyed},
     layer::{
         cache::Cache,
         data::AddData,
+        entries::{Entries, InsertEntries, InsertEnvEntries},
         insert::{Insert, InsertData, InsertWithData},
         inspect::Inspect,
         layer_fn,
@@ -93,6 +114,17 @@ pub trait ContextOperatorExt<In>: ContextOperator<In> {
         layer.layer(self)
     }
 
+    /// Lift into an [`AsyncContextOperator`](asynchronous::AsyncContextOperator),
+    /// so that async layers can be added on top of it.
+    #[cfg(feature = "async")]
+    #[inline]
+    fn into_async(self) -> crate::async_operator::Next<Self>
+    where
+        Self: Sized,
+    {
+        crate::async_operator::Next { inner: self }
+    }
+
     /// Build into an operator without the `Value` wrapper.
     #[inline]
     fn finish(self) -> ContextedOperator<Self>
@@ -103,12 +135,73 @@ pub trait ContextOperatorExt<In>: ContextOperator<In> {
     }
 
     /// Build into an operator without the `Value` wrapper with the given data context.
+    ///
+    /// On reset, the values of the types given here are kept and the others are removed.
     #[inline]
     fn finish_with_data(self, data: Map) -> ContextedOperator<Self>
     where
         Self: Sized,
     {
-        ContextedOperator(self, data)
+        let init = InitData::given(&data);
+        ContextedOperator(self, data, init)
+    }
+
+    /// Build into an operator without the `Value` wrapper with the data context
+    /// built by the given factory, which builds it again on reset.
+    #[inline]
+    fn finish_with<F>(self, data: F) -> ContextedOperator<Self>
+    where
+        F: Fn() -> Map + Send + Sync + 'static,
+        Self: Sized,
+    {
+        let init = InitData::factory(data);
+        ContextedOperator(self, init.build(), init)
+    }
+
+    /// Build into an operator without the `Value` wrapper,
+    /// after checking that the dependencies of the operator are satisfied.
+    ///
+    /// # Errors
+    /// Return the list of the missing types if any of the dependencies is not provided.
+    #[inline]
+    fn try_finish(self) -> Result<ContextedOperator<Self>, MissingDependencies>
+    where
+        Self: Dependent<In> + Sized,
+    {
+        self.try_finish_with_data(Map::default())
+    }
+
+    /// Build into an operator without the `Value` wrapper with the given data context,
+    /// after checking that the dependencies of the operator are satisfied.
+    ///
+    /// # Errors
+    /// Return the list of the missing types if any of the dependencies is not provided.
+    fn try_finish_with_data(self, data: Map) -> Result<ContextedOperator<Self>, MissingDependencies>
+    where
+        Self: Dependent<In> + Sized,
+    {
+        let mut deps = Dependencies::default();
+        self.dependencies(&mut deps);
+        deps.check(&data)?;
+        Ok(self.finish_with_data(data))
+    }
+
+    /// Build into an operator without the `Value` wrapper with the data context
+    /// built by the given factory, after checking that the dependencies of the operator are satisfied.
+    ///
+    /// # Errors
+    /// Return the list of the missing types if any of the dependencies is not provided.
+    fn try_finish_with<F>(self, data: F) -> Result<ContextedOperator<Self>, MissingDependencies>
+    where
+        F: Fn() -> Map + Send + Sync + 'static,
+        Self: Dependent<In> + Sized,
+    {
+        let init = InitData::factory(data);
+        let data = init.build();
+        let mut deps = Dependencies::default();
+        self.dependencies(&mut deps);
+        deps.check(&data)?;
+        Ok(ContextedOperator(self, data, init))
     }
 
     /// Add a cache layer with the given `length`.
@@ -152,6 +245,19 @@ pub trait ContextOperatorExt<In>: ContextOperator<In> {
         self.with_if(if enable { Some(Insert(f)) } else { None })
     }
 
+    /// Add a [`ParInsertEnv`] layer with the given constructor of a tuple of [`RefOperator`]s.
+    ///
+    /// We use this method to evaluate independent operators concurrently
+    /// and add their outputs to the `env` context.
+    #[cfg(feature = "parallel")]
+    fn par_insert_env<F>(self, f: F) -> <ParInsertEnv<F> as Layer<In, Self>>::Operator
+    where
+        ParInsertEnv<F>: Layer<In, Self>,
+        Self: Sized,
+    {
+        self.with(ParInsertEnv(f))
+    }
+
     /// Add a [`InsertData`] layer with the given [`RefOperator`] constructor.
     /// (i.e. a function that returns a [`RefOperator`]).
     ///
@@ -216,6 +322,32 @@ pub trait ContextOperatorExt<In>: ContextOperator<In> {
         })
     }
 
+    /// Add a [`InsertEnvEntries`] layer with the given [`RefOperator`] constructor.
+    ///
+    /// We use this method to add the output of the operator and its [`Entries`] to the `env` context,
+    /// so that its fields can be extracted separately.
+    fn insert_env_entries<R, Out>(self, f: impl Fn() -> R) -> InsertEnvEntriesOperator<Self, R>
+    where
+        R: for<'a> RefOperator<'a, In, Output = Out>,
+        Out: Entries,
+        Self: Sized,
+    {
+        self.with(InsertEnvEntries(f))
+    }
+
+    /// Add a [`InsertEntries`] layer with the given [`RefOperator`] constructor.
+    ///
+    /// The same as [`insert`], but the [`Entries`] of the `env` value are also added to the `env` context.
+    fn insert_entries<R, Env, Data>(self, f: impl Fn() -> R) -> InsertEntriesOperator<Self, R>
+    where
+        R: for<'a> RefOperator<'a, In, Output = (Env, Option<Data>)>,
+        Env: Entries,
+        Data: Send + Sync + 'static,
+        Self: Sized,
+    {
+        self.with(InsertEntries(f))
+    }
+
     /// Add an inspect layer with the given closure.
     fn inspect<F>(self, f: F) -> InspectOperator<Self, F>
     where
@@ -324,7 +456,51 @@ impl<T, P> ContextOperatorExt<T> for P where P: ContextOperator<T> {}
 
 /// Contexted Operator.
 #[derive(Debug, Default)]
-pub struct ContextedOperator<P>(P, Map);
+pub struct ContextedOperator<P>(P, Map, InitData);
+
+impl<P: Readiness> Readiness for ContextedOperator<P> {
+    fn warm_up(&self) -> usize {
+        self.0.warm_up()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.0.is_ready()
+    }
+}
+
+/// The data context is restored: it is built again if it was built by a factory
+/// (see [`ContextOperatorExt::finish_with`]), otherwise only the values
+/// of the types given when it was built are kept.
+impl<P: Reset> Reset for ContextedOperator<P> {
+    fn reset(&mut self) {
+        self.0.reset();
+        self.2.restore(&mut self.1);
+    }
+}
+
+impl<S, P: Seed<S>> Seed<S> for ContextedOperator<P> {
+    fn seed(&mut self, seed: S) {
+        self.0.seed(seed);
+        self.2.restore(&mut self.1);
+    }
+}
+
+/// The data context is not included in the snapshot.
+#[cfg(feature = "snapshot")]
+impl<P> crate::snapshot::Snapshot for ContextedOperator<P>
+where
+    P: crate::snapshot::Snapshot,
+{
+    type State = P::State;
+
+    fn snapshot(&self) -> Self::State {
+        self.0.snapshot()
+    }
+
+    fn restore(&mut self, state: Self::State) {
+        self.0.restore(state)
+    }
+}
 
 impl<In, P> Operator<In> for ContextedOperator<P>
 where
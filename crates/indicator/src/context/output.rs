@@ -1,6 +1,9 @@
-use crate::Operator;
+use crate::{
+    reset::{Reset, Seed},
+    Operator,
+};
 
-use super::{Context, RefOperator, Value};
+use super::{Context, Dependencies, Dependent, RefOperator, Value};
 
 /// An operator for outputting.
 pub struct Output<F>(F);
@@ -16,6 +19,19 @@ where
     }
 }
 
+impl<F> Reset for Output<F> {
+    fn reset(&mut self) {}
+}
+
+impl<F> Seed<()> for Output<F> {
+    fn seed(&mut self, _seed: ()) {}
+}
+
+/// The closure is assumed to have no dependencies.
+impl<I, F> Dependent<I> for Output<F> {
+    fn dependencies(&self, _deps: &mut Dependencies) {}
+}
+
 /// Create an operator for outputting from a closure.
 pub fn output<I, O>(
     f: impl FnMut(I, &mut Context) -> O,
@@ -41,6 +57,29 @@ where
     }
 }
 
+impl<R: Reset> Reset for InsertEnvAndOutput<R> {
+    fn reset(&mut self) {
+        self.0.reset();
+    }
+}
+
+impl<S, R: Seed<S>> Seed<S> for InsertEnvAndOutput<R> {
+    fn seed(&mut self, seed: S) {
+        self.0.seed(seed);
+    }
+}
+
+impl<T, R, Out> Dependent<T> for InsertEnvAndOutput<R>
+where
+    R: for<'a> RefOperator<'a, T, Output = Out> + Dependent<T>,
+    Out: 'static,
+{
+    fn dependencies(&self, deps: &mut Dependencies) {
+        self.0.dependencies(deps);
+        deps.provide_env::<Out>();
+    }
+}
+
 /// Create an output operator that insert the output into the `env` context.
 pub fn insert_env_and_output<I, O, R, F>(operator: F) -> InsertEnvAndOutput<R>
 where
@@ -73,6 +112,31 @@ where
     }
 }
 
+impl<R: Reset> Reset for InsertAndOutput<R> {
+    fn reset(&mut self) {
+        self.0.reset();
+    }
+}
+
+impl<S, R: Seed<S>> Seed<S> for InsertAndOutput<R> {
+    fn seed(&mut self, seed: S) {
+        self.0.seed(seed);
+    }
+}
+
+impl<T, R, Out, Data> Dependent<T> for InsertAndOutput<R>
+where
+    R: for<'a> RefOperator<'a, T, Output = (Out, Option<Data>)> + Dependent<T>,
+    Out: 'static,
+    Data: 'static,
+{
+    fn dependencies(&self, deps: &mut Dependencies) {
+        self.0.dependencies(deps);
+        deps.provide_env::<Out>();
+        deps.provide_data::<Data>();
+    }
+}
+
 /// Create an output operator that insert the output into the `env` and `data` context.
 pub fn insert_and_output<I, O, D, R, F>(operator: F) -> InsertAndOutput<R>
 where
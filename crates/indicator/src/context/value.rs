@@ -1,8 +1,12 @@
 use core::{fmt, marker::PhantomData};
 
-use crate::Operator;
+use crate::{
+    readiness::Readiness,
+    reset::{Reset, Seed},
+    Operator,
+};
 
-use super::{anymap::Map, Context};
+use super::{anymap::Map, Context, Dependencies, Dependent};
 
 /// Value with context.
 #[derive(Debug)]
@@ -107,6 +111,24 @@ impl<T> Operator<Value<T>> for Input<T> {
     }
 }
 
+impl<T> Readiness for Input<T> {
+    fn warm_up(&self) -> usize {
+        0
+    }
+
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
+
+impl<T> Reset for Input<T> {
+    fn reset(&mut self) {}
+}
+
+impl<T> Seed<()> for Input<T> {
+    fn seed(&mut self, _seed: ()) {}
+}
+
 /// Create an identity operator `Input` that returns the input value.
 pub fn input<T>() -> Input<T> {
     Input(PhantomData)
@@ -121,6 +143,14 @@ pub struct ValueRef<'a, T> {
     pub(crate) context: &'a Context,
 }
 
+impl<'a, T> Clone for ValueRef<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for ValueRef<'a, T> {}
+
 impl<'a, T> ValueRef<'a, T> {
     /// Get the reference to the inner value.
     #[inline]
@@ -134,3 +164,7 @@ impl<'a, T> ValueRef<'a, T> {
         self.context
     }
 }
+
+impl<T> Dependent<T> for Input<T> {
+    fn dependencies(&self, _deps: &mut Dependencies) {}
+}
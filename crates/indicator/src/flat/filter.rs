@@ -0,0 +1,38 @@
+use crate::reset::{Reset, Seed};
+
+use super::FlatOperator;
+
+/// [`Filter`] operator.
+#[derive(Debug, Clone, Copy)]
+pub struct Filter<F> {
+    f: F,
+}
+
+/// Create a [`Filter`] operator that only keeps the inputs satisfying the predicate.
+pub fn filter<I, F>(f: F) -> Filter<F>
+where
+    F: FnMut(&I) -> bool,
+{
+    Filter { f }
+}
+
+impl<I, F> FlatOperator<I> for Filter<F>
+where
+    F: FnMut(&I) -> bool,
+{
+    type Item = I;
+
+    fn for_each(&mut self, input: I, f: &mut dyn FnMut(Self::Item)) {
+        if (self.f)(&input) {
+            f(input);
+        }
+    }
+}
+
+impl<F> Reset for Filter<F> {
+    fn reset(&mut self) {}
+}
+
+impl<F> Seed<()> for Filter<F> {
+    fn seed(&mut self, _seed: ()) {}
+}
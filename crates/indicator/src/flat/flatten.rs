@@ -0,0 +1,49 @@
+use crate::{
+    operator::map::Map,
+    reset::{Reset, Seed},
+    Operator,
+};
+
+use super::FlatOperator;
+
+/// Flatten the outputs of an [`Operator`].
+///
+/// Created by [`OperatorExt::flatten`](crate::OperatorExt::flatten).
+#[derive(Debug, Clone, Copy)]
+pub struct Flatten<I, P>(
+    pub(crate) P,
+    pub(crate) core::marker::PhantomData<fn() -> I>,
+);
+
+impl<I, P> FlatOperator<I> for Flatten<I, P>
+where
+    P: Operator<I>,
+    P::Output: IntoIterator,
+{
+    type Item = <P::Output as IntoIterator>::Item;
+
+    fn for_each(&mut self, input: I, f: &mut dyn FnMut(Self::Item)) {
+        self.0.next(input).into_iter().for_each(f);
+    }
+}
+
+impl<I, P: Reset> Reset for Flatten<I, P> {
+    fn reset(&mut self) {
+        self.0.reset();
+    }
+}
+
+impl<I, S, P: Seed<S>> Seed<S> for Flatten<I, P> {
+    fn seed(&mut self, seed: S) {
+        self.0.seed(seed);
+    }
+}
+
+/// Create a [`FlatOperator`] from a closure returning zero or many outputs.
+pub fn flat_map<I, O, F>(f: F) -> Flatten<I, Map<F>>
+where
+    F: FnMut(I) -> O,
+    O: IntoIterator,
+{
+    Flatten(crate::map(f), core::marker::PhantomData)
+}
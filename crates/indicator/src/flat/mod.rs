@@ -0,0 +1,143 @@
+/// Then combinators.
+pub mod then;
+
+/// Flatten adapter.
+pub mod flatten;
+
+/// Filter operator.
+pub mod filter;
+
+pub use filter::{filter, Filter};
+pub use flatten::{flat_map, Flatten};
+pub use then::{FlatThen, ThenEach};
+
+use crate::{map, operator::map::Map, Operator};
+
+/// Operator that produces zero or many outputs for each input.
+///
+/// It is applied to an iterator or a stream with `flat_indicator`.
+pub trait FlatOperator<I> {
+    /// The type of each of the outputs.
+    type Item;
+
+    /// Produce the outputs of the input, passing them to `f` one by one.
+    ///
+    /// The combinators chain their stages with it, so the outputs of each stage are not collected.
+    fn for_each(&mut self, input: I, f: &mut dyn FnMut(Self::Item));
+}
+
+impl<I, P> FlatOperator<I> for &mut P
+where
+    P: FlatOperator<I> + ?Sized,
+{
+    type Item = P::Item;
+
+    fn for_each(&mut self, input: I, f: &mut dyn FnMut(Self::Item)) {
+        (*self).for_each(input, f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I, P> FlatOperator<I> for alloc::boxed::Box<P>
+where
+    P: FlatOperator<I> + ?Sized,
+{
+    type Item = P::Item;
+
+    fn for_each(&mut self, input: I, f: &mut dyn FnMut(Self::Item)) {
+        self.as_mut().for_each(input, f)
+    }
+}
+
+/// Extension trait for [`FlatOperator`].
+pub trait FlatOperatorExt<I>: FlatOperator<I> {
+    /// Combine with another flat operator that uses `Self::Item` as input type.
+    ///
+    /// The `other` operator is performed on each of the outputs of `self`.
+    fn flat_then<P2>(self, other: P2) -> FlatThen<I, Self, P2>
+    where
+        Self: Sized,
+        P2: FlatOperator<Self::Item>,
+    {
+        FlatThen(self, other, core::marker::PhantomData)
+    }
+
+    /// Combine with an [`Operator`] that uses `Self::Item` as input type.
+    ///
+    /// The `other` operator is performed on each of the outputs of `self`.
+    fn then_each<P2>(self, other: P2) -> ThenEach<I, Self, P2>
+    where
+        Self: Sized,
+        P2: Operator<Self::Item>,
+    {
+        ThenEach(self, other, core::marker::PhantomData)
+    }
+
+    /// Map each of the outputs.
+    fn map_each<O, F>(self, f: F) -> ThenEach<I, Self, Map<F>>
+    where
+        Self: Sized,
+        F: FnMut(Self::Item) -> O,
+    {
+        self.then_each(map(f))
+    }
+
+    /// Only keep the outputs satisfying the predicate.
+    fn filter<F>(self, f: F) -> FlatThen<I, Self, Filter<F>>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> bool,
+    {
+        self.flat_then(filter(f))
+    }
+}
+
+impl<I, P: FlatOperator<I>> FlatOperatorExt<I> for P {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IndicatorIteratorExt;
+
+    #[test]
+    fn flat_then() {
+        let mut outputs = [1, 2, 3, 4].into_iter().flat_indicator(
+            flat_map(|x: i32| [x; 2])
+                .filter(|x| x % 2 == 0)
+                .flat_then(flat_map(|x: i32| [x, -x]))
+                .map_each(|x| x * 10),
+        );
+        for expected in [20, -20, 20, -20, 40, -40, 40, -40] {
+            assert_eq!(outputs.next(), Some(expected));
+        }
+        assert_eq!(outputs.next(), None);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn flatten_ticked() {
+        use crate::{bar::Ohlc, OperatorExt, TickValue};
+
+        let mut bricks = [10, 14, 15]
+            .into_iter()
+            .map(|x| TickValue::big_bang(Ohlc::flat(x)))
+            .flat_indicator(crate::bar::renko(2).flatten())
+            .map(|brick| brick.value.close);
+        assert_eq!(bricks.next(), Some(12));
+        assert_eq!(bricks.next(), Some(14));
+        assert_eq!(bricks.next(), None);
+    }
+
+    #[cfg(feature = "stream")]
+    #[tokio::test]
+    async fn flat_stream() {
+        use crate::IndicatorStreamExt;
+        use futures::StreamExt;
+
+        let outputs = futures::stream::iter([1, 2, 3])
+            .flat_indicator(flat_map(|x: i32| 0..x).filter(|x| x % 2 == 0))
+            .collect::<alloc::vec::Vec<_>>()
+            .await;
+        assert_eq!(outputs, [0, 0, 0, 2]);
+    }
+}
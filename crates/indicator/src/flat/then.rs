@@ -0,0 +1,92 @@
+use crate::{
+    reset::{Reset, Seed},
+    Operator,
+};
+
+use super::FlatOperator;
+
+/// [`FlatThen`] combinator.
+#[derive(Debug, Clone, Copy)]
+pub struct FlatThen<I, P1, P2>(
+    pub(crate) P1,
+    pub(crate) P2,
+    pub(crate) core::marker::PhantomData<fn() -> I>,
+);
+
+impl<I, P1, P2> FlatOperator<I> for FlatThen<I, P1, P2>
+where
+    P1: FlatOperator<I>,
+    P2: FlatOperator<P1::Item>,
+{
+    type Item = P2::Item;
+
+    fn for_each(&mut self, input: I, f: &mut dyn FnMut(Self::Item)) {
+        let Self(first, second, _) = self;
+        first.for_each(input, &mut |output| second.for_each(output, f));
+    }
+}
+
+impl<I, P1, P2> Reset for FlatThen<I, P1, P2>
+where
+    P1: Reset,
+    P2: Reset,
+{
+    fn reset(&mut self) {
+        self.0.reset();
+        self.1.reset();
+    }
+}
+
+impl<I, S1, S2, P1, P2> Seed<(S1, S2)> for FlatThen<I, P1, P2>
+where
+    P1: Seed<S1>,
+    P2: Seed<S2>,
+{
+    fn seed(&mut self, seed: (S1, S2)) {
+        self.0.seed(seed.0);
+        self.1.seed(seed.1);
+    }
+}
+
+/// [`ThenEach`] combinator.
+#[derive(Debug, Clone, Copy)]
+pub struct ThenEach<I, P1, P2>(
+    pub(crate) P1,
+    pub(crate) P2,
+    pub(crate) core::marker::PhantomData<fn() -> I>,
+);
+
+impl<I, P1, P2> FlatOperator<I> for ThenEach<I, P1, P2>
+where
+    P1: FlatOperator<I>,
+    P2: Operator<P1::Item>,
+{
+    type Item = P2::Output;
+
+    fn for_each(&mut self, input: I, f: &mut dyn FnMut(Self::Item)) {
+        let Self(first, second, _) = self;
+        first.for_each(input, &mut |output| f(second.next(output)));
+    }
+}
+
+impl<I, P1, P2> Reset for ThenEach<I, P1, P2>
+where
+    P1: Reset,
+    P2: Reset,
+{
+    fn reset(&mut self) {
+        self.0.reset();
+        self.1.reset();
+    }
+}
+
+impl<I, S1, S2, P1, P2> Seed<(S1, S2)> for ThenEach<I, P1, P2>
+where
+    P1: Seed<S1>,
+    P2: Seed<S2>,
+{
+    fn seed(&mut self, seed: (S1, S2)) {
+        self.0.seed(seed.0);
+        self.1.seed(seed.1);
+    }
+}
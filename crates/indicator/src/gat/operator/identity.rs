@@ -1,6 +1,7 @@
 use core::marker::PhantomData;
 
 use super::GatOperator;
+use crate::reset::{Reset, Seed};
 
 /// Identity operator.
 pub struct Identity<I>(PhantomData<I>);
@@ -26,7 +27,8 @@ impl<I> Default for Identity<I> {
 }
 
 impl<I> GatOperator<I> for Identity<I> {
-    type Output<'out> = I
+    type Output<'out>
+        = I
     where
         Self: 'out,
         I: 'out;
@@ -39,6 +41,14 @@ impl<I> GatOperator<I> for Identity<I> {
     }
 }
 
+impl<I> Reset for Identity<I> {
+    fn reset(&mut self) {}
+}
+
+impl<I> Seed<()> for Identity<I> {
+    fn seed(&mut self, _seed: ()) {}
+}
+
 /// Create a [`Identity`] operator.
 pub fn id<I>() -> Identity<I> {
     Identity(PhantomData)
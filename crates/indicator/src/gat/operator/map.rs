@@ -1,4 +1,5 @@
 use super::GatOperator;
+use crate::reset::{Reset, Seed};
 
 /// Operator returns by [`map`].
 #[derive(Debug, Clone, Copy)]
@@ -23,7 +24,11 @@ impl<I, O, F> GatOperator<I> for Map<F>
 where
     F: FnMut(I) -> O,
 {
-    type Output<'out> = O where F: 'out, I: 'out;
+    type Output<'out>
+        = O
+    where
+        F: 'out,
+        I: 'out;
 
     #[inline]
     fn next<'out>(&'out mut self, input: I) -> Self::Output<'out>
@@ -33,3 +38,11 @@ where
         (self.0)(input)
     }
 }
+
+impl<F> Reset for Map<F> {
+    fn reset(&mut self) {}
+}
+
+impl<F> Seed<()> for Map<F> {
+    fn seed(&mut self, _seed: ()) {}
+}
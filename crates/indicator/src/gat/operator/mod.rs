@@ -1,4 +1,5 @@
 use self::{map::Map, mux::Mux, then::Then};
+use crate::reset::{Reset, Seed};
 
 /// Combine two operators.
 pub mod then;
@@ -27,11 +28,12 @@ pub trait GatOperator<I> {
         I: 'out;
 }
 
-impl<'a, I, P> GatOperator<I> for &'a mut P
+impl<I, P> GatOperator<I> for &mut P
 where
     P: GatOperator<I>,
 {
-    type Output<'out> = P::Output<'out>
+    type Output<'out>
+        = P::Output<'out>
     where
         Self: 'out,
         I: 'out;
@@ -124,3 +126,15 @@ where
         self.0.next(input)
     }
 }
+
+impl<P: Reset> Reset for Op<P> {
+    fn reset(&mut self) {
+        self.0.reset();
+    }
+}
+
+impl<S, P: Seed<S>> Seed<S> for Op<P> {
+    fn seed(&mut self, seed: S) {
+        self.0.seed(seed);
+    }
+}
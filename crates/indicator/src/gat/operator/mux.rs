@@ -1,4 +1,5 @@
 use super::GatOperator;
+use crate::reset::{Reset, Seed};
 
 /// Operator returns by [`mux`].
 #[derive(Debug, Clone, Copy)]
@@ -27,7 +28,8 @@ where
     P1: GatOperator<I>,
     P2: GatOperator<I>,
 {
-    type Output<'out> = (P1::Output<'out>, P2::Output<'out>)
+    type Output<'out>
+        = (P1::Output<'out>, P2::Output<'out>)
     where
         I: 'out,
         P1: 'out,
@@ -40,3 +42,25 @@ where
         (self.0.next(input.clone()), self.1.next(input))
     }
 }
+
+impl<P1, P2> Reset for Mux<P1, P2>
+where
+    P1: Reset,
+    P2: Reset,
+{
+    fn reset(&mut self) {
+        self.0.reset();
+        self.1.reset();
+    }
+}
+
+impl<S1, S2, P1, P2> Seed<(S1, S2)> for Mux<P1, P2>
+where
+    P1: Seed<S1>,
+    P2: Seed<S2>,
+{
+    fn seed(&mut self, seed: (S1, S2)) {
+        self.0.seed(seed.0);
+        self.1.seed(seed.1);
+    }
+}
@@ -1,4 +1,5 @@
 use super::GatOperator;
+use crate::reset::{Reset, Seed};
 
 /// The [`GatOperator`] produces by [`then`](super::OperatorExt).
 #[derive(Debug, Clone, Copy)]
@@ -9,7 +10,8 @@ where
     P1: GatOperator<I>,
     P2: for<'out> GatOperator<P1::Output<'out>>,
 {
-    type Output<'out> = <P2 as GatOperator<<P1 as GatOperator<I>>::Output<'out>>>::Output<'out>
+    type Output<'out>
+        = <P2 as GatOperator<<P1 as GatOperator<I>>::Output<'out>>>::Output<'out>
     where
         I: 'out,
         P1: 'out,
@@ -23,3 +25,25 @@ where
         self.1.next(self.0.next(input))
     }
 }
+
+impl<P1, P2> Reset for Then<P1, P2>
+where
+    P1: Reset,
+    P2: Reset,
+{
+    fn reset(&mut self) {
+        self.0.reset();
+        self.1.reset();
+    }
+}
+
+impl<S1, S2, P1, P2> Seed<(S1, S2)> for Then<P1, P2>
+where
+    P1: Seed<S1>,
+    P2: Seed<S2>,
+{
+    fn seed(&mut self, seed: (S1, S2)) {
+        self.0.seed(seed.0);
+        self.1.seed(seed.1);
+    }
+}
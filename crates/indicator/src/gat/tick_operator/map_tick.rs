@@ -1,4 +1,7 @@
-use crate::{TickValue, Tickable};
+use crate::{
+    reset::{Reset, Seed},
+    TickValue, Tickable,
+};
 
 use super::{
     super::operator::map::{map, Map},
@@ -29,7 +32,11 @@ where
     I: Tickable,
     P: GatOperator<I::Value>,
 {
-    type Output<'out> = TickValue<P::Output<'out>> where P: 'out, I: 'out;
+    type Output<'out>
+        = TickValue<P::Output<'out>>
+    where
+        P: 'out,
+        I: 'out;
 
     #[inline]
     fn next<'out>(&'out mut self, input: I) -> Self::Output<'out>
@@ -39,3 +46,15 @@ where
         input.into_tick_value().map(|v| self.0.next(v))
     }
 }
+
+impl<P: Reset> Reset for MapTick<P> {
+    fn reset(&mut self) {
+        self.0.reset();
+    }
+}
+
+impl<S, P: Seed<S>> Seed<S> for MapTick<P> {
+    fn seed(&mut self, seed: S) {
+        self.0.seed(seed);
+    }
+}
@@ -1,9 +1,16 @@
 use core::num::NonZeroUsize;
 
-use crate::gat::GatOperator;
+use crate::{
+    gat::GatOperator,
+    readiness::Readiness,
+    reset::{Reset, Seed},
+};
 
 use super::queue::{circular::Circular, Collection, Queue, QueueMut, Tumbling};
 
+#[cfg(feature = "snapshot")]
+use crate::snapshot::Snapshot;
+
 /// Operation.
 pub trait Operation<I, T> {
     /// Output.
@@ -19,7 +26,10 @@ impl<I, T, F> Operation<I, T> for F
 where
     F: for<'a> FnMut(QueueMut<'a, T>, I),
 {
-    type Output<'out> = () where T: 'out;
+    type Output<'out>
+        = ()
+    where
+        T: 'out;
 
     #[inline]
     fn step(&mut self, w: QueueMut<T>, x: I) {
@@ -47,12 +57,64 @@ impl<Q: Queue, P> TumblingOperator<Q, P> {
     }
 }
 
+/// The operator is ready when the queue is full.
+impl<Q: Queue, P> Readiness for TumblingOperator<Q, P> {
+    fn warm_up(&self) -> usize {
+        self.queue.cap()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.queue.is_full()
+    }
+}
+
+impl<Q: Queue, P: Reset> Reset for TumblingOperator<Q, P> {
+    fn reset(&mut self) {
+        self.queue.reset();
+        self.op.reset();
+    }
+}
+
+/// Seed with the items of the completed windows, from the oldest to the latest.
+///
+/// The next input will start a new window.
+impl<Q, P, H> Seed<H> for TumblingOperator<Q, P>
+where
+    Q: Queue,
+    P: Reset,
+    H: IntoIterator<Item = Q::Item>,
+{
+    fn seed(&mut self, seed: H) {
+        self.op.reset();
+        self.queue.seed(seed);
+    }
+}
+
+#[cfg(feature = "snapshot")]
+impl<Q, P> Snapshot for TumblingOperator<Q, P>
+where
+    Q: Queue + Snapshot,
+    P: Snapshot,
+{
+    type State = (Q::State, P::State);
+
+    fn snapshot(&self) -> Self::State {
+        (self.queue.snapshot(), self.op.snapshot())
+    }
+
+    fn restore(&mut self, state: Self::State) {
+        self.queue.restore(state.0);
+        self.op.restore(state.1);
+    }
+}
+
 impl<I, Q, P> GatOperator<I> for TumblingOperator<Q, P>
 where
     Q: Queue,
     P: Operation<I, Q::Item>,
 {
-    type Output<'out> = P::Output<'out>
+    type Output<'out>
+        = P::Output<'out>
     where
         Self: 'out,
         I: 'out;
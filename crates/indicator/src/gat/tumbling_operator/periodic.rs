@@ -1,6 +1,8 @@
 use core::num::NonZeroUsize;
 
-use crate::{prelude::GatOperator, Period, Tick, TickValue, Tickable, TumblingWindow};
+use crate::{
+    prelude::GatOperator, reset::Reset, Period, Tick, TickValue, Tickable, TumblingWindow,
+};
 
 use super::{
     operator::{Operation, TumblingOperator},
@@ -68,12 +70,22 @@ impl<P, const PUSH_FIRST: bool> Op<P, PUSH_FIRST> {
     }
 }
 
+/// The periodic operation is assumed stateless and is not reset.
+impl<P, const PUSH_FIRST: bool> Reset for Op<P, PUSH_FIRST> {
+    fn reset(&mut self) {
+        self.last = Tick::BIG_BANG;
+    }
+}
+
 impl<I, T, P> Operation<I, T> for Op<P, false>
 where
     I: Tickable,
     P: PeriodicOp<I, T>,
 {
-    type Output<'out> = TickQueueRef<'out, T> where T: 'out;
+    type Output<'out>
+        = TickQueueRef<'out, T>
+    where
+        T: 'out;
 
     fn step<'a>(&mut self, mut queue: QueueMut<'a, T>, event: I) -> Self::Output<'a> {
         let tick = event.tick();
@@ -95,7 +107,10 @@ where
     T: Clone,
     P: PeriodicOp<I, T>,
 {
-    type Output<'out> = TickQueueRef<'out, T> where T: 'out;
+    type Output<'out>
+        = TickQueueRef<'out, T>
+    where
+        T: 'out;
 
     fn step<'a>(&mut self, mut queue: QueueMut<'a, T>, event: I) -> Self::Output<'a> {
         let tick = event.tick();
@@ -116,6 +131,20 @@ where
     }
 }
 
+/// The periodic operation is assumed stateless and is not included in the snapshot.
+#[cfg(feature = "snapshot")]
+impl<P, const PUSH_FIRST: bool> crate::snapshot::Snapshot for Op<P, PUSH_FIRST> {
+    type State = Tick;
+
+    fn snapshot(&self) -> Self::State {
+        self.last
+    }
+
+    fn restore(&mut self, state: Self::State) {
+        self.last = state;
+    }
+}
+
 /// Periodic Operator Builder.
 #[derive(Debug, Clone, Copy)]
 pub struct Periodic<Q, const PUSH_FIRST: bool> {
@@ -261,7 +290,7 @@ mod tests {
                 if n && w.len() > 1 {
                     assert!(w[0] != w[1]);
                 }
-                if n && w.len() >= 1 {
+                if n && !w.is_empty() {
                     cache = w[0].value;
                 } else if w.len() > 1 {
                     assert!(w[1].value == cache);
@@ -285,4 +314,34 @@ mod tests {
             op.next(x);
         }
     }
+
+    #[cfg(feature = "snapshot")]
+    #[test]
+    fn snapshot() {
+        use crate::snapshot::Snapshot;
+
+        let build = || {
+            Periodic::with_circular_n::<2, TickValue<usize>>(Period::secs(2))
+                .build_fn(|_, _, x: TickValue<usize>| x)
+        };
+        let base = datetime!(2022-09-23 00:00:00 +00:00);
+        let data = (0..6)
+            .map(|x| TickValue::new(base + time::Duration::seconds(x as i64), x))
+            .collect::<Vec<_>>();
+        let mut op = build();
+        for x in &data[..3] {
+            op.next(*x);
+        }
+        let json = serde_json::to_string(&op.snapshot()).unwrap();
+        let mut restored = build();
+        restored.restore(serde_json::from_str(&json).unwrap());
+        for x in &data[3..] {
+            let expected = {
+                let w = op.next(*x);
+                (w.len(), w[0], w[w.len() - 1])
+            };
+            let w = restored.next(*x);
+            assert_eq!((w.len(), w[0], w[w.len() - 1]), expected);
+        }
+    }
 }
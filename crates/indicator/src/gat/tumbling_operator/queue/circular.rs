@@ -14,6 +14,13 @@ use tinyvec::TinyVec;
 
 use super::{Collection, Queue};
 
+#[cfg(feature = "snapshot")]
+use crate::snapshot::Snapshot;
+#[cfg(feature = "snapshot")]
+use alloc::vec::Vec;
+#[cfg(feature = "snapshot")]
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
 /// Circular Queue backed by [`TinyVec`].
 #[derive(Debug, Clone)]
 pub struct Circular<const N: usize, T> {
@@ -128,6 +135,45 @@ impl<T, const N: usize> Queue for Circular<N, T> {
     }
 }
 
+/// The state of a [`Circular`] queue.
+#[cfg(feature = "snapshot")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CircularState<T> {
+    /// Capacity.
+    pub cap: usize,
+    /// The items, from the oldest to the latest.
+    pub items: Vec<T>,
+}
+
+#[cfg(feature = "snapshot")]
+impl<T, const N: usize> Snapshot for Circular<N, T>
+where
+    T: Clone + Serialize + DeserializeOwned,
+{
+    type State = CircularState<T>;
+
+    fn snapshot(&self) -> Self::State {
+        CircularState {
+            cap: self.cap,
+            items: (0..self.len())
+                .rev()
+                .filter_map(|idx| self.get(idx).cloned())
+                .collect(),
+        }
+    }
+
+    /// Restore the queue from a snapshot, only the latest `cap` items are kept.
+    ///
+    /// # Panic
+    /// Panic if `cap` is zero.
+    fn restore(&mut self, state: Self::State) {
+        *self = Self::with_capacity(state.cap);
+        for item in state.items {
+            self.enque_and_deque_overflow(item);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -198,4 +244,20 @@ mod tests {
         queue.enque(7);
         assert_eq!(queue.deque(), Some(7));
     }
+
+    #[cfg(feature = "snapshot")]
+    #[test]
+    fn snapshot() {
+        let mut queue = Circular::<1, _>::with_capacity(3);
+        for x in 1..=5 {
+            queue.enque_and_deque_overflow(x);
+        }
+        let state = queue.snapshot();
+        assert_eq!(state.items, [3, 4, 5]);
+        let mut restored = Circular::<1, _>::with_capacity(1);
+        restored.restore(state);
+        assert_eq!(restored.cap(), 3);
+        assert_eq!(restored.get(0), Some(&5));
+        assert_eq!(restored.deque(), Some(3));
+    }
 }
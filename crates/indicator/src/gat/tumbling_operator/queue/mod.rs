@@ -1,5 +1,8 @@
 use core::ops::{Deref, DerefMut, Index, IndexMut};
 
+#[cfg(feature = "snapshot")]
+use crate::snapshot::Snapshot;
+
 /// Circular Queue.
 pub mod circular;
 
@@ -44,6 +47,12 @@ pub trait Queue {
         self.len() == self.cap()
     }
 
+    /// Remove all the items.
+    #[inline]
+    fn clear(&mut self) {
+        while self.deque().is_some() {}
+    }
+
     /// Returns whether elements are on stack.
     fn is_inline(&self) -> bool;
 
@@ -121,6 +130,20 @@ where
         Self(queue, Change::Push(None))
     }
 
+    /// Clear the queue and the last change.
+    pub(crate) fn reset(&mut self) {
+        self.0.clear();
+        self.1 = Change::Push(None);
+    }
+
+    /// Reset and then enque the given items, from the oldest to the latest.
+    pub(crate) fn seed(&mut self, items: impl IntoIterator<Item = Q::Item>) {
+        self.reset();
+        for item in items {
+            self.0.enque_and_deque_overflow(item);
+        }
+    }
+
     /// Convert to a view of the queue.
     pub fn as_view<'a>(&'a self) -> View<'a, dyn Queue<Item = Q::Item> + 'a> {
         View {
@@ -148,6 +171,24 @@ where
     }
 }
 
+/// The last change is not included in the snapshot.
+#[cfg(feature = "snapshot")]
+impl<Q> Snapshot for Tumbling<Q>
+where
+    Q: Queue + Snapshot,
+{
+    type State = Q::State;
+
+    fn snapshot(&self) -> Self::State {
+        self.0.snapshot()
+    }
+
+    fn restore(&mut self, state: Self::State) {
+        self.0.restore(state);
+        self.1 = Change::Push(None);
+    }
+}
+
 impl<Q> Deref for Tumbling<Q>
 where
     Q: Queue,
@@ -301,7 +342,7 @@ pub struct QueueMut<'a, T>(ViewMut<'a, dyn Queue<Item = T> + 'a>);
 
 impl<'a, T> QueueMut<'a, T> {
     /// As a [`QueueRef`]
-    pub fn as_queue_ref(&self) -> QueueRef<T> {
+    pub fn as_queue_ref(&self) -> QueueRef<'_, T> {
         QueueRef(View {
             queue: self.queue,
             change: self.change,
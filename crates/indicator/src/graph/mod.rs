@@ -0,0 +1,501 @@
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec};
+use core::{
+    any::{Any, TypeId},
+    fmt,
+    marker::PhantomData,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::Operator;
+
+/// Handle to the output of a node (or the input) of a graph.
+#[derive(Debug)]
+pub struct Handle<T> {
+    graph: usize,
+    idx: Option<usize>,
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Handle<T> {}
+
+impl<T> PartialEq for Handle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.graph == other.graph && self.idx == other.idx
+    }
+}
+
+impl<T> Eq for Handle<T> {}
+
+impl<T> Handle<T> {
+    fn new(graph: usize, idx: Option<usize>) -> Self {
+        Self {
+            graph,
+            idx,
+            _value: PhantomData,
+        }
+    }
+}
+
+impl<T: 'static> Handle<T> {
+    /// Erase the type of the handle.
+    pub fn raw(&self) -> RawHandle {
+        RawHandle {
+            graph: self.graph,
+            idx: self.idx,
+            ty: TypeId::of::<T>(),
+        }
+    }
+}
+
+/// [`Handle`] with its type erased, see [`Inputs::handles`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawHandle {
+    graph: usize,
+    idx: Option<usize>,
+    ty: TypeId,
+}
+
+impl<T: 'static> Handle<T> {
+    /// Get the value of the handle, from the input or the evaluated nodes.
+    pub(crate) fn get<'a, I: 'static>(&self, input: &'a I, values: &'a Values) -> &'a T {
+        let value = match self.idx {
+            Some(idx) => values[idx]
+                .downcast_ref::<Option<T>>()
+                .and_then(Option::as_ref),
+            None => (input as &dyn Any).downcast_ref::<T>(),
+        };
+        value.expect("the handles are checked when the nodes are added")
+    }
+}
+
+/// Errors of building graphs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// A node with the same key and inputs exists with a different output type.
+    DifferentType(String),
+    /// The handle is not one of the graph, or it is of a different type.
+    InvalidHandle,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DifferentType(key) => {
+                write!(f, "node `{key}` exists with a different output type")
+            }
+            Self::InvalidHandle => write!(f, "the handle is not one of the graph"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+pub(crate) type Values = [Box<dyn Any + Send>];
+
+/// Handles of the inputs of a node: a [`Handle`], a tuple or a `Vec` of them.
+pub trait Inputs<I> {
+    /// The values of the inputs.
+    type Values;
+
+    /// The handles with their types erased.
+    fn handles(&self) -> Vec<RawHandle>;
+
+    /// Read the values.
+    fn read(&self, input: &I, values: &Values) -> Self::Values;
+}
+
+impl<I, T> Inputs<I> for Handle<T>
+where
+    I: 'static,
+    T: Clone + 'static,
+{
+    type Values = T;
+
+    fn handles(&self) -> Vec<RawHandle> {
+        alloc::vec![self.raw()]
+    }
+
+    fn read(&self, input: &I, values: &Values) -> Self::Values {
+        self.get(input, values).clone()
+    }
+}
+
+macro_rules! tuple {
+    ($($ty:ident => $idx:tt),+) => {
+        impl<I, $($ty),+> Inputs<I> for ($(Handle<$ty>,)+)
+        where
+            I: 'static,
+            $($ty: Clone + 'static),+
+        {
+            type Values = ($($ty,)+);
+
+            fn handles(&self) -> Vec<RawHandle> {
+                alloc::vec![$(self.$idx.raw()),+]
+            }
+
+            fn read(&self, input: &I, values: &Values) -> Self::Values {
+                ($(self.$idx.read(input, values),)+)
+            }
+        }
+    };
+}
+
+tuple!(A => 0);
+tuple!(A => 0, B => 1);
+tuple!(A => 0, B => 1, C => 2);
+tuple!(A => 0, B => 1, C => 2, D => 3);
+
+impl<I, H> Inputs<I> for Vec<H>
+where
+    H: Inputs<I>,
+{
+    type Values = Vec<H::Values>;
+
+    fn handles(&self) -> Vec<RawHandle> {
+        self.iter().flat_map(Inputs::handles).collect()
+    }
+
+    fn read(&self, input: &I, values: &Values) -> Self::Values {
+        self.iter()
+            .map(|inputs| inputs.read(input, values))
+            .collect()
+    }
+}
+
+/// Node evaluated with the input and the values of the previous nodes.
+///
+/// It is implemented by the nodes of operators, and by the nodes reading
+/// their inputs themselves, such as the ones of pipelines.
+pub(crate) trait Evaluate<I>: Send {
+    /// The output.
+    type Output: Send + 'static;
+
+    /// Evaluate the output.
+    fn evaluate(&mut self, input: &I, values: &Values) -> Self::Output;
+}
+
+trait DynNode<I>: Send {
+    fn next(&mut self, input: &I, values: &Values) -> Box<dyn Any + Send>;
+    fn next_into(&mut self, input: &I, values: &Values, output: &mut (dyn Any + Send));
+}
+
+impl<I, N> DynNode<I> for N
+where
+    N: Evaluate<I>,
+{
+    fn next(&mut self, input: &I, values: &Values) -> Box<dyn Any + Send> {
+        Box::new(Some(self.evaluate(input, values)))
+    }
+
+    fn next_into(&mut self, input: &I, values: &Values, output: &mut (dyn Any + Send)) {
+        let output = output
+            .downcast_mut::<Option<N::Output>>()
+            .expect("the type of the output");
+        *output = Some(self.evaluate(input, values));
+    }
+}
+
+struct Node<H, P> {
+    inputs: H,
+    op: P,
+}
+
+impl<I, H, P> Evaluate<I> for Node<H, P>
+where
+    H: Inputs<I> + Send,
+    P: Operator<H::Values> + Send,
+    P::Output: Send + 'static,
+{
+    type Output = P::Output;
+
+    fn evaluate(&mut self, input: &I, values: &Values) -> Self::Output {
+        self.op.next(self.inputs.read(input, values))
+    }
+}
+
+/// The key of a node and the indices of its inputs.
+type NodeKey = (String, Vec<Option<usize>>);
+
+/// Builder of a graph of operators, see [`graph`].
+pub struct GraphBuilder<I> {
+    id: usize,
+    nodes: Vec<Box<dyn DynNode<I>>>,
+    types: Vec<TypeId>,
+    keys: BTreeMap<NodeKey, usize>,
+}
+
+impl<I> fmt::Debug for GraphBuilder<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GraphBuilder")
+            .field("nodes", &self.nodes.len())
+            .finish()
+    }
+}
+
+impl<I: 'static> GraphBuilder<I> {
+    /// The handle of the input.
+    pub fn input(&self) -> Handle<I> {
+        Handle::new(self.id, None)
+    }
+
+    /// The number of nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Is empty.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Check that the handles are of the graph, and get their indices.
+    fn indices(&self, handles: Vec<RawHandle>) -> Result<Vec<Option<usize>>, Error> {
+        handles
+            .into_iter()
+            .map(|handle| {
+                let expected = match handle.idx {
+                    Some(idx) => self.types.get(idx).copied(),
+                    None => Some(TypeId::of::<I>()),
+                };
+                if handle.graph == self.id && expected == Some(handle.ty) {
+                    Ok(handle.idx)
+                } else {
+                    Err(Error::InvalidHandle)
+                }
+            })
+            .collect()
+    }
+
+    /// Add a node applying `op` to the values of `inputs`,
+    /// unless a node with the same `key` and the same inputs exists,
+    /// in which case its handle is returned and `op` is dropped.
+    ///
+    /// The key should identify the operator with its parameters, e.g. `"ema(12)"`.
+    ///
+    /// # Errors
+    /// Return an error if one of the handles is not of the graph,
+    /// or if the existing node has a different output type.
+    pub fn node<H, P>(
+        &mut self,
+        key: impl Into<String>,
+        inputs: H,
+        op: P,
+    ) -> Result<Handle<P::Output>, Error>
+    where
+        H: Inputs<I> + Send + 'static,
+        P: Operator<H::Values> + Send + 'static,
+        P::Output: Send + 'static,
+    {
+        let handles = inputs.handles();
+        self.evaluate(key.into(), handles, || Ok(Node { inputs, op }))
+    }
+
+    /// Add the node built by `build` with the inputs of `handles`,
+    /// unless a node with the same `key` and the same inputs exists.
+    pub(crate) fn evaluate<N, E>(
+        &mut self,
+        key: String,
+        handles: Vec<RawHandle>,
+        build: impl FnOnce() -> Result<N, E>,
+    ) -> Result<Handle<N::Output>, E>
+    where
+        N: Evaluate<I> + 'static,
+        E: From<Error>,
+    {
+        let key = (key, self.indices(handles)?);
+        let ty = TypeId::of::<N::Output>();
+        if let Some(idx) = self.keys.get(&key) {
+            if self.types[*idx] != ty {
+                return Err(Error::DifferentType(key.0).into());
+            }
+            return Ok(Handle::new(self.id, Some(*idx)));
+        }
+        self.nodes.push(Box::new(build()?));
+        self.types.push(ty);
+        let idx = self.nodes.len() - 1;
+        self.keys.insert(key, idx);
+        Ok(Handle::new(self.id, Some(idx)))
+    }
+
+    /// Build the graph into an operator producing the values of `outputs`.
+    ///
+    /// # Errors
+    /// Return an error if one of the handles is not of the graph.
+    pub fn build<H>(self, outputs: H) -> Result<Graph<I, H>, Error>
+    where
+        H: Inputs<I>,
+    {
+        self.indices(outputs.handles())?;
+        Ok(Graph {
+            nodes: self.nodes,
+            values: Vec::new(),
+            outputs,
+        })
+    }
+}
+
+/// Create a builder of a graph of operators with the input of type `I`.
+///
+/// Nodes are operators applied to the outputs of the previous nodes (or the input).
+/// Identical nodes (with the same key and inputs) are added only once,
+/// so shared subcomputations are evaluated once per input.
+///
+/// ```
+/// use indicator::{graph::graph, map, IndicatorIteratorExt};
+///
+/// let mut builder = graph::<i64>();
+/// let x = builder.input();
+/// let double = builder.node("double", x, map(|x: i64| x * 2)).unwrap();
+/// let plus_one = builder.node("plus_one", double, map(|x: i64| x + 1)).unwrap();
+/// // The same node is returned.
+/// assert!(builder.node("double", x, map(|x: i64| x * 2)).unwrap() == double);
+/// let op = builder.build((double, plus_one)).unwrap();
+/// assert!([1, 2].into_iter().indicator(op).eq([(2, 3), (4, 5)]));
+/// ```
+pub fn graph<I: 'static>() -> GraphBuilder<I> {
+    // Identify the builder, so that the handles of other builders are rejected.
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+    GraphBuilder {
+        id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+        nodes: Vec::new(),
+        types: Vec::new(),
+        keys: BTreeMap::new(),
+    }
+}
+
+/// Operator evaluating the nodes of a graph in topological order.
+///
+/// Created by [`GraphBuilder::build`].
+pub struct Graph<I, H> {
+    nodes: Vec<Box<dyn DynNode<I>>>,
+    values: Vec<Box<dyn Any + Send>>,
+    outputs: H,
+}
+
+impl<I, H> fmt::Debug for Graph<I, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Graph")
+            .field("nodes", &self.nodes.len())
+            .finish()
+    }
+}
+
+impl<I, H> Operator<I> for Graph<I, H>
+where
+    H: Inputs<I>,
+{
+    type Output = H::Values;
+
+    fn next(&mut self, input: I) -> Self::Output {
+        let Self {
+            nodes,
+            values,
+            outputs,
+        } = self;
+        for (idx, node) in nodes.iter_mut().enumerate() {
+            // Nodes only depend on the previous ones.
+            let (evaluated, rest) = values.split_at_mut(idx);
+            match rest.first_mut() {
+                Some(output) => node.next_into(&input, evaluated, output.as_mut()),
+                None => {
+                    let output = node.next(&input, evaluated);
+                    values.push(output);
+                }
+            }
+        }
+        outputs.read(&input, values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{map, IndicatorIteratorExt, OperatorExt};
+    use alloc::sync::Arc;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn shared() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let ema = |length: i64| {
+            let count = count.clone();
+            let mut value = None;
+            map(move |x: i64| {
+                count.fetch_add(1, Ordering::Relaxed);
+                let ema = value.map_or(x, |prev: i64| prev + (x - prev) * 2 / (length + 1));
+                value = Some(ema);
+                ema
+            })
+        };
+        let mut builder = graph::<i64>();
+        let x = builder.input();
+        let fast = builder.node("ema(1)", x, ema(1)).unwrap();
+        let slow = builder.node("ema(3)", x, ema(3)).unwrap();
+        let macd = builder
+            .node("sub", (fast, slow), map(|(a, b)| a - b))
+            .unwrap();
+        let fast_again = builder.node("ema(1)", x, ema(1)).unwrap();
+        let cross = builder
+            .node("gt", (fast_again, macd), map(|(a, b)| a > b))
+            .unwrap();
+        assert_eq!(builder.len(), 4);
+        let op = builder.build((macd, cross)).unwrap().boxed();
+        let outputs = [10, 20, 30].into_iter().indicator(op);
+        assert!(outputs.eq([(0, true), (5, true), (8, true)]));
+        assert_eq!(count.load(Ordering::Relaxed), 6);
+    }
+
+    #[test]
+    fn errors() {
+        let mut builder = graph::<i64>();
+        let x = builder.input();
+        builder.node("f", x, map(|x: i64| x)).unwrap();
+        assert!(matches!(
+            builder.node("f", x, map(|x: i64| x > 0)),
+            Err(Error::DifferentType(_))
+        ));
+
+        // Handles of another graph.
+        let mut other = graph::<bool>();
+        let y = other.input();
+        let z = other.node("g", y, map(|y: bool| !y)).unwrap();
+        assert_eq!(
+            builder.node("h", y, map(|y: bool| y)).err(),
+            Some(Error::InvalidHandle)
+        );
+        assert_eq!(
+            builder.node("h", z, map(|z: bool| z)).err(),
+            Some(Error::InvalidHandle)
+        );
+        assert_eq!(builder.build(z).err(), Some(Error::InvalidHandle));
+    }
+
+    #[test]
+    fn foreign() {
+        let mut first = graph::<i64>();
+        let mut second = graph::<i64>();
+        let (x, y) = (first.input(), second.input());
+        assert!(x != y);
+        let f = first.node("f", x, map(|x: i64| x + 1)).unwrap();
+        let g = second.node("g", y, map(|y: i64| y * 2)).unwrap();
+        // Same index and type, but of another builder.
+        assert_eq!(
+            second.node("h", f, map(|f: i64| f)).err(),
+            Some(Error::InvalidHandle)
+        );
+        assert_eq!(
+            first.node("h", y, map(|y: i64| y)).err(),
+            Some(Error::InvalidHandle)
+        );
+        assert_eq!(first.build(g).err(), Some(Error::InvalidHandle));
+        let op = second.build((y, g)).unwrap();
+        assert!([1, 2].into_iter().indicator(op).eq([(1, 2), (2, 4)]));
+    }
+}
@@ -0,0 +1,220 @@
+use alloc::{string::String, vec::Vec};
+use core::marker::PhantomData;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::{fs::File, io, path::Path};
+
+use super::{mapping::CsvColumns, Error, Mapping};
+use crate::TickValue;
+
+/// Reader of CSV market data (with headers), yielding [`TickValue`]s.
+///
+/// The cells are deserialized against the types of the fields,
+/// so a cell such as `001` is kept as is for a `String` field.
+pub struct CsvReader<R, T> {
+    headers: ::csv::StringRecord,
+    columns: CsvColumns,
+    records: ::csv::StringRecordsIntoIter<R>,
+    mapping: Mapping,
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<T> CsvReader<File, T> {
+    /// Open a CSV file.
+    pub fn from_path(path: impl AsRef<Path>, mapping: Mapping) -> Result<Self, Error> {
+        Self::new(File::open(path)?, mapping)
+    }
+}
+
+impl<R: io::Read, T> CsvReader<R, T> {
+    /// Create a reader, the first line is read as the headers.
+    ///
+    /// # Errors
+    /// Return an error if a column of the mapping is not in the headers.
+    pub fn new(reader: R, mapping: Mapping) -> Result<Self, Error> {
+        let mut reader = ::csv::Reader::from_reader(reader);
+        let headers = reader.headers()?.clone();
+        let columns = mapping.resolve_csv(&headers)?;
+        Ok(Self {
+            headers,
+            columns,
+            records: reader.into_records(),
+            mapping,
+            _value: PhantomData,
+        })
+    }
+}
+
+impl<R: io::Read, T: DeserializeOwned> Iterator for CsvReader<R, T> {
+    type Item = Result<TickValue<T>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let record = match self.records.next()? {
+            Ok(record) => record,
+            Err(err) => return Some(Err(err.into())),
+        };
+        Some(self.mapping.decode_csv(&self.columns, &record))
+    }
+}
+
+/// Writer of [`TickValue`]s as CSV, with the headers given by the first row.
+pub struct CsvWriter<W: io::Write> {
+    writer: ::csv::Writer<W>,
+    mapping: Mapping,
+    headers: Option<Vec<String>>,
+}
+
+impl CsvWriter<File> {
+    /// Create a CSV file.
+    pub fn from_path(path: impl AsRef<Path>, mapping: Mapping) -> Result<Self, Error> {
+        Ok(Self::new(File::create(path)?, mapping))
+    }
+}
+
+impl<W: io::Write> CsvWriter<W> {
+    /// Create a writer.
+    pub fn new(writer: W, mapping: Mapping) -> Self {
+        Self {
+            writer: ::csv::Writer::from_writer(writer),
+            mapping,
+            headers: None,
+        }
+    }
+
+    /// Write a row.
+    ///
+    /// Columns that are not in the headers are ignored,
+    /// and missing columns are written as empty cells.
+    pub fn write<T: Serialize>(&mut self, row: &TickValue<T>) -> Result<(), Error> {
+        let mut record = self.mapping.encode(row)?;
+        let headers = match &self.headers {
+            Some(headers) => headers,
+            None => {
+                let headers: Vec<String> = record.keys().cloned().collect();
+                self.writer.write_record(&headers)?;
+                self.headers.insert(headers)
+            }
+        };
+        let cells = headers
+            .iter()
+            .map(|column| match record.remove(column) {
+                None | Some(Value::Null) => String::new(),
+                Some(Value::String(text)) => text,
+                Some(value) => value.to_string(),
+            })
+            .collect::<Vec<_>>();
+        self.writer.write_record(&cells)?;
+        Ok(())
+    }
+
+    /// Flush the underlying writer.
+    pub fn flush(&mut self) -> Result<(), Error> {
+        Ok(self.writer.flush()?)
+    }
+
+    /// Flush and return the underlying writer.
+    pub fn into_inner(self) -> Result<W, Error> {
+        self.writer
+            .into_inner()
+            .map_err(|err| Error::Io(err.into_error()))
+    }
+}
+
+impl<W: io::Write> core::fmt::Debug for CsvWriter<W> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CsvWriter")
+            .field("mapping", &self.mapping)
+            .field("headers", &self.headers)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R, T> core::fmt::Debug for CsvReader<R, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CsvReader")
+            .field("headers", &self.headers)
+            .field("mapping", &self.mapping)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bar::Ohlc, io::TimestampFormat, map_t, IndicatorIteratorExt};
+
+    const DATA: &str = "\
+ts,open,high,low,close,volume
+1672531200,1.0,2.0,0.5,1.5,10
+1672531260,1.5,3.0,1.0,2.5,20
+";
+
+    #[test]
+    fn roundtrip() {
+        let mapping = Mapping::new().format(TimestampFormat::UnixSecs);
+        let reader = CsvReader::<_, Ohlc<f64>>::new(DATA.as_bytes(), mapping.clone()).unwrap();
+        let mut writer = CsvWriter::new(Vec::new(), mapping);
+        for row in reader
+            .map(Result::unwrap)
+            .indicator(map_t(|bar: Ohlc<f64>| bar))
+        {
+            writer.write(&row).unwrap();
+        }
+        let output = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        assert_eq!(
+            output,
+            "\
+ts,open,high,low,close
+1672531200,1.0,2.0,0.5,1.5
+1672531260,1.5,3.0,1.0,2.5
+"
+        );
+    }
+
+    #[test]
+    fn typed_cells() {
+        #[derive(Debug, PartialEq, serde::Deserialize)]
+        struct Trade {
+            id: String,
+            side: String,
+            price: f64,
+            size: Option<f64>,
+        }
+
+        let data = "\
+ts,id,side,price,size
+1672531200,001,true,1.5,
+1672531260,002,false,2.5,10
+";
+        let mapping = Mapping::new().format(TimestampFormat::UnixSecs);
+        let rows = CsvReader::<_, Trade>::new(data.as_bytes(), mapping)
+            .unwrap()
+            .map(|row| row.unwrap().value)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rows[0],
+            Trade {
+                id: "001".into(),
+                side: "true".into(),
+                price: 1.5,
+                size: None,
+            }
+        );
+        assert_eq!(rows[1].size, Some(10.0));
+
+        let mapping = Mapping::new().format(TimestampFormat::UnixSecs).value("id");
+        let mut ids = CsvReader::<_, String>::new(data.as_bytes(), mapping).unwrap();
+        assert_eq!(ids.next().unwrap().unwrap().value, "001");
+    }
+
+    #[test]
+    fn missing_column() {
+        let reader = CsvReader::<_, f64>::new(
+            DATA.as_bytes(),
+            Mapping::new()
+                .format(TimestampFormat::UnixSecs)
+                .value("price"),
+        );
+        assert!(matches!(reader, Err(Error::MissingColumn(_))));
+    }
+}
@@ -0,0 +1,134 @@
+use core::marker::PhantomData;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::Path,
+};
+
+use super::{Error, Mapping};
+use crate::TickValue;
+
+/// Reader of newline-delimited JSON market data, yielding [`TickValue`]s.
+///
+/// Each non-empty line is a JSON object.
+#[derive(Debug)]
+pub struct JsonlReader<R, T> {
+    lines: io::Lines<R>,
+    mapping: Mapping,
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<T> JsonlReader<BufReader<File>, T> {
+    /// Open a JSON-lines file.
+    pub fn from_path(path: impl AsRef<Path>, mapping: Mapping) -> Result<Self, Error> {
+        Ok(Self::new(BufReader::new(File::open(path)?), mapping))
+    }
+}
+
+impl<R: BufRead, T> JsonlReader<R, T> {
+    /// Create a reader.
+    pub fn new(reader: R, mapping: Mapping) -> Self {
+        Self {
+            lines: reader.lines(),
+            mapping,
+            _value: PhantomData,
+        }
+    }
+}
+
+impl<R: BufRead, T: DeserializeOwned> Iterator for JsonlReader<R, T> {
+    type Item = Result<TickValue<T>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = loop {
+            match self.lines.next()? {
+                Ok(line) if line.trim().is_empty() => continue,
+                Ok(line) => break line,
+                Err(err) => return Some(Err(err.into())),
+            }
+        };
+        let record = match serde_json::from_str(&line) {
+            Ok(Value::Object(record)) => record,
+            Ok(_) => return Some(Err(Error::InvalidRecord(line))),
+            Err(err) => return Some(Err(err.into())),
+        };
+        Some(self.mapping.decode(record))
+    }
+}
+
+/// Writer of [`TickValue`]s as newline-delimited JSON.
+#[derive(Debug)]
+pub struct JsonlWriter<W> {
+    writer: W,
+    mapping: Mapping,
+}
+
+impl JsonlWriter<io::BufWriter<File>> {
+    /// Create a JSON-lines file.
+    pub fn from_path(path: impl AsRef<Path>, mapping: Mapping) -> Result<Self, Error> {
+        Ok(Self::new(io::BufWriter::new(File::create(path)?), mapping))
+    }
+}
+
+impl<W: io::Write> JsonlWriter<W> {
+    /// Create a writer.
+    pub fn new(writer: W, mapping: Mapping) -> Self {
+        Self { writer, mapping }
+    }
+
+    /// Write a row.
+    pub fn write<T: Serialize>(&mut self, row: &TickValue<T>) -> Result<(), Error> {
+        let record = self.mapping.encode(row)?;
+        serde_json::to_writer(&mut self.writer, &record)?;
+        self.writer.write_all(b"\n")?;
+        Ok(())
+    }
+
+    /// Flush the underlying writer.
+    pub fn flush(&mut self) -> Result<(), Error> {
+        Ok(self.writer.flush()?)
+    }
+
+    /// Flush and return the underlying writer.
+    pub fn into_inner(mut self) -> Result<W, Error> {
+        self.flush()?;
+        Ok(self.writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{map_t, IndicatorIteratorExt};
+    use alloc::string::String;
+
+    #[test]
+    fn roundtrip() {
+        let data = r#"{"ts":"2023-01-01T00:00:00Z","price":1.5}
+
+{"ts":null,"price":2.5}
+"#;
+        let mapping = Mapping::new().value("price");
+        let reader = JsonlReader::<_, f64>::new(data.as_bytes(), mapping.clone());
+        let mut writer = JsonlWriter::new(Vec::new(), mapping);
+        for row in reader
+            .map(Result::unwrap)
+            .indicator(map_t(|x: f64| x * 2.0))
+        {
+            writer.write(&row).unwrap();
+        }
+        let output = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        assert_eq!(
+            output,
+            "{\"ts\":\"2023-01-01T00:00:00Z\",\"price\":3.0}\n{\"ts\":null,\"price\":5.0}\n"
+        );
+    }
+
+    #[test]
+    fn invalid_record() {
+        let mut reader = JsonlReader::<_, f64>::new("[1]".as_bytes(), Mapping::new());
+        assert!(matches!(reader.next(), Some(Err(Error::InvalidRecord(_)))));
+    }
+}
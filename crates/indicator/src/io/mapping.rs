@@ -0,0 +1,346 @@
+use ::csv::StringRecord;
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+use time::{
+    format_description::{self, OwnedFormatItem},
+    OffsetDateTime, PrimitiveDateTime,
+};
+
+use super::Error;
+use crate::{Tick, TickValue};
+
+/// The format of the timestamp column.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum TimestampFormat {
+    /// RFC3339 strings, the same as the serde representation of [`Tick`].
+    #[default]
+    Rfc3339,
+    /// Seconds since the Unix epoch.
+    UnixSecs,
+    /// Milliseconds since the Unix epoch.
+    UnixMillis,
+    /// Nanoseconds since the Unix epoch.
+    UnixNanos,
+    /// Custom format description (see [`time::format_description`]).
+    /// Datetimes without offset are taken as UTC.
+    Custom(OwnedFormatItem),
+}
+
+impl TimestampFormat {
+    /// Create a custom format from its description,
+    /// e.g. `"[year]-[month]-[day] [hour]:[minute]:[second]"`.
+    pub fn custom(description: &str) -> Result<Self, Error> {
+        format_description::parse_owned::<2>(description)
+            .map(Self::Custom)
+            .map_err(|err| Error::Timestamp(err.to_string()))
+    }
+
+    /// Parse a tick from the value of the timestamp column,
+    /// null is parsed as [`Tick::BIG_BANG`].
+    pub fn parse(&self, value: Value) -> Result<Tick, Error> {
+        let invalid = |value: &Value| Error::Timestamp(value.to_string());
+        let scale = match self {
+            Self::Rfc3339 => return Ok(serde_json::from_value(value)?),
+            _ if value.is_null() => return Ok(Tick::BIG_BANG),
+            Self::UnixSecs => 1_000_000_000,
+            Self::UnixMillis => 1_000_000,
+            Self::UnixNanos => 1,
+            Self::Custom(format) => {
+                let text = value.as_str().ok_or_else(|| invalid(&value))?;
+                let ts = OffsetDateTime::parse(text, format)
+                    .or_else(|_| PrimitiveDateTime::parse(text, format).map(|ts| ts.assume_utc()))
+                    .map_err(|_| invalid(&value))?;
+                return Ok(Tick::new(ts));
+            }
+        };
+        let ts = match &value {
+            Value::Number(n) => n.as_i64(),
+            Value::String(s) => s.parse().ok(),
+            _ => None,
+        }
+        .ok_or_else(|| invalid(&value))?;
+        OffsetDateTime::from_unix_timestamp_nanos(i128::from(ts) * scale)
+            .map(Tick::new)
+            .map_err(|_| invalid(&value))
+    }
+
+    /// Format a tick as the value of the timestamp column,
+    /// [`Tick::BIG_BANG`] is formatted as null.
+    pub fn format(&self, tick: Tick) -> Result<Value, Error> {
+        let scale = match self {
+            Self::Rfc3339 => return Ok(serde_json::to_value(tick)?),
+            _ if tick.ts().is_none() => return Ok(Value::Null),
+            Self::UnixSecs => 1_000_000_000,
+            Self::UnixMillis => 1_000_000,
+            Self::UnixNanos => 1,
+            Self::Custom(format) => {
+                let text = tick
+                    .ts()
+                    .map(|ts| ts.format(format))
+                    .transpose()
+                    .map_err(|err| Error::Timestamp(err.to_string()))?;
+                return Ok(text.map_or(Value::Null, Value::String));
+            }
+        };
+        let ts = tick.ts().map(|ts| ts.unix_timestamp_nanos() / scale);
+        let ts = ts
+            .and_then(|ts| i64::try_from(ts).ok())
+            .ok_or_else(|| Error::Timestamp(alloc::format!("{tick:?}")))?;
+        Ok(Value::from(ts))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Fields {
+    Rest,
+    Scalar(String),
+    Mapped(Vec<(String, String)>),
+}
+
+/// The columns of a [`Mapping`] resolved against the headers of a CSV file.
+#[derive(Debug, Clone)]
+pub(super) struct CsvColumns {
+    timestamp: usize,
+    /// The names of the fields, `None` for a single column.
+    names: Option<StringRecord>,
+    cells: Vec<usize>,
+}
+
+/// How the columns of a record are mapped to a [`TickValue`].
+///
+/// By default, the timestamp is read from the column `ts` in RFC3339 format,
+/// and the value is deserialized from the rest of the columns by their names,
+/// e.g. `open`, `high`, `low`, `close` for [`Ohlc`](crate::bar::Ohlc).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mapping {
+    timestamp: String,
+    format: TimestampFormat,
+    fields: Fields,
+}
+
+impl Default for Mapping {
+    fn default() -> Self {
+        Self {
+            timestamp: "ts".into(),
+            format: TimestampFormat::default(),
+            fields: Fields::Rest,
+        }
+    }
+}
+
+impl Mapping {
+    /// Create the default mapping.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the timestamp column.
+    pub fn timestamp(mut self, column: impl Into<String>) -> Self {
+        self.timestamp = column.into();
+        self
+    }
+
+    /// Set the timestamp format.
+    pub fn format(mut self, format: TimestampFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Read the value from a single column.
+    pub fn value(mut self, column: impl Into<String>) -> Self {
+        self.fields = Fields::Scalar(column.into());
+        self
+    }
+
+    /// Read the `field` of the value from `column`.
+    ///
+    /// Once a field is mapped, only the mapped columns are read.
+    pub fn field(mut self, field: impl Into<String>, column: impl Into<String>) -> Self {
+        let pair = (field.into(), column.into());
+        match &mut self.fields {
+            Fields::Mapped(fields) => fields.push(pair),
+            fields => *fields = Fields::Mapped(alloc::vec![pair]),
+        }
+        self
+    }
+
+    /// Decode a record.
+    pub fn decode<T: DeserializeOwned>(
+        &self,
+        mut record: Map<String, Value>,
+    ) -> Result<TickValue<T>, Error> {
+        let mut take = |column: &str| {
+            record
+                .remove(column)
+                .ok_or_else(|| Error::MissingColumn(column.into()))
+        };
+        let tick = self.format.parse(take(&self.timestamp)?)?;
+        let value = match &self.fields {
+            Fields::Scalar(column) => take(column)?,
+            Fields::Mapped(fields) => fields
+                .iter()
+                .map(|(field, column)| Ok((field.clone(), take(column)?)))
+                .collect::<Result<Map<_, _>, Error>>()?
+                .into(),
+            Fields::Rest => record.into(),
+        };
+        Ok(TickValue {
+            tick,
+            value: serde_json::from_value(value)?,
+        })
+    }
+
+    /// Resolve the columns against the `headers` of a CSV file.
+    pub(super) fn resolve_csv(&self, headers: &StringRecord) -> Result<CsvColumns, Error> {
+        let position = |column: &str| {
+            headers
+                .iter()
+                .position(|header| header == column)
+                .ok_or_else(|| Error::MissingColumn(column.into()))
+        };
+        let timestamp = position(&self.timestamp)?;
+        let (names, cells) = match &self.fields {
+            Fields::Scalar(column) => (None, alloc::vec![position(column)?]),
+            Fields::Mapped(fields) => {
+                let names = fields.iter().map(|(field, _)| field.as_str()).collect();
+                let cells = fields
+                    .iter()
+                    .map(|(_, column)| position(column))
+                    .collect::<Result<_, _>>()?;
+                (Some(names), cells)
+            }
+            Fields::Rest => {
+                let (names, cells): (StringRecord, _) = headers
+                    .iter()
+                    .enumerate()
+                    .filter(|(idx, _)| *idx != timestamp)
+                    .map(|(idx, header)| (header, idx))
+                    .unzip();
+                (Some(names), cells)
+            }
+        };
+        Ok(CsvColumns {
+            timestamp,
+            names,
+            cells,
+        })
+    }
+
+    /// Decode a CSV record with the resolved `columns`,
+    /// deserializing the cells against the types of the fields.
+    ///
+    /// An empty timestamp cell is parsed as null.
+    pub(super) fn decode_csv<T: DeserializeOwned>(
+        &self,
+        columns: &CsvColumns,
+        record: &StringRecord,
+    ) -> Result<TickValue<T>, Error> {
+        // The records have as many cells as the headers, which is checked by the reader.
+        let tick = match &record[columns.timestamp] {
+            "" => self.format.parse(Value::Null)?,
+            ts => self.format.parse(Value::String(ts.into()))?,
+        };
+        let cells = columns
+            .cells
+            .iter()
+            .map(|idx| &record[*idx])
+            .collect::<StringRecord>();
+        let value = cells.deserialize(columns.names.as_ref())?;
+        Ok(TickValue { tick, value })
+    }
+
+    /// Encode a row as a record, the timestamp column comes first.
+    ///
+    /// Values that are not serialized as maps are written to the column `value`,
+    /// unless a single column is specified.
+    pub fn encode<T: Serialize>(&self, row: &TickValue<T>) -> Result<Map<String, Value>, Error> {
+        let mut record = Map::new();
+        record.insert(self.timestamp.clone(), self.format.format(row.tick)?);
+        match (&self.fields, serde_json::to_value(&row.value)?) {
+            (Fields::Scalar(column), value) => {
+                record.insert(column.clone(), value);
+            }
+            (Fields::Mapped(fields), Value::Object(mut value)) => {
+                for (field, column) in fields {
+                    let value = value
+                        .remove(field)
+                        .ok_or_else(|| Error::MissingColumn(field.clone()))?;
+                    record.insert(column.clone(), value);
+                }
+            }
+            (_, Value::Object(value)) => record.extend(value),
+            (_, value) => {
+                record.insert("value".into(), value);
+            }
+        }
+        Ok(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bar::Ohlc;
+    use serde_json::json;
+    use time::macros::datetime;
+
+    #[test]
+    fn timestamps() {
+        let tick = Tick::new(datetime!(2023-01-01 00:00:01 UTC));
+        let formats = [
+            (TimestampFormat::Rfc3339, json!("2023-01-01T00:00:01Z")),
+            (TimestampFormat::UnixSecs, json!(1672531201)),
+            (TimestampFormat::UnixMillis, json!(1672531201000i64)),
+            (
+                TimestampFormat::custom("[year]-[month]-[day] [hour]:[minute]:[second]").unwrap(),
+                json!("2023-01-01 00:00:01"),
+            ),
+        ];
+        for (format, value) in formats {
+            assert_eq!(format.format(tick).unwrap(), value);
+            assert_eq!(format.parse(value).unwrap(), tick);
+        }
+        assert_eq!(
+            TimestampFormat::UnixMillis
+                .parse(json!("1672531201000"))
+                .unwrap(),
+            tick
+        );
+        assert_eq!(
+            TimestampFormat::UnixSecs.parse(Value::Null).unwrap(),
+            Tick::BIG_BANG
+        );
+    }
+
+    #[test]
+    fn mapping() {
+        let record = json!({"t": 1672531201, "o": 1, "h": 3, "l": 0, "c": 2, "v": 10});
+        let Value::Object(record) = record else {
+            unreachable!()
+        };
+        let mapping = Mapping::new()
+            .timestamp("t")
+            .format(TimestampFormat::UnixSecs)
+            .field("open", "o")
+            .field("high", "h")
+            .field("low", "l")
+            .field("close", "c");
+        let row = mapping.decode::<Ohlc<i64>>(record.clone()).unwrap();
+        assert_eq!(row.value.high, 3);
+        let mut encoded = mapping.encode(&row).unwrap();
+        assert_eq!(encoded.keys().next().unwrap(), "t");
+        encoded.insert("v".into(), json!(10));
+        assert_eq!(encoded, record);
+
+        let close = Mapping::new()
+            .timestamp("t")
+            .format(TimestampFormat::UnixSecs)
+            .value("c");
+        let row = close.decode::<i64>(record).unwrap();
+        assert_eq!(row.value, 2);
+    }
+}
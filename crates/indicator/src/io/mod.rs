@@ -0,0 +1,38 @@
+use alloc::string::String;
+use thiserror::Error;
+
+/// Column mapping and timestamp formats.
+pub mod mapping;
+
+/// CSV readers and writers.
+pub mod csv;
+
+/// JSON-lines readers and writers.
+pub mod jsonl;
+
+pub use self::csv::{CsvReader, CsvWriter};
+pub use jsonl::{JsonlReader, JsonlWriter};
+pub use mapping::{Mapping, TimestampFormat};
+
+/// Errors of reading and writing market data.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// IO error.
+    #[error("io: {0}")]
+    Io(#[from] std::io::Error),
+    /// CSV error.
+    #[error("csv: {0}")]
+    Csv(#[from] ::csv::Error),
+    /// JSON error.
+    #[error("json: {0}")]
+    Json(#[from] serde_json::Error),
+    /// A column is missing from the record.
+    #[error("missing column: {0}")]
+    MissingColumn(String),
+    /// The record is not an object.
+    #[error("invalid record: {0}")]
+    InvalidRecord(String),
+    /// Invalid timestamp.
+    #[error("invalid timestamp: {0}")]
+    Timestamp(String),
+}
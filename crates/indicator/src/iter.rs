@@ -1,4 +1,11 @@
-use crate::Operator;
+use alloc::{collections::VecDeque, vec::Vec};
+
+use crate::{
+    flat::FlatOperator,
+    readiness::{Marked, Readiness},
+    try_operator::TryOperator,
+    Operator,
+};
 
 /// Operated iterator.
 #[derive(Debug, Clone, Copy)]
@@ -19,6 +26,159 @@ where
     }
 }
 
+/// Flat-operated iterator.
+///
+/// The outputs of an input are queued, reusing the queue across the inputs.
+#[derive(Debug, Clone)]
+pub struct FlatOperated<It, P, O> {
+    source: It,
+    op: P,
+    pending: VecDeque<O>,
+}
+
+impl<It, P> Iterator for FlatOperated<It, P, P::Item>
+where
+    It: Iterator,
+    P: FlatOperator<It::Item>,
+{
+    type Item = P::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(output) = self.pending.pop_front() {
+                return Some(output);
+            }
+            let input = self.source.next()?;
+            let pending = &mut self.pending;
+            self.op
+                .for_each(input, &mut |output| pending.push_back(output));
+        }
+    }
+}
+
+/// Batch-operated iterator, feeding the operator with batches of inputs.
+///
+/// The buffers of the inputs and the outputs are reused across the batches.
+#[derive(Debug, Clone)]
+pub struct BatchOperated<It: Iterator, P, O> {
+    source: It,
+    op: P,
+    size: usize,
+    inputs: Vec<It::Item>,
+    outputs: Vec<O>,
+    pending: VecDeque<O>,
+}
+
+impl<It, P> Iterator for BatchOperated<It, P, P::Output>
+where
+    It: Iterator,
+    P: Operator<It::Item>,
+{
+    type Item = P::Output;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(output) = self.pending.pop_front() {
+                return Some(output);
+            }
+            self.inputs.extend(self.source.by_ref().take(self.size));
+            if self.inputs.is_empty() {
+                return None;
+            }
+            self.op.drain_batch(&mut self.inputs, &mut self.outputs);
+            self.pending.extend(self.outputs.drain(..));
+        }
+    }
+}
+
+/// Try-operated iterator, stopping after the first error.
+#[derive(Debug, Clone, Copy)]
+pub struct TryOperated<It, P> {
+    source: Option<It>,
+    op: P,
+}
+
+impl<It, P> Iterator for TryOperated<It, P>
+where
+    It: Iterator,
+    P: TryOperator<It::Item>,
+{
+    type Item = Result<P::Output, P::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let input = self.source.as_mut()?.next()?;
+        let res = self.op.try_next(input);
+        if res.is_err() {
+            self.source = None;
+        }
+        Some(res)
+    }
+}
+
+/// Try-operated iterator, skipping the errors.
+#[derive(Debug, Clone, Copy)]
+pub struct SkipErrOperated<It, P> {
+    source: It,
+    op: P,
+}
+
+impl<It, P> Iterator for SkipErrOperated<It, P>
+where
+    It: Iterator,
+    P: TryOperator<It::Item>,
+{
+    type Item = P::Output;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.source
+            .by_ref()
+            .find_map(|input| self.op.try_next(input).ok())
+    }
+}
+
+/// Marked-operated iterator, marking the outputs with the readiness of the operator.
+#[derive(Debug, Clone, Copy)]
+pub struct MarkedOperated<It, P> {
+    source: It,
+    op: P,
+}
+
+impl<It, P> Iterator for MarkedOperated<It, P>
+where
+    It: Iterator,
+    P: Operator<It::Item> + Readiness,
+{
+    type Item = Marked<P::Output>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let input = self.source.next()?;
+        let output = self.op.next(input);
+        Some(Marked::new(output, self.op.is_ready()))
+    }
+}
+
+/// Warmed-operated iterator, skipping the outputs produced before the operator is ready.
+#[derive(Debug, Clone, Copy)]
+pub struct WarmedOperated<It, P> {
+    source: It,
+    op: P,
+}
+
+impl<It, P> Iterator for WarmedOperated<It, P>
+where
+    It: Iterator,
+    P: Operator<It::Item> + Readiness,
+{
+    type Item = P::Output;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.source.by_ref().find_map(|input| {
+            let output = self.op.next(input);
+            self.op.is_ready().then_some(output)
+        })
+    }
+}
+
 /// Iterator extension trait for indicators.
 pub trait IndicatorIteratorExt: Iterator {
     /// Apply an indicator to the iterator.
@@ -29,6 +189,78 @@ pub trait IndicatorIteratorExt: Iterator {
     {
         Operated { source: self, op }
     }
+
+    /// Apply a [`FlatOperator`] to the iterator, flattening the outputs.
+    fn flat_indicator<P>(self, op: P) -> FlatOperated<Self, P, P::Item>
+    where
+        Self: Sized,
+        P: FlatOperator<Self::Item>,
+    {
+        FlatOperated {
+            source: self,
+            op,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Apply an indicator to the iterator, feeding it with batches of `size` inputs
+    /// through [`Operator::drain_batch`].
+    ///
+    /// A `size` of `0` is treated as `1`.
+    fn batch_indicator<P>(self, op: P, size: usize) -> BatchOperated<Self, P, P::Output>
+    where
+        Self: Sized,
+        P: Operator<Self::Item>,
+    {
+        let size = size.max(1);
+        BatchOperated {
+            source: self,
+            op,
+            size,
+            inputs: Vec::with_capacity(size),
+            outputs: Vec::with_capacity(size),
+            pending: VecDeque::with_capacity(size),
+        }
+    }
+
+    /// Apply a [`TryOperator`] to the iterator, stopping after the first error.
+    fn try_indicator<P>(self, op: P) -> TryOperated<Self, P>
+    where
+        Self: Sized,
+        P: TryOperator<Self::Item>,
+    {
+        TryOperated {
+            source: Some(self),
+            op,
+        }
+    }
+
+    /// Apply a [`TryOperator`] to the iterator, skipping the errors.
+    fn skip_err_indicator<P>(self, op: P) -> SkipErrOperated<Self, P>
+    where
+        Self: Sized,
+        P: TryOperator<Self::Item>,
+    {
+        SkipErrOperated { source: self, op }
+    }
+
+    /// Apply an indicator to the iterator, marking the outputs with its readiness.
+    fn marked_indicator<P>(self, op: P) -> MarkedOperated<Self, P>
+    where
+        Self: Sized,
+        P: Operator<Self::Item> + Readiness,
+    {
+        MarkedOperated { source: self, op }
+    }
+
+    /// Apply an indicator to the iterator, skipping the outputs produced before it is ready.
+    fn warmed_indicator<P>(self, op: P) -> WarmedOperated<Self, P>
+    where
+        Self: Sized,
+        P: Operator<Self::Item> + Readiness,
+    {
+        WarmedOperated { source: self, op }
+    }
 }
 
 impl<It: Iterator> IndicatorIteratorExt for It {}
@@ -3,22 +3,22 @@
 //! This crate provides abstractions of different levels to build indicators:
 //!
 //! - Indicators are the combinations of [`Operator`]s. We can chain them by [`OperatorExt::then`]
-//! and apply them to the same input "simultaneously" by [`OperatorExt::facet`].
+//!   and apply them to the same input "simultaneously" by [`OperatorExt::facet`].
 //!
 //! - To handle [Time series] better, we introduced utils for ticked operators, which are defined in [`TickedOperatorExt`],
-//! of which the input and output are both [`Tickable`] and sharing the same [`Tick`].
-//! We provide many ticked version utils. For example, we can apply different [`TickedOperatorExt`]s
-//! (using [`TickedOperatorExt::facet_t`] or [`ticked::FacetMap`]) to the same [`Tickable`] stream to
-//! get a "synced" result stream of the combined outputs of those operators sharing the same [`Tick`]
-//! of theirs input. Each item of a "synced" stream will have the form of `TickValue<(O1, O2)>`.
+//!   of which the input and output are both [`Tickable`] and sharing the same [`Tick`].
+//!   We provide many ticked version utils. For example, we can apply different [`TickedOperatorExt`]s
+//!   (using [`TickedOperatorExt::facet_t`] or [`ticked::FacetMap`]) to the same [`Tickable`] stream to
+//!   get a "synced" result stream of the combined outputs of those operators sharing the same [`Tick`]
+//!   of theirs input. Each item of a "synced" stream will have the form of `TickValue<(O1, O2)>`.
 //!
 //! - [`TumblingOperator`]s are defined by some operations on events of non-overlapping time windows ([`TumblingWindow`]).
-//! [Moving average] is one of the famous examples, which is defined by the average of the numbers
-//! from those events occur in the same tumbling window. We call those operations [`TumblingOperation`]s.
-//! We can use [`tumbling`] function to create a [`TumblingOperator`] from a [`TumblingOperation`].
+//!   [Moving average] is one of the famous examples, which is defined by the average of the numbers
+//!   from those events occur in the same tumbling window. We call those operations [`TumblingOperation`]s.
+//!   We can use [`tumbling`] function to create a [`TumblingOperator`] from a [`TumblingOperation`].
 //!
 //! - Finally, we can apply the indicators to [`Iterator`]s or [`Stream`](futures::stream::Stream)s
-//! by using [`IndicatorIteratorExt::indicator`] or [`IndicatorStreamExt::indicator`] accordingly.
+//!   by using [`IndicatorIteratorExt::indicator`] or [`IndicatorStreamExt::indicator`] accordingly.
 //!
 //!
 //! [Time series]: https://en.wikipedia.org/wiki/Time_series
@@ -61,7 +61,6 @@
 #![deny(missing_docs)]
 #![cfg_attr(not(feature = "std"), no_std)]
 
-#[cfg(feature = "alloc")]
 extern crate alloc;
 
 /// Operator.
@@ -76,6 +75,30 @@ pub mod ticked;
 /// Iterator extension trait.
 pub mod iter;
 
+/// Zero-or-many output operators.
+pub mod flat;
+
+/// Fallible operators.
+pub mod try_operator;
+
+/// Warm-up and readiness.
+pub mod readiness;
+
+/// Reset and seed.
+pub mod reset;
+
+/// Event detectors.
+pub mod signal;
+
+/// Bar transforms.
+pub mod bar;
+
+/// Rolling statistics.
+pub mod rolling;
+
+/// Operator graphs.
+pub mod graph;
+
 /// Operator using GAT.
 #[cfg(feature = "gat")]
 pub mod gat;
@@ -100,14 +123,31 @@ pub mod reactive;
 #[cfg(feature = "context")]
 pub mod context;
 
+/// Snapshot and restore.
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
+
+/// Columnar batches.
+#[cfg(feature = "columnar")]
+pub mod columnar;
+
+/// Readers and writers of market data.
+#[cfg(feature = "io")]
+pub mod io;
+
+/// Declarative pipelines.
+#[cfg(feature = "pipeline")]
+pub mod pipeline;
+
 /// Prelude.
 pub mod prelude {
     #[cfg(feature = "context")]
     pub use crate::context::{
-        extractor::{Data, Env, In, Prev},
+        extractor::{Data, Env, History, In, Prev, PrevN},
         input, insert_and_output, insert_env_and_output,
         layer::{layer_fn, stack::id_layer, BoxLayer, Layer, LayerExt},
-        output, BoxContextOperator, ContextOperator, ContextOperatorExt, Value, ValueRef,
+        output, BoxContextOperator, ContextOperator, ContextOperatorExt, Entries, Key, Keyed,
+        Value, ValueRef,
     };
     #[cfg(feature = "gat")]
     pub use crate::gat::*;
@@ -120,8 +160,11 @@ pub mod prelude {
 #[cfg(feature = "indicator_macros")]
 pub use indicator_macros as macros;
 
+pub use flat::{FlatOperator, FlatOperatorExt};
 pub use iter::IndicatorIteratorExt;
 pub use operator::{facet, map, Operator, OperatorExt};
+pub use readiness::{Peek, Readiness};
+pub use reset::{Reset, Seed};
 pub use ticked::{
     facet_t, map_t,
     tumbling::{
@@ -130,6 +173,7 @@ pub use ticked::{
     },
     tuple_t, TickedOperatorExt,
 };
+pub use try_operator::{TryOperator, TryOperatorExt};
 pub use window::{Period, PeriodKind, Tick, TickValue, Tickable, TumblingWindow};
 
 #[cfg(feature = "std")]
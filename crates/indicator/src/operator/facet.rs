@@ -1,4 +1,12 @@
 use super::Operator;
+use crate::{
+    readiness::Readiness,
+    reset::{Reset, Seed},
+};
+use alloc::vec::Vec;
+
+#[cfg(feature = "snapshot")]
+use crate::snapshot::Snapshot;
 
 #[cfg(feature = "std")]
 pub use facet_map::{facet_map, FacetMap};
@@ -26,11 +34,87 @@ where
     fn next(&mut self, input: I) -> Self::Output {
         (self.0.next(input.clone()), self.1.next(input))
     }
+
+    /// Perform the batch on each operator in turn.
+    fn next_batch(&mut self, inputs: &[I], outputs: &mut Vec<Self::Output>) {
+        let mut o1 = Vec::with_capacity(inputs.len());
+        let mut o2 = Vec::with_capacity(inputs.len());
+        self.0.next_batch(inputs, &mut o1);
+        self.1.next_batch(inputs, &mut o2);
+        outputs.extend(o1.into_iter().zip(o2));
+    }
+
+    fn drain_batch(&mut self, inputs: &mut Vec<I>, outputs: &mut Vec<Self::Output>) {
+        self.next_batch(inputs, outputs);
+        inputs.clear();
+    }
+}
+
+impl<I, P1, P2> Readiness for Facet<I, P1, P2>
+where
+    P1: Readiness,
+    P2: Readiness,
+{
+    fn warm_up(&self) -> usize {
+        self.0.warm_up().max(self.1.warm_up())
+    }
+
+    fn is_ready(&self) -> bool {
+        self.0.is_ready() && self.1.is_ready()
+    }
+}
+
+impl<I, P1, P2> Reset for Facet<I, P1, P2>
+where
+    P1: Reset,
+    P2: Reset,
+{
+    fn reset(&mut self) {
+        self.0.reset();
+        self.1.reset();
+    }
+}
+
+impl<I, S1, S2, P1, P2> Seed<(S1, S2)> for Facet<I, P1, P2>
+where
+    P1: Seed<S1>,
+    P2: Seed<S2>,
+{
+    fn seed(&mut self, seed: (S1, S2)) {
+        self.0.seed(seed.0);
+        self.1.seed(seed.1);
+    }
+}
+
+#[cfg(feature = "snapshot")]
+impl<I, P1, P2> Snapshot for Facet<I, P1, P2>
+where
+    P1: Snapshot,
+    P2: Snapshot,
+{
+    type State = (P1::State, P2::State);
+
+    fn snapshot(&self) -> Self::State {
+        (self.0.snapshot(), self.1.snapshot())
+    }
+
+    fn restore(&mut self, state: Self::State) {
+        self.0.restore(state.0);
+        self.1.restore(state.1);
+    }
 }
 
 #[cfg(feature = "std")]
 mod facet_map {
     use super::Operator;
+    #[cfg(feature = "snapshot")]
+    use crate::snapshot::Snapshot;
+    use crate::{
+        readiness::Readiness,
+        reset::{Reset, Seed},
+    };
+    #[cfg(feature = "snapshot")]
+    use serde::{de::DeserializeOwned, Serialize};
     use std::collections::HashMap;
     use std::hash::Hash;
 
@@ -67,4 +151,67 @@ mod facet_map {
     {
         FacetMap(ops.into_iter().collect(), core::marker::PhantomData)
     }
+
+    impl<I, Q, P> Readiness for FacetMap<I, Q, P>
+    where
+        P: Readiness,
+    {
+        fn warm_up(&self) -> usize {
+            self.0.values().map(P::warm_up).max().unwrap_or(0)
+        }
+
+        fn is_ready(&self) -> bool {
+            self.0.values().all(P::is_ready)
+        }
+    }
+
+    impl<I, Q, P> Reset for FacetMap<I, Q, P>
+    where
+        P: Reset,
+    {
+        fn reset(&mut self) {
+            self.0.values_mut().for_each(P::reset);
+        }
+    }
+
+    /// Seed the operators of the keys in the seed, the other operators are reset.
+    impl<I, Q, S, P> Seed<HashMap<Q, S>> for FacetMap<I, Q, P>
+    where
+        Q: Eq + Hash,
+        P: Seed<S>,
+    {
+        fn seed(&mut self, seed: HashMap<Q, S>) {
+            self.reset();
+            for (k, s) in seed {
+                if let Some(p) = self.0.get_mut(&k) {
+                    p.seed(s);
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "snapshot")]
+    impl<I, Q, P> Snapshot for FacetMap<I, Q, P>
+    where
+        Q: Eq + Hash + Clone + Serialize + DeserializeOwned,
+        P: Snapshot,
+    {
+        type State = HashMap<Q, P::State>;
+
+        fn snapshot(&self) -> Self::State {
+            self.0
+                .iter()
+                .map(|(k, p)| (k.clone(), p.snapshot()))
+                .collect()
+        }
+
+        /// Restore the operators of the keys in the snapshot, the other operators are untouched.
+        fn restore(&mut self, state: Self::State) {
+            for (k, s) in state {
+                if let Some(p) = self.0.get_mut(&k) {
+                    p.restore(s);
+                }
+            }
+        }
+    }
 }
@@ -1,4 +1,12 @@
 use super::Operator;
+use crate::{
+    readiness::Readiness,
+    reset::{Reset, Seed},
+};
+use alloc::vec::Vec;
+
+#[cfg(feature = "snapshot")]
+use crate::snapshot::Snapshot;
 
 /// [`Map`] operator.
 #[derive(Debug, Clone, Copy)]
@@ -23,4 +31,42 @@ where
     fn next(&mut self, input: I) -> Self::Output {
         (self.f)(input)
     }
+
+    fn next_batch(&mut self, inputs: &[I], outputs: &mut Vec<Self::Output>)
+    where
+        I: Clone,
+    {
+        outputs.extend(inputs.iter().cloned().map(&mut self.f));
+    }
+
+    fn drain_batch(&mut self, inputs: &mut Vec<I>, outputs: &mut Vec<Self::Output>) {
+        outputs.extend(inputs.drain(..).map(&mut self.f));
+    }
+}
+
+impl<F> Readiness for Map<F> {
+    fn warm_up(&self) -> usize {
+        0
+    }
+
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
+
+impl<F> Reset for Map<F> {
+    fn reset(&mut self) {}
+}
+
+impl<F> Seed<()> for Map<F> {
+    fn seed(&mut self, _seed: ()) {}
+}
+
+#[cfg(feature = "snapshot")]
+impl<F> Snapshot for Map<F> {
+    type State = ();
+
+    fn snapshot(&self) -> Self::State {}
+
+    fn restore(&mut self, _state: Self::State) {}
 }
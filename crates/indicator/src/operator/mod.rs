@@ -11,10 +11,11 @@ pub use facet::{facet, Facet};
 pub use map::{map, Map};
 use then::Then;
 
+use alloc::{boxed::Box, vec::Vec};
 #[cfg(feature = "std")]
 pub use facet::{facet_map, FacetMap};
-extern crate alloc;
-use alloc::boxed::Box;
+
+use crate::flat::Flatten;
 
 #[cfg(feature = "async")]
 use crate::async_operator::Next;
@@ -32,9 +33,31 @@ pub trait Operator<I> {
 
     /// Produce the next output.
     fn next(&mut self, input: I) -> Self::Output;
+
+    /// Produce the outputs of a batch of inputs, appending them to `outputs`.
+    ///
+    /// It is equivalent to calling [`next`](Operator::next) on each input in order,
+    /// but it is called only once per batch through a [`BoxOperator`],
+    /// and some operators provide faster implementations.
+    fn next_batch(&mut self, inputs: &[I], outputs: &mut Vec<Self::Output>)
+    where
+        I: Clone,
+    {
+        outputs.reserve(inputs.len());
+        outputs.extend(inputs.iter().cloned().map(|input| self.next(input)));
+    }
+
+    /// Produce the outputs of a batch of owned inputs, appending them to `outputs`.
+    ///
+    /// The same as [`next_batch`](Operator::next_batch) but the inputs are moved out,
+    /// leaving `inputs` empty with its capacity, so that the buffer can be reused.
+    fn drain_batch(&mut self, inputs: &mut Vec<I>, outputs: &mut Vec<Self::Output>) {
+        outputs.reserve(inputs.len());
+        outputs.extend(inputs.drain(..).map(|input| self.next(input)));
+    }
 }
 
-impl<'a, I, P> Operator<I> for &'a mut P
+impl<I, P> Operator<I> for &mut P
 where
     P: Operator<I> + ?Sized,
 {
@@ -43,6 +66,17 @@ where
     fn next(&mut self, input: I) -> Self::Output {
         (*self).next(input)
     }
+
+    fn next_batch(&mut self, inputs: &[I], outputs: &mut Vec<Self::Output>)
+    where
+        I: Clone,
+    {
+        (*self).next_batch(inputs, outputs)
+    }
+
+    fn drain_batch(&mut self, inputs: &mut Vec<I>, outputs: &mut Vec<Self::Output>) {
+        (*self).drain_batch(inputs, outputs)
+    }
 }
 
 /// Operator extension trait.
@@ -94,6 +128,15 @@ pub trait OperatorExt<I>: Operator<I> {
         Box::new(self)
     }
 
+    /// Flatten the outputs, turning into a [`FlatOperator`](crate::FlatOperator).
+    fn flatten(self) -> Flatten<I, Self>
+    where
+        Self: Sized,
+        Self::Output: IntoIterator,
+    {
+        Flatten(self, core::marker::PhantomData)
+    }
+
     #[cfg(feature = "async")]
     /// Convert into a [`AsyncOperator`].
     fn into_async_operator(self) -> Next<Self>
@@ -115,4 +158,15 @@ where
     fn next(&mut self, input: I) -> Self::Output {
         self.as_mut().next(input)
     }
+
+    fn next_batch(&mut self, inputs: &[I], outputs: &mut Vec<Self::Output>)
+    where
+        I: Clone,
+    {
+        self.as_mut().next_batch(inputs, outputs)
+    }
+
+    fn drain_batch(&mut self, inputs: &mut Vec<I>, outputs: &mut Vec<Self::Output>) {
+        self.as_mut().drain_batch(inputs, outputs)
+    }
 }
@@ -1,4 +1,12 @@
 use super::Operator;
+use crate::{
+    readiness::Readiness,
+    reset::{Reset, Seed},
+};
+use alloc::vec::Vec;
+
+#[cfg(feature = "snapshot")]
+use crate::snapshot::Snapshot;
 
 /// [`Then`] combinator.
 #[derive(Debug, Clone, Copy)]
@@ -18,4 +26,78 @@ where
     fn next(&mut self, input: I) -> Self::Output {
         self.1.next(self.0.next(input))
     }
+
+    /// Perform the batch stage by stage.
+    fn next_batch(&mut self, inputs: &[I], outputs: &mut Vec<Self::Output>)
+    where
+        I: Clone,
+    {
+        let mut intermediate = Vec::with_capacity(inputs.len());
+        self.0.next_batch(inputs, &mut intermediate);
+        self.1.drain_batch(&mut intermediate, outputs);
+    }
+
+    /// Perform the batch stage by stage.
+    fn drain_batch(&mut self, inputs: &mut Vec<I>, outputs: &mut Vec<Self::Output>) {
+        let mut intermediate = Vec::with_capacity(inputs.len());
+        self.0.drain_batch(inputs, &mut intermediate);
+        self.1.drain_batch(&mut intermediate, outputs);
+    }
+}
+
+/// The stages are fed together from the first input, so the second one
+/// warms up on the outputs of the first one, whether they are ready or not.
+/// The combinator is ready once both stages are, after the larger warm-up.
+impl<I, P1, P2> Readiness for Then<I, P1, P2>
+where
+    P1: Readiness,
+    P2: Readiness,
+{
+    fn warm_up(&self) -> usize {
+        self.0.warm_up().max(self.1.warm_up())
+    }
+
+    fn is_ready(&self) -> bool {
+        self.0.is_ready() && self.1.is_ready()
+    }
+}
+
+impl<I, P1, P2> Reset for Then<I, P1, P2>
+where
+    P1: Reset,
+    P2: Reset,
+{
+    fn reset(&mut self) {
+        self.0.reset();
+        self.1.reset();
+    }
+}
+
+impl<I, S1, S2, P1, P2> Seed<(S1, S2)> for Then<I, P1, P2>
+where
+    P1: Seed<S1>,
+    P2: Seed<S2>,
+{
+    fn seed(&mut self, seed: (S1, S2)) {
+        self.0.seed(seed.0);
+        self.1.seed(seed.1);
+    }
+}
+
+#[cfg(feature = "snapshot")]
+impl<I, P1, P2> Snapshot for Then<I, P1, P2>
+where
+    P1: Snapshot,
+    P2: Snapshot,
+{
+    type State = (P1::State, P2::State);
+
+    fn snapshot(&self) -> Self::State {
+        (self.0.snapshot(), self.1.snapshot())
+    }
+
+    fn restore(&mut self, state: Self::State) {
+        self.0.restore(state.0);
+        self.1.restore(state.1);
+    }
 }
@@ -0,0 +1,44 @@
+use alloc::{string::String, vec::Vec};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::PeriodKind;
+
+/// Parameters of a node, parsed by its constructor.
+pub type Params = Map<String, Value>;
+
+/// A node of a pipeline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeConfig {
+    /// The name of the node, used to refer to its output.
+    pub name: String,
+    /// The type of the node in the registry.
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// The inputs, names of fields or previous nodes.
+    #[serde(default)]
+    pub inputs: Vec<String>,
+    /// Parameters.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub params: Params,
+    /// Evaluate the node over the windows of the period (e.g. `"1h"`) instead of every input.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub period: Option<PeriodKind>,
+}
+
+impl NodeConfig {
+    /// Parse the parameters.
+    pub fn params<P: DeserializeOwned>(&self) -> Result<P, serde_json::Error> {
+        serde_json::from_value(Value::Object(self.params.clone()))
+    }
+}
+
+/// A pipeline: a list of nodes, each of them may use the outputs of the previous ones,
+/// and the names of the outputs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PipelineConfig {
+    /// Nodes.
+    pub nodes: Vec<NodeConfig>,
+    /// Outputs, names of nodes or fields.
+    pub outputs: Vec<String>,
+}
@@ -0,0 +1,487 @@
+use super::{
+    node,
+    operator::{describe, Builder, Ref},
+    Error, Fields, NodeConfig, Params, Registry,
+};
+use crate::{operator::BoxOperator, OperatorExt, PeriodKind, TickValue};
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{
+    ops::{Add, Div, Mul, Sub},
+    str::FromStr,
+};
+
+/// Expression syntax tree.
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Number(String),
+    Ident(String),
+    Call(String, Vec<Expr>),
+    Neg(Box<Expr>),
+    Binary(char, Box<Expr>, Box<Expr>),
+    At(Box<Expr>, PeriodKind),
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: impl ToString) -> Error {
+        Error::Syntax {
+            position: self.pos,
+            message: message.to_string(),
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.source[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.rest().chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), Error> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(alloc::format!("expected `{c}`")))
+        }
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let len = rest.find(|c| !f(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    fn parse(mut self) -> Result<Expr, Error> {
+        let expr = self.expr()?;
+        match self.peek() {
+            None => Ok(expr),
+            Some(c) => Err(self.error(alloc::format!("unexpected `{c}`"))),
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, Error> {
+        let mut lhs = self.term()?;
+        while let Some(op @ ('+' | '-')) = self.peek() {
+            self.pos += 1;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(self.term()?));
+        }
+        Ok(lhs)
+    }
+
+    fn term(&mut self) -> Result<Expr, Error> {
+        let mut lhs = self.unary()?;
+        while let Some(op @ ('*' | '/')) = self.peek() {
+            self.pos += 1;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(self.unary()?));
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Expr, Error> {
+        if self.eat('-') {
+            return Ok(Expr::Neg(Box::new(self.unary()?)));
+        }
+        let mut expr = self.primary()?;
+        while self.eat('@') {
+            self.skip_whitespace();
+            let period = self.take_while(|c| c.is_ascii_alphanumeric() || c == '.');
+            let kind = serde_json::from_value(period.into())
+                .map_err(|_| self.error(alloc::format!("invalid period `{period}`")))?;
+            expr = Expr::At(Box::new(expr), kind);
+        }
+        Ok(expr)
+    }
+
+    fn primary(&mut self) -> Result<Expr, Error> {
+        match self.peek() {
+            Some('(') => {
+                self.pos += 1;
+                let expr = self.expr()?;
+                self.expect(')')?;
+                Ok(expr)
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => {
+                let number = self.take_while(|c| c.is_ascii_digit() || c == '.');
+                Ok(Expr::Number(number.into()))
+            }
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                let name = self.take_while(|c| c.is_alphanumeric() || c == '_');
+                if !self.eat('(') {
+                    return Ok(Expr::Ident(name.into()));
+                }
+                let mut args = Vec::new();
+                if !self.eat(')') {
+                    loop {
+                        args.push(self.expr()?);
+                        if self.eat(')') {
+                            break;
+                        }
+                        self.expect(',')?;
+                    }
+                }
+                Ok(Expr::Call(name.into(), args))
+            }
+            Some(c) => Err(self.error(alloc::format!("unexpected `{c}`"))),
+            None => Err(self.error("unexpected end of expression")),
+        }
+    }
+}
+
+/// A compiled value: its reference, its name and its period.
+struct Operand<T> {
+    value: Ref<T>,
+    name: String,
+    period: Option<PeriodKind>,
+}
+
+struct Compiler<'a, I, T> {
+    registry: &'a Registry<T>,
+    builder: Builder<I, T>,
+}
+
+fn merge(
+    name: &str,
+    lhs: Option<PeriodKind>,
+    rhs: Option<PeriodKind>,
+) -> Result<Option<PeriodKind>, Error> {
+    match (lhs, rhs) {
+        (Some(lhs), Some(rhs)) if lhs != rhs => Err(Error::Invalid {
+            node: name.into(),
+            message: "inputs of different periods".into(),
+        }),
+        (lhs, rhs) => Ok(lhs.or(rhs)),
+    }
+}
+
+impl<'a, I, T> Compiler<'a, I, T>
+where
+    I: Fields<T> + 'static,
+    T: Copy
+        + Default
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + FromStr
+        + Send
+        + 'static,
+{
+    /// Add a node unless an identical one exists.
+    fn node(
+        &mut self,
+        name: &str,
+        period: Option<PeriodKind>,
+        inputs: Vec<Ref<T>>,
+        build: impl FnOnce() -> Result<node::BoxNode<T>, Error>,
+    ) -> Result<Operand<T>, Error> {
+        Ok(Operand {
+            value: self.builder.node(name, inputs, build)?,
+            name: name.into(),
+            period,
+        })
+    }
+
+    /// Compile the expression, with `parent` the name of the node using it
+    /// (`None` for the output).
+    fn compile(&mut self, expr: &Expr, parent: Option<&str>) -> Result<Operand<T>, Error> {
+        match expr {
+            Expr::Number(number) => {
+                let value: T = number.parse().map_err(|_| Error::Invalid {
+                    node: number.clone(),
+                    message: "invalid number".into(),
+                })?;
+                self.node(number, None, Vec::new(), || {
+                    Ok(node::function(move |_: &[T]| value))
+                })
+            }
+            Expr::Ident(name) => {
+                let idx = I::names()
+                    .iter()
+                    .position(|field| field == name)
+                    .ok_or_else(|| match parent {
+                        Some(node) => Error::UnknownInput {
+                            node: node.into(),
+                            input: name.clone(),
+                        },
+                        None => Error::UnknownOutput(name.clone()),
+                    })?;
+                Ok(Operand {
+                    value: Ref::Field(idx),
+                    name: name.clone(),
+                    period: None,
+                })
+            }
+            Expr::At(expr, period) => {
+                let operand = self.compile(expr, parent)?;
+                let period = merge("@", Some(*period), operand.period)?;
+                Ok(Operand { period, ..operand })
+            }
+            Expr::Neg(expr) => {
+                let operand = self.compile(expr, Some("neg"))?;
+                let inputs = alloc::vec![operand.value];
+                self.node("neg", operand.period, inputs, || {
+                    Ok(node::function(|x: &[T]| T::default() - x[0]))
+                })
+            }
+            Expr::Binary(op, lhs, rhs) => {
+                let name = op.to_string();
+                let lhs = self.compile(lhs, Some(&name))?;
+                let rhs = self.compile(rhs, Some(&name))?;
+                let period = merge(&name, lhs.period, rhs.period)?;
+                let inputs = alloc::vec![lhs.value, rhs.value];
+                let op = *op;
+                self.node(&name, period, inputs, || {
+                    Ok(match op {
+                        '+' => node::function(|x: &[T]| x[0] + x[1]),
+                        '-' => node::function(|x: &[T]| x[0] - x[1]),
+                        '*' => node::function(|x: &[T]| x[0] * x[1]),
+                        _ => node::function(|x: &[T]| x[0] / x[1]),
+                    })
+                })
+            }
+            Expr::Call(name, args) => self.call(name, args),
+        }
+    }
+
+    /// Compile a call: the leading arguments are the inputs of the node,
+    /// and the rest are the values of its parameters in order.
+    fn call(&mut self, name: &str, args: &[Expr]) -> Result<Operand<T>, Error> {
+        let schema = self
+            .registry
+            .schema(name)
+            .ok_or_else(|| Error::UnknownType(name.into()))?;
+        let invalid = |message: String| Error::Invalid {
+            node: name.into(),
+            message,
+        };
+        if args.len() < schema.inputs || args.len() > schema.inputs + schema.params.len() {
+            return Err(invalid(alloc::format!(
+                "expected {} inputs and at most {} parameters",
+                schema.inputs,
+                schema.params.len()
+            )));
+        }
+        let (inputs, values) = args.split_at(schema.inputs);
+        let mut params = Params::new();
+        for (param, value) in schema.params.iter().zip(values) {
+            let value = match value {
+                Expr::Number(number) => serde_json::from_str(number)
+                    .map_err(|_| invalid(alloc::format!("invalid number `{number}`")))?,
+                Expr::Ident(ident) => serde_json::Value::String(ident.clone()),
+                _ => {
+                    return Err(invalid(alloc::format!(
+                        "parameter `{}` must be a literal",
+                        param.name
+                    )))
+                }
+            };
+            params.insert(param.name.clone(), value);
+        }
+        let mut period = None;
+        let mut names = Vec::with_capacity(inputs.len());
+        let mut refs = Vec::with_capacity(inputs.len());
+        for input in inputs {
+            let operand = self.compile(input, Some(name))?;
+            period = merge(name, period, operand.period)?;
+            names.push(operand.name);
+            refs.push(operand.value);
+        }
+        let key = describe(name, &params, period);
+        let config = NodeConfig {
+            name: name.into(),
+            kind: name.into(),
+            inputs: names,
+            params,
+            period,
+        };
+        let registry = self.registry;
+        // The period is applied to the node.
+        self.node(&key, None, refs, || registry.construct(&config))
+    }
+}
+
+/// Compile an expression over the fields of `I` into an operator,
+/// with the node types of the registry as functions.
+///
+/// The syntax supports:
+/// - numbers, fields (e.g. `close`) and parentheses;
+/// - `+`, `-`, `*`, `/` and unary `-`;
+/// - calls of node types, e.g. `sma(close, 12)`, where the leading arguments are the inputs
+///   and the rest are the values of the parameters in the order of the schema;
+/// - period qualifiers, e.g. `sma(close@1h, 12)`, which evaluate the nodes using the
+///   qualified values over the windows of the period.
+///
+/// Identical subexpressions are evaluated only once.
+/// The output is `None` while it is not available (e.g. during warm-up).
+pub fn compile<I, T>(
+    source: &str,
+    registry: &Registry<T>,
+) -> Result<BoxOperator<'static, TickValue<I>, TickValue<Option<T>>>, Error>
+where
+    I: Fields<T> + 'static,
+    T: Copy
+        + Default
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + FromStr
+        + Send
+        + 'static,
+{
+    let expr = Parser { source, pos: 0 }.parse()?;
+    let mut compiler = Compiler::<I, T> {
+        registry,
+        builder: Builder::new(),
+    };
+    let output = compiler.compile(&expr, None)?;
+    let pipeline = compiler.builder.build(&[output.value])?;
+    Ok(pipeline
+        .map(|output: TickValue<Vec<Option<T>>>| output.map(|values| values[0]))
+        .boxed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bar::Ohlc, rolling::ema, IndicatorIteratorExt, Operator};
+    use time::{macros::datetime, Duration};
+
+    fn parse(source: &str) -> Result<Expr, Error> {
+        Parser { source, pos: 0 }.parse()
+    }
+
+    #[test]
+    fn syntax() {
+        let expr = parse("(high + low) / 2").unwrap();
+        assert!(matches!(expr, Expr::Binary('/', _, _)));
+        let expr = parse("-sma(close@1h, 3) * 2").unwrap();
+        assert!(matches!(expr, Expr::Binary('*', _, _)));
+        for source in ["sma(close", "1 +", "close @ 1x", "a b"] {
+            assert!(
+                matches!(parse(source), Err(Error::Syntax { .. })),
+                "{source}"
+            );
+        }
+    }
+
+    #[test]
+    fn evaluate() {
+        let registry = Registry::<f64>::builtin();
+        let bars = [(3.0, 1.0), (5.0, 3.0), (4.0, 2.0)]
+            .into_iter()
+            .map(|(high, low)| {
+                TickValue::big_bang(Ohlc {
+                    open: low,
+                    high,
+                    low,
+                    close: high,
+                })
+            });
+        let op =
+            compile::<Ohlc<f64>, f64>("sma((high + low) / 2, 2) - sma(close, 2) + -1", &registry)
+                .unwrap();
+        let outputs = bars.indicator(op).map(|x| x.value);
+        assert!(outputs.eq([None, Some(-2.0), Some(-2.0)]));
+    }
+
+    #[test]
+    fn macd() {
+        let registry = Registry::<f64>::builtin();
+        let op = compile::<Ohlc<f64>, f64>("ema(close, 12) - ema(close, 26)", &registry).unwrap();
+        let closes = (0..30).map(|x| x as f64).collect::<Vec<_>>();
+        let (mut fast, mut slow) = (ema(12), ema(26));
+        let expected = closes
+            .iter()
+            .enumerate()
+            .map(|(idx, x)| {
+                let macd = fast.next(*x) - slow.next(*x);
+                (idx >= 25).then_some(macd)
+            })
+            .collect::<Vec<_>>();
+        let outputs = closes
+            .iter()
+            .map(|x| {
+                TickValue::big_bang(Ohlc {
+                    open: *x,
+                    high: *x,
+                    low: *x,
+                    close: *x,
+                })
+            })
+            .indicator(op)
+            .map(|x| x.value)
+            .collect::<Vec<_>>();
+        assert_eq!(outputs, expected);
+    }
+
+    #[test]
+    fn shared() {
+        let registry = Registry::<f64>::builtin();
+        let mut compiler = Compiler::<f64, f64> {
+            registry: &registry,
+            builder: Builder::new(),
+        };
+        let expr =
+            parse("sma(value, 2) - sma(value, 3) + sma(value, 2) * sma(value@1m, 2)").unwrap();
+        compiler.compile(&expr, None).unwrap();
+        // `sma(value, 2)`, `sma(value, 3)`, `-`, `sma(value@1m, 2)`, `*`, `+`.
+        assert_eq!(compiler.builder.len(), 6);
+    }
+
+    #[test]
+    fn periods() {
+        let registry = Registry::<f64>::builtin();
+        let op = compile::<f64, f64>("sum(value@1m, 2)", &registry).unwrap();
+        let base = datetime!(2023-01-01 00:00:00 UTC);
+        let outputs = [1.0, 2.0, 3.0]
+            .into_iter()
+            .enumerate()
+            .map(|(idx, x)| TickValue::new(base + Duration::seconds(30 * idx as i64), x))
+            .indicator(op)
+            .map(|x| x.value);
+        assert!(outputs.eq([None, None, Some(5.0)]));
+    }
+
+    #[test]
+    fn unknown() {
+        let registry = Registry::<f64>::builtin();
+        let Err(err) = compile::<Ohlc<f64>, f64>("sma(volume, 2)", &registry) else {
+            panic!("`volume` is not a field");
+        };
+        assert_eq!(err.to_string(), "unknown input `volume` of node `sma`");
+        let Err(err) = compile::<Ohlc<f64>, f64>("close - -volume", &registry) else {
+            panic!("`volume` is not a field");
+        };
+        assert_eq!(err.to_string(), "unknown input `volume` of node `neg`");
+        let Err(err) = compile::<Ohlc<f64>, f64>("volume", &registry) else {
+            panic!("`volume` is not a field");
+        };
+        assert_eq!(err.to_string(), "unknown output `volume`");
+    }
+}
@@ -0,0 +1,105 @@
+use alloc::string::String;
+use thiserror::Error;
+
+use crate::bar::Ohlc;
+
+/// Serde representation of pipelines.
+pub mod config;
+
+/// Nodes of pipelines.
+pub mod node;
+
+/// Schemas of node types.
+pub mod schema;
+
+/// Registry of node types.
+pub mod registry;
+
+/// Pipeline operator.
+pub mod operator;
+
+/// Expression language.
+pub mod expr;
+
+pub use config::{NodeConfig, Params, PipelineConfig};
+pub use expr::compile;
+pub use node::{BoxNode, Node};
+pub use operator::Pipeline;
+pub use registry::Registry;
+pub use schema::{NodeSchema, ParamSchema, ParamType};
+
+/// Errors of building pipelines.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// The node type is not registered.
+    #[error("unknown node type `{0}`")]
+    UnknownType(String),
+    /// The input is neither a field nor a previous node.
+    #[error("unknown input `{input}` of node `{node}`")]
+    UnknownInput {
+        /// Node.
+        node: String,
+        /// Input.
+        input: String,
+    },
+    /// The name is already used by a field or another node.
+    #[error("duplicate name `{0}`")]
+    DuplicateName(String),
+    /// The output is not a node or a field.
+    #[error("unknown output `{0}`")]
+    UnknownOutput(String),
+    /// Syntax error of an expression.
+    #[error("syntax error at {position}: {message}")]
+    Syntax {
+        /// Byte position in the source.
+        position: usize,
+        /// Message.
+        message: String,
+    },
+    /// Invalid parameters or inputs.
+    #[error("invalid node `{node}`: {message}")]
+    Invalid {
+        /// Node.
+        node: String,
+        /// Message.
+        message: String,
+    },
+    /// Invalid graph of the nodes.
+    #[error(transparent)]
+    Graph(#[from] crate::graph::Error),
+}
+
+/// Input with named fields, which are the sources of a pipeline.
+pub trait Fields<T> {
+    /// The names of the fields.
+    fn names() -> &'static [&'static str];
+
+    /// Get the field at `idx` (of [`Fields::names`]).
+    fn get(&self, idx: usize) -> T;
+}
+
+/// A single value is the field `value`.
+impl<T: Copy> Fields<T> for T {
+    fn names() -> &'static [&'static str] {
+        &["value"]
+    }
+
+    fn get(&self, _idx: usize) -> T {
+        *self
+    }
+}
+
+impl<T: Copy> Fields<T> for Ohlc<T> {
+    fn names() -> &'static [&'static str] {
+        &["open", "high", "low", "close"]
+    }
+
+    fn get(&self, idx: usize) -> T {
+        match idx {
+            0 => self.open,
+            1 => self.high,
+            2 => self.low,
+            _ => self.close,
+        }
+    }
+}
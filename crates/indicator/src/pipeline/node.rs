@@ -0,0 +1,171 @@
+use alloc::{boxed::Box, string::ToString, vec::Vec};
+
+use super::{Error, NodeConfig};
+use crate::{Operator, Peek, Period, Readiness, Tick, TumblingWindow};
+
+/// Node of a pipeline: an operator over the values of its inputs.
+pub trait Node<T>: Send {
+    /// Evaluate the node with the values of its inputs at `tick`,
+    /// `None` if the output is not available (e.g. during warm-up).
+    fn next(&mut self, tick: Tick, inputs: &[T]) -> Option<T>;
+
+    /// Evaluate the node as [`next`](Node::next) would, without advancing it.
+    ///
+    /// Only the nodes that support it can be evaluated over periods.
+    fn peek(&self, _tick: Tick, _inputs: &[T]) -> Option<T> {
+        None
+    }
+
+    /// Whether the node supports [`peek`](Node::peek).
+    fn is_peekable(&self) -> bool {
+        false
+    }
+}
+
+/// Boxed [`Node`].
+pub type BoxNode<T> = Box<dyn Node<T>>;
+
+/// Node of an operator with one input.
+///
+/// Created by [`unary`].
+#[derive(Debug, Clone)]
+pub struct Unary<P>(P);
+
+impl<T, P> Node<T> for Unary<P>
+where
+    T: Copy + 'static,
+    P: Peek<T, Output = T> + Readiness + Send + 'static,
+{
+    fn next(&mut self, _tick: Tick, inputs: &[T]) -> Option<T> {
+        let output = self.0.next(inputs[0]);
+        self.0.is_ready().then_some(output)
+    }
+
+    fn peek(&self, _tick: Tick, inputs: &[T]) -> Option<T> {
+        self.0.peek(inputs[0]).ready()
+    }
+
+    fn is_peekable(&self) -> bool {
+        true
+    }
+}
+
+/// Create a node from an operator with one input,
+/// of which the outputs are available once it is ready.
+pub fn unary<T, P>(op: P) -> BoxNode<T>
+where
+    T: Copy + 'static,
+    P: Peek<T, Output = T> + Readiness + Send + 'static,
+{
+    Box::new(Unary(op))
+}
+
+/// Node of a stateless function of the inputs.
+///
+/// Created by [`function`].
+#[derive(Debug, Clone)]
+pub struct Function<F>(F);
+
+impl<T, F> Node<T> for Function<F>
+where
+    F: Fn(&[T]) -> T + Send + 'static,
+{
+    fn next(&mut self, _tick: Tick, inputs: &[T]) -> Option<T> {
+        Some((self.0)(inputs))
+    }
+
+    fn peek(&self, _tick: Tick, inputs: &[T]) -> Option<T> {
+        Some((self.0)(inputs))
+    }
+
+    fn is_peekable(&self) -> bool {
+        true
+    }
+}
+
+/// Create a node from a stateless function of the inputs.
+pub fn function<T, F>(f: F) -> BoxNode<T>
+where
+    F: Fn(&[T]) -> T + Send + 'static,
+{
+    Box::new(Function(f))
+}
+
+/// Node of an operator with one input that cannot be peeked,
+/// such as the ones built from context operators.
+///
+/// Created by [`operator`].
+#[derive(Debug)]
+pub struct Op<P>(P);
+
+impl<T, P> Node<T> for Op<P>
+where
+    T: Copy,
+    P: Operator<T, Output = Option<T>> + Send,
+{
+    fn next(&mut self, _tick: Tick, inputs: &[T]) -> Option<T> {
+        self.0.next(inputs[0])
+    }
+}
+
+/// Create a node from an operator with one input,
+/// of which the output is `None` if it is not available.
+pub fn operator<T, P>(op: P) -> BoxNode<T>
+where
+    T: Copy + 'static,
+    P: Operator<T, Output = Option<T>> + Send + 'static,
+{
+    Box::new(Op(op))
+}
+
+/// Node evaluated over the windows of a period.
+///
+/// The inner node only advances once a window is confirmed,
+/// with the last inputs of the window.
+/// The output for the current window is evaluated by peeking the inner node.
+pub struct Resampled<T> {
+    period: Period,
+    node: BoxNode<T>,
+    current: Option<(Tick, Vec<T>)>,
+}
+
+impl<T> Resampled<T> {
+    /// Create a node evaluating `node` over the windows of `period`,
+    /// `None` if the node cannot be peeked.
+    pub fn new(period: Period, node: BoxNode<T>) -> Option<Self> {
+        if !node.is_peekable() {
+            return None;
+        }
+        Some(Self {
+            period,
+            node,
+            current: None,
+        })
+    }
+}
+
+impl<T: Copy + Send + 'static> Node<T> for Resampled<T> {
+    fn next(&mut self, tick: Tick, inputs: &[T]) -> Option<T> {
+        match &mut self.current {
+            Some((last, current)) if self.period.same_window(last, &tick) => {
+                *last = tick;
+                current.clear();
+                current.extend_from_slice(inputs);
+            }
+            current => {
+                if let Some((last, confirmed)) = current.replace((tick, inputs.to_vec())) {
+                    self.node.next(last, &confirmed);
+                }
+            }
+        }
+        self.node.peek(tick, inputs)
+    }
+}
+
+/// Parse the (validated) parameters of the node.
+pub fn params<P: serde::de::DeserializeOwned>(config: &NodeConfig) -> Result<P, Error> {
+    config.params().map_err(|err| Error::Invalid {
+        node: config.name.clone(),
+        message: err.to_string(),
+    })
+}
@@ -0,0 +1,278 @@
+use alloc::{format, string::String, vec::Vec};
+use core::marker::PhantomData;
+
+use super::{node, BoxNode, Error, Fields, Params, PipelineConfig, Registry};
+use crate::{
+    graph::{graph, Evaluate, Graph, GraphBuilder, Handle, Inputs, Values},
+    Operator, PeriodKind, TickValue,
+};
+
+/// Reference to a value in a pipeline.
+#[derive(Debug, Clone, Copy)]
+pub(super) enum Ref<T> {
+    Field(usize),
+    Node(Handle<Option<T>>),
+}
+
+/// Node of a pipeline in the graph, of which the output is `None` if it is not available.
+struct Slot<T> {
+    node: BoxNode<T>,
+    inputs: Vec<Ref<T>>,
+    buffer: Vec<T>,
+}
+
+impl<I, T> Evaluate<TickValue<I>> for Slot<T>
+where
+    I: Fields<T> + 'static,
+    T: Copy + Send + 'static,
+{
+    type Output = Option<T>;
+
+    fn evaluate(&mut self, input: &TickValue<I>, values: &Values) -> Self::Output {
+        self.buffer.clear();
+        for r in self.inputs.iter() {
+            let value = match r {
+                Ref::Field(idx) => input.value.get(*idx),
+                Ref::Node(handle) => (*handle.get(input, values))?,
+            };
+            self.buffer.push(value);
+        }
+        self.node.next(input.tick, &self.buffer)
+    }
+}
+
+/// Describe a node by its type, parameters and period.
+pub(super) fn describe(kind: &str, params: &Params, period: Option<PeriodKind>) -> String {
+    let mut key = format!("{kind}{}", serde_json::Value::Object(params.clone()));
+    if let Some(period) = period {
+        key.push_str(&format!("@{period}"));
+    }
+    key
+}
+
+/// Builder of the nodes of a pipeline on a [`GraphBuilder`],
+/// where the identical nodes are added only once.
+pub(super) struct Builder<I, T> {
+    graph: GraphBuilder<TickValue<I>>,
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<I, T> Builder<I, T>
+where
+    I: Fields<T> + 'static,
+    T: Copy + Send + 'static,
+{
+    pub(super) fn new() -> Self {
+        Self {
+            graph: graph(),
+            _value: PhantomData,
+        }
+    }
+
+    /// The number of nodes.
+    #[cfg(test)]
+    pub(super) fn len(&self) -> usize {
+        self.graph.len()
+    }
+
+    fn slot(
+        &mut self,
+        key: &str,
+        inputs: Vec<Ref<T>>,
+        build: impl FnOnce() -> Result<BoxNode<T>, Error>,
+    ) -> Result<Handle<Option<T>>, Error> {
+        // The fields are identified by the key, and the nodes by their handles.
+        let mut nodes = Vec::new();
+        let fields = inputs
+            .iter()
+            .map(|input| match input {
+                Ref::Field(idx) => I::names()[*idx],
+                Ref::Node(handle) => {
+                    nodes.push(*handle);
+                    "#"
+                }
+            })
+            .collect::<Vec<_>>();
+        let key = format!("{key}({})", fields.join(","));
+        let handles = Inputs::<TickValue<I>>::handles(&nodes);
+        self.graph.evaluate(key, handles, || {
+            Ok::<_, Error>(Slot {
+                node: build()?,
+                inputs,
+                buffer: Vec::new(),
+            })
+        })
+    }
+
+    /// Add the node of the inputs built by `build`, unless an identical one exists.
+    ///
+    /// The `key` should identify the node with its parameters and period, see [`describe`].
+    pub(super) fn node(
+        &mut self,
+        key: &str,
+        inputs: Vec<Ref<T>>,
+        build: impl FnOnce() -> Result<BoxNode<T>, Error>,
+    ) -> Result<Ref<T>, Error> {
+        self.slot(key, inputs, build).map(Ref::Node)
+    }
+
+    /// Build the pipeline producing the values of `outputs`.
+    pub(super) fn build(mut self, outputs: &[Ref<T>]) -> Result<Pipeline<I, T>, Error> {
+        let outputs = outputs
+            .iter()
+            .map(|output| match output {
+                Ref::Node(handle) => Ok(*handle),
+                // The fields are output through the nodes of themselves.
+                Ref::Field(_) => self.slot("field", alloc::vec![*output], || {
+                    Ok(node::function(|x: &[T]| x[0]))
+                }),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Pipeline(self.graph.build(outputs)?))
+    }
+}
+
+/// Operator evaluating the nodes of a pipeline in order.
+///
+/// The output is the values of the outputs of the pipeline,
+/// `None` if it is not available.
+/// A node is not evaluated if any of its inputs is not available,
+/// and the identical nodes are evaluated only once.
+///
+/// Created by [`Registry::build`].
+pub struct Pipeline<I, T>(Graph<TickValue<I>, Vec<Handle<Option<T>>>>);
+
+impl<I, T> core::fmt::Debug for Pipeline<I, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Pipeline").field(&self.0).finish()
+    }
+}
+
+impl<I, T> Pipeline<I, T>
+where
+    I: Fields<T> + 'static,
+    T: Copy + Send + 'static,
+{
+    pub(super) fn new(registry: &Registry<T>, config: &PipelineConfig) -> Result<Self, Error> {
+        let mut builder = Builder::<I, T>::new();
+        let mut names: Vec<(&str, Ref<T>)> = I::names()
+            .iter()
+            .enumerate()
+            .map(|(idx, name)| (*name, Ref::Field(idx)))
+            .collect();
+        let resolve = |names: &[(&str, Ref<T>)], name: &str| {
+            names
+                .iter()
+                .find(|(candidate, _)| *candidate == name)
+                .map(|(_, r)| *r)
+        };
+        for node in config.nodes.iter() {
+            if resolve(&names, &node.name).is_some() {
+                return Err(Error::DuplicateName(node.name.clone()));
+            }
+            let inputs = node
+                .inputs
+                .iter()
+                .map(|input| {
+                    resolve(&names, input).ok_or_else(|| Error::UnknownInput {
+                        node: node.name.clone(),
+                        input: input.clone(),
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let key = describe(&node.kind, &node.params, node.period);
+            let value = builder.node(&key, inputs, || registry.construct(node))?;
+            names.push((&node.name, value));
+        }
+        let outputs = config
+            .outputs
+            .iter()
+            .map(|output| {
+                resolve(&names, output).ok_or_else(|| Error::UnknownOutput(String::from(output)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        builder.build(&outputs)
+    }
+}
+
+impl<I, T> Operator<TickValue<I>> for Pipeline<I, T>
+where
+    I: 'static,
+    T: Copy + 'static,
+{
+    type Output = TickValue<Vec<Option<T>>>;
+
+    fn next(&mut self, input: TickValue<I>) -> Self::Output {
+        TickValue {
+            tick: input.tick,
+            value: self.0.next(input),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bar::Ohlc, IndicatorIteratorExt, OperatorExt};
+    use time::{macros::datetime, Duration};
+
+    #[test]
+    fn build() {
+        let config: PipelineConfig = serde_json::from_value(serde_json::json!({
+            "nodes": [
+                {"name": "fast", "type": "sma", "inputs": ["close"], "params": {"length": 2}},
+                {"name": "slow", "type": "sma", "inputs": ["close"], "params": {"length": 3}},
+                {"name": "diff", "type": "sub", "inputs": ["fast", "slow"]},
+                {"name": "hourly", "type": "sum", "inputs": ["close"], "params": {"length": 2}, "period": "1h"},
+            ],
+            "outputs": ["diff", "hourly", "high"],
+        }))
+        .unwrap();
+        let op = Registry::<f64>::builtin()
+            .build::<Ohlc<f64>>(&config)
+            .unwrap()
+            .boxed();
+        let base = datetime!(2023-01-01 00:00:00 UTC);
+        let outputs = [1.0, 2.0, 6.0, -2.0]
+            .into_iter()
+            .enumerate()
+            .map(|(idx, x)| {
+                TickValue::new(base + Duration::minutes(30 * idx as i64), Ohlc::flat(x))
+            })
+            .indicator(op)
+            .map(|x| x.value);
+        let expected = [
+            [None, None, Some(1.0)],
+            [None, None, Some(2.0)],
+            [Some(1.0), Some(8.0), Some(6.0)],
+            [Some(0.0), Some(0.0), Some(-2.0)],
+        ];
+        for (output, expected) in outputs.zip(expected) {
+            assert_eq!(output, expected);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let registry = Registry::<f64>::builtin();
+        let build = |json| {
+            let config: PipelineConfig = serde_json::from_value(json).unwrap();
+            registry.build::<f64>(&config).err().unwrap()
+        };
+        let err = build(serde_json::json!({
+            "nodes": [{"name": "x", "type": "rsi", "inputs": ["value"]}],
+            "outputs": [],
+        }));
+        assert!(matches!(err, Error::UnknownType(_)));
+        let err = build(serde_json::json!({
+            "nodes": [{"name": "x", "type": "sma", "inputs": ["y"], "params": {"length": 1}}],
+            "outputs": [],
+        }));
+        assert!(matches!(err, Error::UnknownInput { .. }));
+        let err = build(serde_json::json!({
+            "nodes": [{"name": "x", "type": "sma", "inputs": ["value"]}],
+            "outputs": [],
+        }));
+        assert!(matches!(err, Error::Invalid { .. }));
+    }
+}
@@ -0,0 +1,265 @@
+use alloc::{boxed::Box, collections::BTreeMap, string::String};
+use core::ops::{Add, Div, Mul, Sub};
+use serde::Deserialize;
+
+use super::{
+    node::{self, BoxNode, Resampled},
+    schema::{NodeSchema, ParamSchema, ParamType},
+    Error, Fields, NodeConfig, Pipeline, PipelineConfig,
+};
+use crate::{
+    rolling::{ema, rolling_mean, rolling_sum},
+    Period,
+};
+
+type Constructor<T> = Box<dyn Fn(&NodeConfig) -> Result<BoxNode<T>, Error> + Send + Sync>;
+
+struct Entry<T> {
+    schema: NodeSchema,
+    constructor: Constructor<T>,
+}
+
+/// Registry of node types: their schemas and constructors.
+pub struct Registry<T> {
+    entries: BTreeMap<String, Entry<T>>,
+}
+
+impl<T> Default for Registry<T> {
+    fn default() -> Self {
+        Self {
+            entries: BTreeMap::default(),
+        }
+    }
+}
+
+impl<T> core::fmt::Debug for Registry<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map()
+            .entries(
+                self.entries
+                    .iter()
+                    .map(|(kind, entry)| (kind, &entry.schema)),
+            )
+            .finish()
+    }
+}
+
+impl<T: Copy + Send + 'static> Registry<T> {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the node type `kind`, replacing the previous one.
+    ///
+    /// The constructor is called with the config validated by the `schema`,
+    /// where the defaults of the parameters are filled in.
+    pub fn register<F>(
+        &mut self,
+        kind: impl Into<String>,
+        schema: NodeSchema,
+        constructor: F,
+    ) -> &mut Self
+    where
+        F: Fn(&NodeConfig) -> Result<BoxNode<T>, Error> + Send + Sync + 'static,
+    {
+        self.entries.insert(
+            kind.into(),
+            Entry {
+                schema,
+                constructor: Box::new(constructor),
+            },
+        );
+        self
+    }
+
+    /// Whether the node type is registered.
+    pub fn contains(&self, kind: &str) -> bool {
+        self.entries.contains_key(kind)
+    }
+
+    /// The schema of the node type.
+    pub fn schema(&self, kind: &str) -> Option<&NodeSchema> {
+        self.entries.get(kind).map(|entry| &entry.schema)
+    }
+
+    /// The registered node types and their schemas, ordered by name.
+    pub fn schemas(&self) -> impl Iterator<Item = (&str, &NodeSchema)> {
+        self.entries
+            .iter()
+            .map(|(kind, entry)| (kind.as_str(), &entry.schema))
+    }
+
+    /// Construct a node, evaluated over the windows of its period if it is given.
+    pub fn construct(&self, config: &NodeConfig) -> Result<BoxNode<T>, Error> {
+        let entry = self
+            .entries
+            .get(&config.kind)
+            .ok_or_else(|| Error::UnknownType(config.kind.clone()))?;
+        let validated = NodeConfig {
+            params: entry.schema.validate(config)?,
+            ..config.clone()
+        };
+        let node = (entry.constructor)(&validated)?;
+        let Some(kind) = config.period else {
+            return Ok(node);
+        };
+        let period = Period {
+            offset: time::UtcOffset::UTC,
+            kind,
+        };
+        match Resampled::new(period, node) {
+            Some(node) => Ok(Box::new(node)),
+            None => Err(Error::Invalid {
+                node: config.name.clone(),
+                message: alloc::format!("`{}` cannot be evaluated over periods", config.kind),
+            }),
+        }
+    }
+
+    /// Build the pipeline of the config, with the inputs of type `I`.
+    ///
+    /// Use [`OperatorExt::boxed`](crate::OperatorExt::boxed) to get a [`BoxOperator`](crate::prelude::BoxOperator).
+    pub fn build<I: Fields<T> + 'static>(
+        &self,
+        config: &PipelineConfig,
+    ) -> Result<Pipeline<I, T>, Error> {
+        Pipeline::new(self, config)
+    }
+}
+
+#[derive(Deserialize)]
+struct Length {
+    length: usize,
+}
+
+fn length(config: &NodeConfig) -> Result<usize, Error> {
+    let Length { length } = node::params(config)?;
+    if length == 0 {
+        return Err(Error::Invalid {
+            node: config.name.clone(),
+            message: "`length` must be positive".into(),
+        });
+    }
+    Ok(length)
+}
+
+impl<T> Registry<T>
+where
+    T: Copy
+        + Default
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + From<u8>
+        + Send
+        + 'static,
+{
+    /// Create a registry with the built-in node types:
+    /// - `sma` and `sum`: rolling mean and sum of one input, with the parameter `length`.
+    /// - `ema`: exponential moving average of one input, with the parameter `length`.
+    /// - `add`, `sub`, `mul` and `div`: arithmetic of two inputs.
+    pub fn builtin() -> Self {
+        let mut registry = Self::new();
+        let rolling = |description: &str| {
+            NodeSchema::new(1).describe(description).param(
+                ParamSchema::new("length", ParamType::Integer)
+                    .describe("The number of inputs in the window."),
+            )
+        };
+        registry
+            .register("sma", rolling("Simple moving average."), |config| {
+                Ok(node::unary(rolling_mean::<T>(length(config)?)))
+            })
+            .register("sum", rolling("Rolling sum."), |config| {
+                Ok(node::unary(rolling_sum::<T>(length(config)?)))
+            })
+            .register("ema", rolling("Exponential moving average."), |config| {
+                Ok(node::unary(ema::<T>(length(config)?)))
+            });
+        macro_rules! binary {
+            ($($name:literal => $op:tt, $description:literal),*) => {
+                $(
+                    registry.register($name, NodeSchema::new(2).describe($description), |_| {
+                        Ok(node::function(|x: &[T]| x[0] $op x[1]))
+                    });
+                )*
+            };
+        }
+        binary!(
+            "add" => +, "Sum of the inputs.",
+            "sub" => -, "Difference of the inputs.",
+            "mul" => *, "Product of the inputs.",
+            "div" => /, "Quotient of the inputs."
+        );
+        registry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn introspect() {
+        let registry = Registry::<f64>::builtin();
+        assert_eq!(
+            registry
+                .schemas()
+                .map(|(kind, _)| kind)
+                .collect::<alloc::vec::Vec<_>>(),
+            ["add", "div", "ema", "mul", "sma", "sub", "sum"]
+        );
+        let sma = registry.schema("sma").unwrap();
+        assert_eq!(sma.inputs, 1);
+        assert_eq!(sma.params[0].name, "length");
+        assert_eq!(
+            serde_json::to_value(sma).unwrap()["params"][0]["type"],
+            "integer"
+        );
+    }
+
+    #[cfg(feature = "context")]
+    #[test]
+    fn context_operator() {
+        use crate::{context::insert_env_and_output, prelude::*, IndicatorIteratorExt};
+
+        /// Twice the input.
+        #[operator(input = f64)]
+        fn double(In(x): In<&f64>) -> f64 {
+            x * 2.0
+        }
+
+        let mut registry = Registry::<f64>::new();
+        registry.register(
+            "double",
+            NodeSchema::new(1).describe("Twice the input."),
+            |_| {
+                Ok(node::operator(
+                    insert_env_and_output(double).finish().map(Some),
+                ))
+            },
+        );
+        let config: PipelineConfig = serde_json::from_value(serde_json::json!({
+            "nodes": [{"name": "y", "type": "double", "inputs": ["value"]}],
+            "outputs": ["y"],
+        }))
+        .unwrap();
+        let outputs = [1.0, 2.0]
+            .into_iter()
+            .map(crate::TickValue::big_bang)
+            .indicator(registry.build::<f64>(&config).unwrap())
+            .map(|x| x.value[0]);
+        assert!(outputs.eq([Some(2.0), Some(4.0)]));
+
+        let mut resampled = config.clone();
+        resampled.nodes[0].period = Some(crate::PeriodKind::Duration(
+            core::time::Duration::from_secs(60),
+        ));
+        assert!(matches!(
+            registry.build::<f64>(&resampled),
+            Err(Error::Invalid { .. })
+        ));
+    }
+}
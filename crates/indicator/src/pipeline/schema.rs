@@ -0,0 +1,193 @@
+use alloc::{string::String, vec::Vec};
+use serde::Serialize;
+use serde_json::Value;
+
+use super::{Error, NodeConfig, Params};
+use crate::PeriodKind;
+
+/// The type of a parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParamType {
+    /// Integer.
+    Integer,
+    /// Number.
+    Number,
+    /// Boolean.
+    Boolean,
+    /// String.
+    String,
+    /// Period, e.g. `"1h"`.
+    Period,
+}
+
+impl ParamType {
+    /// The name of the type.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Integer => "integer",
+            Self::Number => "number",
+            Self::Boolean => "boolean",
+            Self::String => "string",
+            Self::Period => "period",
+        }
+    }
+
+    fn check(&self, value: &Value) -> bool {
+        match self {
+            Self::Integer => value.is_i64() || value.is_u64(),
+            Self::Number => value.is_number(),
+            Self::Boolean => value.is_boolean(),
+            Self::String => value.is_string(),
+            Self::Period => serde_json::from_value::<PeriodKind>(value.clone()).is_ok(),
+        }
+    }
+}
+
+/// The schema of a parameter.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParamSchema {
+    /// Name.
+    pub name: String,
+    /// Type.
+    #[serde(rename = "type")]
+    pub ty: ParamType,
+    /// Default value, the parameter is required if it is `None`.
+    pub default: Option<Value>,
+    /// Description.
+    pub description: String,
+}
+
+impl ParamSchema {
+    /// Create a required parameter.
+    pub fn new(name: impl Into<String>, ty: ParamType) -> Self {
+        Self {
+            name: name.into(),
+            ty,
+            default: None,
+            description: String::new(),
+        }
+    }
+
+    /// Set the default value, making the parameter optional.
+    pub fn default(mut self, value: impl Into<Value>) -> Self {
+        self.default = Some(value.into());
+        self
+    }
+
+    /// Set the description.
+    pub fn describe(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+}
+
+/// The schema of a node type.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct NodeSchema {
+    /// Description.
+    pub description: String,
+    /// The number of inputs.
+    pub inputs: usize,
+    /// Parameters.
+    pub params: Vec<ParamSchema>,
+}
+
+impl NodeSchema {
+    /// Create a schema of a node type with `inputs` inputs and no parameters.
+    pub fn new(inputs: usize) -> Self {
+        Self {
+            inputs,
+            ..Default::default()
+        }
+    }
+
+    /// Set the description.
+    pub fn describe(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Add a parameter.
+    pub fn param(mut self, param: ParamSchema) -> Self {
+        self.params.push(param);
+        self
+    }
+
+    /// Validate the inputs and the parameters of the node,
+    /// return the parameters with the defaults filled in.
+    pub fn validate(&self, config: &NodeConfig) -> Result<Params, Error> {
+        let invalid = |message: String| Error::Invalid {
+            node: config.name.clone(),
+            message,
+        };
+        if config.inputs.len() != self.inputs {
+            return Err(invalid(alloc::format!(
+                "expected {} inputs, got {}",
+                self.inputs,
+                config.inputs.len()
+            )));
+        }
+        if let Some(name) = config
+            .params
+            .keys()
+            .find(|name| self.params.iter().all(|param| param.name != **name))
+        {
+            return Err(invalid(alloc::format!("unknown parameter `{name}`")));
+        }
+        let mut params = Params::new();
+        for param in self.params.iter() {
+            let value = match (config.params.get(&param.name), &param.default) {
+                (Some(value), _) => value,
+                (None, Some(default)) => default,
+                (None, None) => {
+                    return Err(invalid(alloc::format!(
+                        "missing parameter `{}`",
+                        param.name
+                    )))
+                }
+            };
+            if !param.ty.check(value) {
+                return Err(invalid(alloc::format!(
+                    "parameter `{}` must be {}",
+                    param.name,
+                    param.ty.as_str()
+                )));
+            }
+            params.insert(param.name.clone(), value.clone());
+        }
+        Ok(params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn validate() {
+        let schema = NodeSchema::new(1)
+            .param(ParamSchema::new("length", ParamType::Integer))
+            .param(ParamSchema::new("alpha", ParamType::Number).default(0.5));
+        let config = |params: Value| NodeConfig {
+            name: "x".into(),
+            kind: "ema".into(),
+            inputs: alloc::vec!["close".into()],
+            params: serde_json::from_value(params).unwrap(),
+            period: None,
+        };
+        let params = schema.validate(&config(json!({"length": 3}))).unwrap();
+        assert_eq!(Value::Object(params), json!({"length": 3, "alpha": 0.5}));
+        for params in [
+            json!({}),
+            json!({"length": 1.5}),
+            json!({"length": 3, "beta": 1}),
+        ] {
+            assert!(matches!(
+                schema.validate(&config(params)),
+                Err(Error::Invalid { .. })
+            ));
+        }
+    }
+}
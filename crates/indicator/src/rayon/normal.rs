@@ -1,4 +1,7 @@
-use crate::Operator;
+use crate::{
+    reset::{Reset, Seed},
+    Operator,
+};
 use rayon::prelude::*;
 use std::collections::HashMap;
 use std::hash::Hash;
@@ -32,6 +35,28 @@ where
     }
 }
 
+impl<I, P1, P2> Reset for Facet<I, P1, P2>
+where
+    P1: Reset,
+    P2: Reset,
+{
+    fn reset(&mut self) {
+        self.0.reset();
+        self.1.reset();
+    }
+}
+
+impl<I, S1, S2, P1, P2> Seed<(S1, S2)> for Facet<I, P1, P2>
+where
+    P1: Seed<S1>,
+    P2: Seed<S2>,
+{
+    fn seed(&mut self, seed: (S1, S2)) {
+        self.0.seed(seed.0);
+        self.1.seed(seed.1);
+    }
+}
+
 /// [`FacetMap`] combinator.
 #[derive(Debug, Clone)]
 pub struct FacetMap<I, Q, P>(HashMap<Q, P>, core::marker::PhantomData<fn() -> I>);
@@ -56,6 +81,31 @@ where
     }
 }
 
+impl<I, Q, P> Reset for FacetMap<I, Q, P>
+where
+    P: Reset,
+{
+    fn reset(&mut self) {
+        self.0.values_mut().for_each(P::reset);
+    }
+}
+
+/// Seed the operators of the keys in the seed, the other operators are reset.
+impl<I, Q, S, P> Seed<HashMap<Q, S>> for FacetMap<I, Q, P>
+where
+    Q: Eq + Hash,
+    P: Seed<S>,
+{
+    fn seed(&mut self, seed: HashMap<Q, S>) {
+        self.reset();
+        for (k, s) in seed {
+            if let Some(p) = self.0.get_mut(&k) {
+                p.seed(s);
+            }
+        }
+    }
+}
+
 /// Create an operator that apply different operators to the same input,
 /// and return the collections of outputs as its output.
 ///
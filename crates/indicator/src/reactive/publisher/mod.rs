@@ -21,7 +21,7 @@ pub trait Publisher<'a> {
         S: Subscriber<Self::Output> + 'a;
 }
 
-impl<'a, 'b, P> Publisher<'a> for &'b mut P
+impl<'a, P> Publisher<'a> for &mut P
 where
     P: Publisher<'a>,
 {
@@ -0,0 +1,142 @@
+/// Operator that reports whether its outputs are meaningful.
+///
+/// For example, a [`TumblingOperator`](crate::TumblingOperator) with a queue of length `LEN`
+/// produces outputs before the queue is full, which are not meaningful.
+pub trait Readiness {
+    /// The number of steps (windows for windowed operators, inputs for the others)
+    /// required before the outputs become meaningful.
+    fn warm_up(&self) -> usize;
+
+    /// Whether the outputs are meaningful now.
+    fn is_ready(&self) -> bool;
+}
+
+impl<P> Readiness for &mut P
+where
+    P: Readiness + ?Sized,
+{
+    fn warm_up(&self) -> usize {
+        (**self).warm_up()
+    }
+
+    fn is_ready(&self) -> bool {
+        (**self).is_ready()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<P> Readiness for alloc::boxed::Box<P>
+where
+    P: Readiness + ?Sized,
+{
+    fn warm_up(&self) -> usize {
+        self.as_ref().warm_up()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.as_ref().is_ready()
+    }
+}
+
+/// Operator that can evaluate an input without advancing.
+pub trait Peek<I>: crate::Operator<I> {
+    /// The output [`next`](crate::Operator::next) would produce for the input,
+    /// marked with the readiness of the operator after it.
+    fn peek(&self, input: I) -> Marked<Self::Output>;
+}
+
+/// Output marked with the readiness of the operator that produced it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Marked<T> {
+    /// Produced before the operator is ready.
+    Warming(T),
+    /// Produced when the operator is ready.
+    Ready(T),
+}
+
+impl<T> Marked<T> {
+    pub(crate) fn new(value: T, ready: bool) -> Self {
+        if ready {
+            Self::Ready(value)
+        } else {
+            Self::Warming(value)
+        }
+    }
+
+    /// Whether the output is produced when the operator is ready.
+    pub fn is_ready(&self) -> bool {
+        matches!(self, Self::Ready(_))
+    }
+
+    /// Get the output if it is produced when the operator is ready.
+    pub fn ready(self) -> Option<T> {
+        match self {
+            Self::Ready(value) => Some(value),
+            Self::Warming(_) => None,
+        }
+    }
+
+    /// Map the output.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Marked<U> {
+        match self {
+            Self::Ready(value) => Marked::Ready(f(value)),
+            Self::Warming(value) => Marked::Warming(f(value)),
+        }
+    }
+
+    /// Get the output.
+    pub fn into_inner(self) -> T {
+        match self {
+            Self::Ready(value) | Self::Warming(value) => value,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "array-vec"))]
+mod tests {
+    use super::*;
+    use crate::{facet_t, map_t, tumbling, IndicatorIteratorExt, Operator, OperatorExt, Period};
+    use crate::{TickValue, TickedOperatorExt};
+    use arrayvec::ArrayVec;
+    use time::{macros::datetime, Duration};
+
+    fn sum<const LEN: usize>() -> impl Operator<TickValue<i64>, Output = TickValue<i64>> + Readiness
+    {
+        tumbling(
+            Period::secs(1),
+            |q: &ArrayVec<i64, LEN>, y: &mut Option<i64>, x| {
+                *y = Some(x);
+                q.iter().sum::<i64>() + x
+            },
+        )
+    }
+
+    #[test]
+    fn compose() {
+        let op = sum::<2>().then(sum::<1>());
+        assert_eq!(op.warm_up(), 2);
+        let op = facet_t(sum::<2>(), sum::<1>()).map_t(|(a, b)| a + b);
+        assert_eq!(op.warm_up(), 2);
+        let base = datetime!(2023-01-01 00:00:00 UTC);
+        let mut marked = (0..4)
+            .map(|x| TickValue::new(base + Duration::seconds(x), x))
+            .marked_indicator(op)
+            .map(|x| x.map(|x| x.value));
+        assert_eq!(marked.next(), Some(Marked::Warming(0)));
+        assert_eq!(marked.next(), Some(Marked::Warming(1 + 1)));
+        assert_eq!(marked.next(), Some(Marked::Ready(3 + 3)));
+        assert_eq!(marked.next(), Some(Marked::Ready(6 + 5)));
+    }
+
+    #[test]
+    fn warmed() {
+        let base = datetime!(2023-01-01 00:00:00 UTC);
+        let mut warmed = (0..4)
+            .map(|x| TickValue::new(base + Duration::seconds(x), x))
+            .warmed_indicator(sum::<2>().then(map_t(|x| x)))
+            .map(|x| x.value);
+        assert_eq!(warmed.next(), Some(3));
+        assert_eq!(warmed.next(), Some(6));
+        assert_eq!(warmed.next(), None);
+    }
+}
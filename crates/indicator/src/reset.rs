@@ -0,0 +1,105 @@
+/// Operator whose state can be cleared at runtime.
+///
+/// After a reset, the operator behaves as if it were newly created.
+/// The state of the user-provided closures is not cleared,
+/// they are assumed to be stateless.
+pub trait Reset {
+    /// Clear the state.
+    fn reset(&mut self);
+}
+
+impl<P> Reset for &mut P
+where
+    P: Reset + ?Sized,
+{
+    fn reset(&mut self) {
+        (**self).reset()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<P> Reset for alloc::boxed::Box<P>
+where
+    P: Reset + ?Sized,
+{
+    fn reset(&mut self) {
+        self.as_mut().reset()
+    }
+}
+
+/// Operator that can be seeded with historical state.
+///
+/// Seeding clears the current state before loading the seed `S`.
+/// Combinators take a tuple of the seeds of their inner operators,
+/// using `()` for the stateless ones.
+pub trait Seed<S>: Reset {
+    /// Reset and then load the seed.
+    fn seed(&mut self, seed: S);
+}
+
+impl<S, P> Seed<S> for &mut P
+where
+    P: Seed<S> + ?Sized,
+{
+    fn seed(&mut self, seed: S) {
+        (**self).seed(seed)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<S, P> Seed<S> for alloc::boxed::Box<P>
+where
+    P: Seed<S> + ?Sized,
+{
+    fn seed(&mut self, seed: S) {
+        self.as_mut().seed(seed)
+    }
+}
+
+#[cfg(all(test, feature = "array-vec"))]
+mod tests {
+    use super::*;
+    use crate::{
+        map_t, tumbling, IndicatorIteratorExt, Operator, OperatorExt, Period, QueueCapAtLeast,
+        TickValue,
+    };
+    use arrayvec::ArrayVec;
+    use time::{macros::datetime, Duration};
+
+    fn ema() -> impl Operator<TickValue<i64>, Output = TickValue<i64>> + Seed<Option<i64>> {
+        tumbling(
+            Period::secs(1),
+            |q: &ArrayVec<i64, 1>, y: &mut Option<i64>, x| {
+                let ema = q.get_latest(0).map_or(x, |prev| (prev + x) / 2);
+                *y = Some(ema);
+                ema
+            },
+        )
+    }
+
+    fn data() -> impl Iterator<Item = TickValue<i64>> {
+        let base = datetime!(2023-01-01 00:00:00 UTC);
+        [10, 20, 30, 40, 50, 60]
+            .into_iter()
+            .enumerate()
+            .map(move |(idx, x)| TickValue::new(base + Duration::seconds(idx as i64), x))
+    }
+
+    #[test]
+    fn reset() {
+        let mut op = ema().then(map_t(|x| x));
+        data().take(3).for_each(|x| {
+            op.next(x);
+        });
+        op.reset();
+        assert!(data().indicator(op).eq(data().indicator(ema())));
+    }
+
+    #[test]
+    fn seed() {
+        let mut expected = data().indicator(ema());
+        let mut op = ema().then(map_t(|x| x));
+        op.seed((expected.nth(2).map(|x| x.value), ()));
+        assert!(data().skip(3).indicator(op).eq(expected));
+    }
+}
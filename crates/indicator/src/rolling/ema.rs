@@ -0,0 +1,138 @@
+use core::ops::{Add, Div, Mul, Sub};
+
+use crate::{
+    readiness::{Marked, Peek, Readiness},
+    reset::{Reset, Seed},
+    Operator,
+};
+
+#[cfg(feature = "snapshot")]
+use crate::snapshot::Snapshot;
+#[cfg(feature = "snapshot")]
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Exponential moving average with the smoothing factor `2 / (length + 1)`,
+/// seeded with the first input.
+///
+/// Created by [`ema`].
+#[derive(Debug, Clone)]
+pub struct Ema<T> {
+    length: usize,
+    alpha: T,
+    steps: usize,
+    value: Option<T>,
+}
+
+impl<T> Ema<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    fn average(&self, input: T) -> T {
+        self.value
+            .map_or(input, |prev| prev + self.alpha * (input - prev))
+    }
+}
+
+impl<T> Operator<T> for Ema<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    type Output = T;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        self.steps = self.steps.saturating_add(1);
+        *self.value.insert(self.average(input))
+    }
+}
+
+impl<T> Peek<T> for Ema<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+{
+    fn peek(&self, input: T) -> Marked<Self::Output> {
+        Marked::new(
+            self.average(input),
+            self.steps.saturating_add(1) >= self.length,
+        )
+    }
+}
+
+/// The operator is ready when it has seen `length` inputs.
+impl<T> Readiness for Ema<T> {
+    fn warm_up(&self) -> usize {
+        self.length
+    }
+
+    fn is_ready(&self) -> bool {
+        self.steps >= self.length
+    }
+}
+
+impl<T> Reset for Ema<T> {
+    fn reset(&mut self) {
+        self.steps = 0;
+        self.value = None;
+    }
+}
+
+/// Seed with the previous inputs, from the oldest to the latest.
+impl<T, H> Seed<H> for Ema<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    H: IntoIterator<Item = T>,
+{
+    fn seed(&mut self, seed: H) {
+        self.reset();
+        for x in seed {
+            self.next(x);
+        }
+    }
+}
+
+#[cfg(feature = "snapshot")]
+impl<T> Snapshot for Ema<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Serialize + DeserializeOwned,
+{
+    /// The number of inputs seen and the last average.
+    type State = (usize, Option<T>);
+
+    fn snapshot(&self) -> Self::State {
+        (self.steps, self.value)
+    }
+
+    fn restore(&mut self, (steps, value): Self::State) {
+        self.steps = steps;
+        self.value = value;
+    }
+}
+
+/// Convert `n` into `T` with its binary digits.
+fn count<T>(n: usize) -> T
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + From<u8>,
+{
+    let bits = usize::BITS - n.leading_zeros();
+    (0..bits).rev().fold(T::default(), |acc, bit| {
+        acc * T::from(2) + T::from(((n >> bit) & 1) as u8)
+    })
+}
+
+/// Create an exponential moving average with the smoothing factor `2 / (length + 1)`.
+///
+/// The output is seeded with the first input, and the operator is ready
+/// after `length` inputs.
+/// # Panic
+/// Panic if `length` is 0.
+pub fn ema<T>(length: usize) -> Ema<T>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T> + Div<Output = T> + From<u8>,
+{
+    assert!(length > 0, "`length` cannot be 0");
+    Ema {
+        length,
+        alpha: T::from(2) / (count::<T>(length) + T::from(1)),
+        steps: 0,
+        value: None,
+    }
+}
@@ -0,0 +1,157 @@
+use alloc::vec::Vec;
+use core::ops::{Add, Div, Sub};
+
+use crate::{
+    readiness::{Marked, Peek, Readiness},
+    reset::{Reset, Seed},
+    Operator,
+};
+
+use super::Window;
+
+#[cfg(feature = "snapshot")]
+use crate::snapshot::Snapshot;
+#[cfg(feature = "snapshot")]
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Operator that averages the last `length` inputs.
+///
+/// Created by [`rolling_mean`].
+#[derive(Debug, Clone)]
+pub struct RollingMean<T> {
+    window: Window<T>,
+    count: T,
+}
+
+impl<T> RollingMean<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T> + Div<Output = T> + From<u8>,
+{
+    fn mean(&mut self, sum: T, grown: bool) -> T {
+        if grown {
+            self.count = self.count + T::from(1);
+        }
+        sum / self.count
+    }
+}
+
+impl<T> Operator<T> for RollingMean<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T> + Div<Output = T> + From<u8>,
+{
+    type Output = T;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        let (sum, grown) = self.window.push(input);
+        self.mean(sum, grown)
+    }
+
+    fn next_batch(&mut self, inputs: &[T], outputs: &mut Vec<Self::Output>) {
+        outputs.reserve(inputs.len());
+        let Self { window, count } = self;
+        window.push_batch(inputs, |sum, grown| {
+            if grown {
+                *count = *count + T::from(1);
+            }
+            outputs.push(sum / *count);
+        });
+    }
+
+    fn drain_batch(&mut self, inputs: &mut Vec<T>, outputs: &mut Vec<Self::Output>) {
+        self.next_batch(inputs, outputs);
+        inputs.clear();
+    }
+}
+
+impl<T> Peek<T> for RollingMean<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T> + Div<Output = T> + From<u8>,
+{
+    fn peek(&self, input: T) -> Marked<Self::Output> {
+        let (sum, grown) = self.window.peek(input);
+        let count = if grown {
+            self.count + T::from(1)
+        } else {
+            self.count
+        };
+        Marked::new(sum / count, self.window.fills())
+    }
+}
+
+/// The operator is ready when it has seen `length` inputs.
+impl<T> Readiness for RollingMean<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    fn warm_up(&self) -> usize {
+        self.window.length()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.window.is_full()
+    }
+}
+
+impl<T> Reset for RollingMean<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    fn reset(&mut self) {
+        self.window.reset();
+        self.count = T::default();
+    }
+}
+
+/// Seed with the previous inputs, from the oldest to the latest.
+impl<T, H> Seed<H> for RollingMean<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T> + Div<Output = T> + From<u8>,
+    H: IntoIterator<Item = T>,
+{
+    fn seed(&mut self, seed: H) {
+        self.reset();
+        for x in seed {
+            let (sum, grown) = self.window.push(x);
+            self.mean(sum, grown);
+        }
+    }
+}
+
+#[cfg(feature = "snapshot")]
+impl<T> Snapshot for RollingMean<T>
+where
+    T: Copy
+        + Default
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Div<Output = T>
+        + From<u8>
+        + Serialize
+        + DeserializeOwned,
+{
+    /// The inputs in the window, from the oldest to the latest.
+    type State = Vec<T>;
+
+    fn snapshot(&self) -> Self::State {
+        self.window.items().copied().collect()
+    }
+
+    fn restore(&mut self, state: Self::State) {
+        self.seed(state);
+    }
+}
+
+/// Create an operator that averages the last `length` inputs.
+///
+/// The output is the average of all the inputs before it is ready.
+/// # Panic
+/// Panic if `length` is 0.
+pub fn rolling_mean<T>(length: usize) -> RollingMean<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T> + Div<Output = T> + From<u8>,
+{
+    RollingMean {
+        window: Window::new(length),
+        count: T::default(),
+    }
+}
@@ -0,0 +1,227 @@
+use alloc::collections::VecDeque;
+use core::ops::{Add, Sub};
+
+/// Rolling sum.
+pub mod sum;
+
+/// Rolling mean.
+pub mod mean;
+
+/// Exponential moving average.
+pub mod ema;
+
+pub use ema::{ema, Ema};
+pub use mean::{rolling_mean, RollingMean};
+pub use sum::{rolling_sum, RollingSum};
+
+/// The last `length` inputs and their sum.
+#[derive(Debug, Clone)]
+pub(crate) struct Window<T> {
+    length: usize,
+    items: VecDeque<T>,
+    sum: T,
+}
+
+impl<T> Window<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    pub(crate) fn new(length: usize) -> Self {
+        assert!(length > 0, "`length` cannot be 0");
+        Self {
+            length,
+            items: VecDeque::with_capacity(length),
+            sum: T::default(),
+        }
+    }
+
+    pub(crate) fn length(&self) -> usize {
+        self.length
+    }
+
+    pub(crate) fn is_full(&self) -> bool {
+        self.items.len() == self.length
+    }
+
+    #[cfg(feature = "snapshot")]
+    pub(crate) fn items(&self) -> impl Iterator<Item = &T> {
+        self.items.iter()
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.items.clear();
+        self.sum = T::default();
+    }
+
+    /// Push a new input and return the sum,
+    /// and whether the window has grown.
+    pub(crate) fn push(&mut self, x: T) -> (T, bool) {
+        self.sum = self.sum + x;
+        self.items.push_back(x);
+        if self.items.len() > self.length {
+            let oldest = self.items.pop_front().expect("not empty");
+            self.sum = self.sum - oldest;
+            (self.sum, false)
+        } else {
+            (self.sum, true)
+        }
+    }
+
+    /// The sum if `x` were pushed, and whether the window would grow.
+    pub(crate) fn peek(&self, x: T) -> (T, bool) {
+        match self.items.front() {
+            Some(oldest) if self.is_full() => (self.sum + x - *oldest, false),
+            _ => (self.sum + x, true),
+        }
+    }
+
+    /// Whether the window would be full if an input were pushed.
+    pub(crate) fn fills(&self) -> bool {
+        self.items.len() + 1 >= self.length
+    }
+
+    /// Push a batch of inputs, calling `f` with the result of each push.
+    ///
+    /// Once the inputs fill the window, the dropped inputs are read from
+    /// the batch directly instead of the queue.
+    pub(crate) fn push_batch(&mut self, inputs: &[T], mut f: impl FnMut(T, bool)) {
+        let head = inputs.len().min(self.length);
+        for x in &inputs[..head] {
+            let (sum, grown) = self.push(*x);
+            f(sum, grown);
+        }
+        if inputs.len() == head {
+            return;
+        }
+        // The queue now holds `inputs[..length]`.
+        let mut sum = self.sum;
+        for (x, oldest) in inputs[head..].iter().zip(inputs) {
+            sum = sum + *x - *oldest;
+            f(sum, false);
+        }
+        self.sum = sum;
+        self.items.clear();
+        self.items
+            .extend(inputs[inputs.len() - self.length..].iter().copied());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        map, readiness::Marked, IndicatorIteratorExt, Operator, OperatorExt, Peek, Readiness,
+        Reset, Seed,
+    };
+    use alloc::vec::Vec;
+
+    fn batched<P: Operator<i64>>(op: &mut P, inputs: &[i64], size: usize) -> Vec<P::Output> {
+        let mut outputs = Vec::new();
+        for chunk in inputs.chunks(size) {
+            op.next_batch(chunk, &mut outputs);
+        }
+        outputs
+    }
+
+    #[test]
+    fn batch() {
+        let inputs = (0..100).map(|x| (x * 37) % 11 - 5).collect::<Vec<_>>();
+        let expected = inputs
+            .iter()
+            .copied()
+            .indicator(rolling_sum(7).facet(rolling_mean(3)))
+            .collect::<Vec<_>>();
+        for size in [1, 2, 3, 5, 7, 8, 50, 100] {
+            let mut op = rolling_sum(7).facet(rolling_mean(3));
+            assert_eq!(batched(&mut op, &inputs, size), expected);
+        }
+    }
+
+    #[test]
+    fn boxed() {
+        let inputs = (0..20).collect::<Vec<i64>>();
+        let expected = inputs
+            .iter()
+            .copied()
+            .indicator(map(|x: i64| x * 2).then(rolling_sum(4)))
+            .collect::<Vec<_>>();
+        let mut op = map(|x: i64| x * 2).then(rolling_sum(4)).boxed();
+        assert_eq!(batched(&mut op, &inputs, 6), expected);
+        let mut op = map(|x: i64| x * 2).then(rolling_sum(4));
+        let (mut drained, mut outputs) = (inputs.clone(), Vec::new());
+        op.drain_batch(&mut drained, &mut outputs);
+        assert!(drained.is_empty());
+        assert_eq!(outputs, expected);
+        assert!(inputs
+            .iter()
+            .copied()
+            .batch_indicator(map(|x: i64| x * 2).then(rolling_sum(4)), 6)
+            .eq(expected));
+    }
+
+    #[test]
+    fn exponential() {
+        let mut op = ema::<f64>(3);
+        assert_eq!(op.warm_up(), 3);
+        let outputs = [1.0, 3.0, 5.0].map(|x| op.next(x));
+        assert_eq!(outputs, [1.0, 2.0, 3.5]);
+        assert!(op.is_ready());
+        assert_eq!(ema::<i64>(1).next(7), 7);
+        let mut seeded = ema::<f64>(3);
+        seeded.seed([1.0, 3.0]);
+        assert!(!seeded.is_ready());
+        assert_eq!(seeded.next(5.0), 3.5);
+        let mut op = ema::<f64>(12);
+        op.next(0.0);
+        assert_eq!(op.next(13.0), 2.0);
+    }
+
+    #[test]
+    fn then() {
+        for (first, second) in [(2, 4), (4, 2)] {
+            let op = rolling_sum(first).then(rolling_mean(second));
+            assert_eq!(op.warm_up(), 4);
+            let ready = (1..=6)
+                .marked_indicator(op)
+                .map(|x| x.is_ready())
+                .collect::<Vec<_>>();
+            assert_eq!(ready, [false, false, false, true, true, true]);
+        }
+    }
+
+    #[test]
+    fn readiness() {
+        let mut op = rolling_mean(3);
+        assert_eq!(op.warm_up(), 3);
+        assert_eq!(op.next(3), 3);
+        assert_eq!(op.next(5), 4);
+        assert!(!op.is_ready());
+        assert_eq!(op.next(7), 5);
+        assert!(op.is_ready());
+        op.reset();
+        assert!(!op.is_ready());
+        op.seed([1, 2, 3, 4]);
+        assert!(op.is_ready());
+        assert_eq!(op.next(11), 6);
+    }
+
+    fn peeked<P: Peek<i64> + Readiness>(mut op: P, inputs: &[i64])
+    where
+        P::Output: PartialEq + core::fmt::Debug,
+    {
+        for x in inputs {
+            let peeked = op.peek(*x);
+            let output = op.next(*x);
+            assert_eq!(peeked, Marked::new(output, op.is_ready()));
+        }
+    }
+
+    #[test]
+    fn peek() {
+        let inputs = [3, 5, 7, 1, -4, 9];
+        peeked(rolling_sum(3), &inputs);
+        peeked(rolling_mean(3), &inputs);
+        peeked(ema(3), &inputs);
+        peeked(ema(1), &inputs);
+    }
+}
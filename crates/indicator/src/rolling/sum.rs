@@ -0,0 +1,121 @@
+use alloc::vec::Vec;
+use core::ops::{Add, Sub};
+
+use crate::{
+    readiness::{Marked, Peek, Readiness},
+    reset::{Reset, Seed},
+    Operator,
+};
+
+use super::Window;
+
+#[cfg(feature = "snapshot")]
+use crate::snapshot::Snapshot;
+#[cfg(feature = "snapshot")]
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Operator that sums up the last `length` inputs.
+///
+/// Created by [`rolling_sum`].
+#[derive(Debug, Clone)]
+pub struct RollingSum<T> {
+    window: Window<T>,
+}
+
+impl<T> Operator<T> for RollingSum<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    type Output = T;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        self.window.push(input).0
+    }
+
+    fn next_batch(&mut self, inputs: &[T], outputs: &mut Vec<Self::Output>) {
+        outputs.reserve(inputs.len());
+        self.window.push_batch(inputs, |sum, _| outputs.push(sum));
+    }
+
+    fn drain_batch(&mut self, inputs: &mut Vec<T>, outputs: &mut Vec<Self::Output>) {
+        self.next_batch(inputs, outputs);
+        inputs.clear();
+    }
+}
+
+impl<T> Peek<T> for RollingSum<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    fn peek(&self, input: T) -> Marked<Self::Output> {
+        Marked::new(self.window.peek(input).0, self.window.fills())
+    }
+}
+
+/// The operator is ready when it has seen `length` inputs.
+impl<T> Readiness for RollingSum<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    fn warm_up(&self) -> usize {
+        self.window.length()
+    }
+
+    fn is_ready(&self) -> bool {
+        self.window.is_full()
+    }
+}
+
+impl<T> Reset for RollingSum<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    fn reset(&mut self) {
+        self.window.reset();
+    }
+}
+
+/// Seed with the previous inputs, from the oldest to the latest.
+impl<T, H> Seed<H> for RollingSum<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+    H: IntoIterator<Item = T>,
+{
+    fn seed(&mut self, seed: H) {
+        self.reset();
+        for x in seed {
+            self.window.push(x);
+        }
+    }
+}
+
+#[cfg(feature = "snapshot")]
+impl<T> Snapshot for RollingSum<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T> + Serialize + DeserializeOwned,
+{
+    /// The inputs in the window, from the oldest to the latest.
+    type State = Vec<T>;
+
+    fn snapshot(&self) -> Self::State {
+        self.window.items().copied().collect()
+    }
+
+    fn restore(&mut self, state: Self::State) {
+        self.seed(state);
+    }
+}
+
+/// Create an operator that sums up the last `length` inputs.
+///
+/// The output is the sum of all the inputs before it is ready.
+/// # Panic
+/// Panic if `length` is 0.
+pub fn rolling_sum<T>(length: usize) -> RollingSum<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    RollingSum {
+        window: Window::new(length),
+    }
+}
@@ -0,0 +1,198 @@
+use core::ops::Sub;
+
+use crate::{
+    reset::{Reset, Seed},
+    Operator, TickValue, Tickable, TumblingWindow,
+};
+
+use super::{Confirm, Cross, Streak};
+
+/// Operator that detects the crossovers of two series.
+///
+/// Created by [`cross`].
+#[derive(Debug, Clone)]
+pub struct CrossOver<M, T> {
+    confirm: Confirm<M, (T, T)>,
+    hysteresis: T,
+    persistence: usize,
+    state: Option<Cross>,
+    streak: Streak<Cross>,
+}
+
+impl<M, T> CrossOver<M, T>
+where
+    T: Default,
+{
+    /// Only consider the fast series to be above (below) the slow one
+    /// if their difference is greater than `hysteresis`.
+    pub fn hysteresis(mut self, hysteresis: T) -> Self {
+        self.hysteresis = hysteresis;
+        self
+    }
+
+    /// Require the new state to hold for at least `bars` confirmed bars
+    /// before emitting the event.
+    pub fn persistence(mut self, bars: usize) -> Self {
+        self.persistence = bars.max(1);
+        self
+    }
+}
+
+impl<M, T> CrossOver<M, T>
+where
+    T: Copy + PartialOrd + Sub<Output = T> + Default,
+{
+    fn confirmed(&mut self, fast: T, slow: T) -> Option<Cross> {
+        match Cross::classify(fast - slow, self.hysteresis) {
+            Some(side) if self.state.is_none() => {
+                self.state = Some(side);
+                None
+            }
+            Some(side) if Some(side) != self.state => {
+                if self.streak.observe(Some(side)) >= self.persistence {
+                    self.streak.reset();
+                    self.state.replace(side).map(|_| side)
+                } else {
+                    None
+                }
+            }
+            _ => {
+                self.streak.reset();
+                None
+            }
+        }
+    }
+}
+
+impl<M, I, T> Operator<I> for CrossOver<M, T>
+where
+    M: TumblingWindow,
+    I: Tickable<Value = (T, T)>,
+    T: Copy + PartialOrd + Sub<Output = T> + Default,
+{
+    type Output = TickValue<Option<Cross>>;
+
+    fn next(&mut self, input: I) -> Self::Output {
+        let TickValue { tick, value } = input.into_tick_value();
+        let event = self
+            .confirm
+            .push(tick, value)
+            .and_then(|(fast, slow)| self.confirmed(fast, slow));
+        TickValue { tick, value: event }
+    }
+}
+
+impl<M: TumblingWindow, T> Reset for CrossOver<M, T> {
+    fn reset(&mut self) {
+        self.confirm.reset();
+        self.state = None;
+        self.streak.reset();
+    }
+}
+
+/// Seed with the current side of the fast series,
+/// so that the next opposite crossing is emitted.
+impl<M: TumblingWindow, T> Seed<Cross> for CrossOver<M, T> {
+    fn seed(&mut self, seed: Cross) {
+        self.reset();
+        self.state = Some(seed);
+    }
+}
+
+/// Create an operator that detects the crossovers of two series,
+/// given in the form of `(fast, slow)`.
+///
+/// The events are only emitted on confirmed bars,
+/// that is, when the first event of the next window (defined by `mode`) arrives.
+/// ```
+/// use indicator::{signal::{cross, Cross}, *};
+///
+/// fn golden_cross<I>(period: Period) -> impl Operator<I, Output = TickValue<bool>>
+/// where
+///     I: Tickable<Value = (f64, f64)>,
+/// {
+///     cross(period)
+///         .hysteresis(0.5)
+///         .persistence(2)
+///         .map_t(|event| event == Some(Cross::Above))
+/// }
+/// ```
+pub fn cross<M, T>(mode: M) -> CrossOver<M, T>
+where
+    M: TumblingWindow,
+    T: Default,
+{
+    CrossOver {
+        confirm: Confirm::new(mode),
+        hysteresis: T::default(),
+        persistence: 1,
+        state: None,
+        streak: Streak::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndicatorIteratorExt, Period};
+    use time::macros::datetime;
+
+    fn check<P>(op: P, data: &[(i64, i64)], expected: &[Option<Cross>])
+    where
+        P: Operator<TickValue<(i64, i64)>, Output = TickValue<Option<Cross>>>,
+    {
+        let base = datetime!(2023-01-01 00:00:00 UTC);
+        assert_eq!(data.len(), expected.len());
+        data.iter()
+            .enumerate()
+            .map(|(idx, x)| TickValue::new(base + time::Duration::seconds(idx as i64), *x))
+            .indicator(op)
+            .zip(expected)
+            .enumerate()
+            .for_each(|(idx, (event, expected))| assert_eq!(event.value, *expected, "at {idx}"));
+    }
+
+    #[test]
+    fn basic() {
+        check(
+            cross(Period::secs(1)),
+            &[(1, 2), (3, 2), (3, 2), (1, 2), (1, 2)],
+            &[None, None, Some(Cross::Above), None, Some(Cross::Below)],
+        );
+    }
+
+    #[test]
+    fn only_confirmed_bars() {
+        // The second bar crosses above in the middle but closes below.
+        check(
+            cross(Period::secs(2)),
+            &[(1, 2), (1, 2), (3, 2), (1, 2), (1, 2)],
+            &[None; 5],
+        );
+    }
+
+    #[test]
+    fn hysteresis() {
+        check(
+            cross(Period::secs(1)).hysteresis(1),
+            &[(1, 3), (3, 2), (4, 2), (1, 2), (0, 2), (0, 2)],
+            &[
+                None,
+                None,
+                None,
+                Some(Cross::Above),
+                None,
+                Some(Cross::Below),
+            ],
+        );
+    }
+
+    #[test]
+    fn persistence() {
+        check(
+            cross(Period::secs(1)).persistence(2),
+            &[(1, 2), (3, 2), (1, 2), (3, 2), (3, 2), (3, 2)],
+            &[None, None, None, None, None, Some(Cross::Above)],
+        );
+    }
+}
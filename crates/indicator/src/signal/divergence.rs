@@ -0,0 +1,198 @@
+use alloc::collections::VecDeque;
+use core::ops::Sub;
+
+use crate::{reset::Reset, Operator, TickValue, Tickable, TumblingWindow};
+
+use super::{Confirm, Streak};
+
+/// A divergence event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Divergence {
+    /// The price makes a lower low while the oscillator makes a higher low.
+    Bullish,
+    /// The price makes a higher high while the oscillator makes a lower high.
+    Bearish,
+}
+
+/// Operator that detects the divergences between a price series and an oscillator.
+///
+/// Created by [`divergence`].
+#[derive(Debug, Clone)]
+pub struct DivergenceOperator<M, T> {
+    confirm: Confirm<M, (T, T)>,
+    lookback: usize,
+    hysteresis: T,
+    persistence: usize,
+    bars: VecDeque<(T, T)>,
+    streak: Streak<Divergence>,
+}
+
+impl<M, T> DivergenceOperator<M, T> {
+    /// Only consider the oscillator to be diverging
+    /// if it differs from the previous extreme by more than `hysteresis`.
+    pub fn hysteresis(mut self, hysteresis: T) -> Self {
+        self.hysteresis = hysteresis;
+        self
+    }
+
+    /// Require the divergence to hold for at least `bars` confirmed bars
+    /// before emitting the event.
+    pub fn persistence(mut self, bars: usize) -> Self {
+        self.persistence = bars.max(1);
+        self
+    }
+}
+
+impl<M, T> DivergenceOperator<M, T>
+where
+    T: Copy + PartialOrd + Sub<Output = T> + Default,
+{
+    fn detect(&self, price: T, osc: T) -> Option<Divergence> {
+        let mut bars = self.bars.iter();
+        let first = *bars.next()?;
+        let (low, high) = bars.fold((first, first), |(low, high), bar| {
+            (
+                if bar.0 < low.0 { *bar } else { low },
+                if bar.0 > high.0 { *bar } else { high },
+            )
+        });
+        if price < low.0 && osc - low.1 > self.hysteresis {
+            Some(Divergence::Bullish)
+        } else if price > high.0 && high.1 - osc > self.hysteresis {
+            Some(Divergence::Bearish)
+        } else {
+            None
+        }
+    }
+
+    fn confirmed(&mut self, price: T, osc: T) -> Option<Divergence> {
+        let event = self.detect(price, osc);
+        self.bars.push_back((price, osc));
+        if self.bars.len() > self.lookback {
+            self.bars.pop_front();
+        }
+        // Only emit once per streak.
+        (self.streak.observe(event) == self.persistence)
+            .then_some(event)
+            .flatten()
+    }
+}
+
+impl<M, I, T> Operator<I> for DivergenceOperator<M, T>
+where
+    M: TumblingWindow,
+    I: Tickable<Value = (T, T)>,
+    T: Copy + PartialOrd + Sub<Output = T> + Default,
+{
+    type Output = TickValue<Option<Divergence>>;
+
+    fn next(&mut self, input: I) -> Self::Output {
+        let TickValue { tick, value } = input.into_tick_value();
+        let event = self
+            .confirm
+            .push(tick, value)
+            .and_then(|(price, osc)| self.confirmed(price, osc));
+        TickValue { tick, value: event }
+    }
+}
+
+impl<M: TumblingWindow, T> Reset for DivergenceOperator<M, T> {
+    fn reset(&mut self) {
+        self.confirm.reset();
+        self.bars.clear();
+        self.streak.reset();
+    }
+}
+
+/// Create an operator that detects the divergences between a price series
+/// and an oscillator, given in the form of `(price, oscillator)`.
+///
+/// A confirmed bar is compared with the extremes of the previous `lookback` confirmed bars.
+///
+/// The events are only emitted on confirmed bars,
+/// that is, when the first event of the next window (defined by `mode`) arrives.
+/// ```
+/// use indicator::{signal::{divergence, Divergence}, *};
+///
+/// fn bullish<I>(period: Period) -> impl Operator<I, Output = TickValue<bool>>
+/// where
+///     I: Tickable<Value = (f64, f64)>,
+/// {
+///     divergence(period, 14)
+///         .hysteresis(2.0)
+///         .map_t(|event| event == Some(Divergence::Bullish))
+/// }
+/// ```
+pub fn divergence<M, T>(mode: M, lookback: usize) -> DivergenceOperator<M, T>
+where
+    M: TumblingWindow,
+    T: Default,
+{
+    DivergenceOperator {
+        confirm: Confirm::new(mode),
+        lookback: lookback.max(1),
+        hysteresis: T::default(),
+        persistence: 1,
+        bars: VecDeque::with_capacity(lookback.max(1) + 1),
+        streak: Streak::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndicatorIteratorExt, Period};
+    use time::macros::datetime;
+
+    fn run<P>(op: P, data: &[(i64, i64)]) -> Vec<Option<Divergence>>
+    where
+        P: Operator<TickValue<(i64, i64)>, Output = TickValue<Option<Divergence>>>,
+    {
+        let base = datetime!(2023-01-01 00:00:00 UTC);
+        data.iter()
+            .enumerate()
+            .map(|(idx, x)| TickValue::new(base + time::Duration::seconds(idx as i64), *x))
+            .indicator(op)
+            .map(|x| x.value)
+            .collect()
+    }
+
+    #[test]
+    fn basic() {
+        let events = run(
+            divergence(Period::secs(1), 3),
+            &[
+                (10, 50),
+                (8, 30),
+                (9, 40),
+                (7, 35),
+                (12, 60),
+                (13, 55),
+                (0, 0),
+            ],
+        );
+        assert_eq!(
+            events,
+            [
+                None,
+                None,
+                None,
+                None,
+                Some(Divergence::Bullish),
+                None,
+                Some(Divergence::Bearish),
+            ]
+        );
+    }
+
+    #[test]
+    fn hysteresis_and_persistence() {
+        let data = [(10, 50), (8, 30), (7, 33), (6, 40), (0, 0)];
+        let events = run(divergence(Period::secs(1), 3).hysteresis(5), &data);
+        assert_eq!(events, [None, None, None, None, Some(Divergence::Bullish)]);
+        let events = run(divergence(Period::secs(1), 3).persistence(2), &data);
+        assert_eq!(events, [None, None, None, None, Some(Divergence::Bullish)]);
+        let events = run(divergence(Period::secs(1), 3), &data);
+        assert_eq!(events, [None, None, None, Some(Divergence::Bullish), None]);
+    }
+}
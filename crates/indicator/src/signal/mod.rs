@@ -0,0 +1,115 @@
+use core::ops::Sub;
+
+use crate::{Tick, TumblingWindow};
+
+/// Crossover detector.
+pub mod cross;
+
+/// Threshold detector.
+pub mod threshold;
+
+/// Divergence detector.
+#[cfg(feature = "alloc")]
+pub mod divergence;
+
+pub use cross::{cross, CrossOver};
+#[cfg(feature = "alloc")]
+pub use divergence::{divergence, Divergence, DivergenceOperator};
+pub use threshold::{threshold, Threshold};
+
+/// A crossing event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Cross {
+    /// Crossed above.
+    Above,
+    /// Crossed below.
+    Below,
+}
+
+impl Cross {
+    /// Classify the difference with the given hysteresis band.
+    ///
+    /// Return `None` if the difference is inside the band.
+    pub(crate) fn classify<T>(diff: T, hysteresis: T) -> Option<Self>
+    where
+        T: Copy + PartialOrd + Sub<Output = T> + Default,
+    {
+        if diff > hysteresis {
+            Some(Self::Above)
+        } else if T::default() - diff > hysteresis {
+            Some(Self::Below)
+        } else {
+            None
+        }
+    }
+}
+
+/// Keep the last value of the current bar and release it
+/// once a new bar starts (i.e. the bar is confirmed).
+#[derive(Debug, Clone)]
+pub(crate) struct Confirm<M, T> {
+    mode: M,
+    pending: Option<(Tick, T)>,
+}
+
+impl<M: TumblingWindow, T> Confirm<M, T> {
+    pub(crate) fn new(mode: M) -> Self {
+        Self {
+            mode,
+            pending: None,
+        }
+    }
+
+    /// Drop the pending value.
+    pub(crate) fn reset(&mut self) {
+        self.pending = None;
+    }
+
+    /// Push a new value, return the value of the last bar if it is confirmed.
+    pub(crate) fn push(&mut self, tick: Tick, value: T) -> Option<T> {
+        match self.pending.take() {
+            Some((last, _)) if self.mode.same_window(&last, &tick) => {
+                self.pending = Some((last, value));
+                None
+            }
+            confirmed => {
+                self.pending = Some((tick, value));
+                confirmed.map(|(_, value)| value)
+            }
+        }
+    }
+}
+
+/// Count the consecutive observations of the same state.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Streak<S> {
+    state: Option<S>,
+    count: usize,
+}
+
+impl<S> Default for Streak<S> {
+    fn default() -> Self {
+        Self {
+            state: None,
+            count: 0,
+        }
+    }
+}
+
+impl<S: Copy + PartialEq> Streak<S> {
+    /// Observe a state and return the length of the current streak.
+    pub(crate) fn observe(&mut self, state: Option<S>) -> usize {
+        if state.is_some() && state == self.state {
+            self.count += 1;
+        } else {
+            self.state = state;
+            self.count = usize::from(state.is_some());
+        }
+        self.count
+    }
+
+    /// Reset the streak.
+    pub(crate) fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
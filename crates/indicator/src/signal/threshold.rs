@@ -0,0 +1,118 @@
+use core::ops::Sub;
+
+use crate::{
+    reset::{Reset, Seed},
+    Operator, TickValue, Tickable, TumblingWindow,
+};
+
+use super::{cross, Cross, CrossOver};
+
+/// Operator that detects the crossings of a series over a fixed level.
+///
+/// Created by [`threshold`].
+#[derive(Debug, Clone)]
+pub struct Threshold<M, T> {
+    level: T,
+    inner: CrossOver<M, T>,
+}
+
+impl<M, T> Threshold<M, T>
+where
+    T: Default,
+{
+    /// Only consider the series to be above (below) the level
+    /// if their difference is greater than `hysteresis`.
+    pub fn hysteresis(mut self, hysteresis: T) -> Self {
+        self.inner = self.inner.hysteresis(hysteresis);
+        self
+    }
+
+    /// Require the new state to hold for at least `bars` confirmed bars
+    /// before emitting the event.
+    pub fn persistence(mut self, bars: usize) -> Self {
+        self.inner = self.inner.persistence(bars);
+        self
+    }
+}
+
+impl<M, I, T> Operator<I> for Threshold<M, T>
+where
+    M: TumblingWindow,
+    I: Tickable<Value = T>,
+    T: Copy + PartialOrd + Sub<Output = T> + Default,
+{
+    type Output = TickValue<Option<Cross>>;
+
+    fn next(&mut self, input: I) -> Self::Output {
+        let input = input.into_tick_value().map(|x| (x, self.level));
+        self.inner.next(input)
+    }
+}
+
+impl<M: TumblingWindow, T> Reset for Threshold<M, T> {
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+/// Seed with the current side of the series.
+impl<M: TumblingWindow, T> Seed<Cross> for Threshold<M, T> {
+    fn seed(&mut self, seed: Cross) {
+        self.inner.seed(seed);
+    }
+}
+
+/// Create an operator that detects the crossings of a series over `level`.
+///
+/// The events are only emitted on confirmed bars,
+/// that is, when the first event of the next window (defined by `mode`) arrives.
+/// ```
+/// use indicator::{signal::{threshold, Cross}, *};
+///
+/// fn exits_oversold<I>(period: Period) -> impl Operator<I, Output = TickValue<bool>>
+/// where
+///     I: Tickable<Value = f64>,
+/// {
+///     threshold(period, 30.0)
+///         .hysteresis(1.0)
+///         .map_t(|event| event == Some(Cross::Above))
+/// }
+/// ```
+pub fn threshold<M, T>(mode: M, level: T) -> Threshold<M, T>
+where
+    M: TumblingWindow,
+    T: Default,
+{
+    Threshold {
+        level,
+        inner: cross(mode),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IndicatorIteratorExt, Period};
+    use time::{macros::datetime, UtcOffset};
+
+    #[test]
+    fn basic() {
+        let base = datetime!(2023-01-01 00:00:00 UTC);
+        let expected = [
+            None,
+            None,
+            None,
+            None,
+            Some(Cross::Above),
+            None,
+            Some(Cross::Below),
+        ];
+        [25, 29, 31, 35, 30, 28, 27]
+            .into_iter()
+            .enumerate()
+            .map(|(idx, x)| TickValue::new(base + time::Duration::minutes(idx as i64), x))
+            .indicator(threshold(Period::minutes(UtcOffset::UTC, 1), 30).hysteresis(1))
+            .zip(expected)
+            .for_each(|(event, expected)| assert_eq!(event.value, expected));
+    }
+}
@@ -0,0 +1,92 @@
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Schema of the context entries to save.
+#[cfg(feature = "context")]
+pub mod schema;
+
+#[cfg(feature = "context")]
+pub use schema::Schema;
+
+/// Operator whose state can be saved and restored.
+///
+/// The state of the user-provided closures is not included,
+/// they are assumed to be stateless.
+pub trait Snapshot {
+    /// The state.
+    type State: Serialize + DeserializeOwned;
+
+    /// Take a snapshot of the current state.
+    fn snapshot(&self) -> Self::State;
+
+    /// Restore the state from a snapshot.
+    fn restore(&mut self, state: Self::State);
+}
+
+impl<P> Snapshot for &mut P
+where
+    P: Snapshot + ?Sized,
+{
+    type State = P::State;
+
+    fn snapshot(&self) -> Self::State {
+        (**self).snapshot()
+    }
+
+    fn restore(&mut self, state: Self::State) {
+        (**self).restore(state)
+    }
+}
+
+impl<P> Snapshot for alloc::boxed::Box<P>
+where
+    P: Snapshot + ?Sized,
+{
+    type State = P::State;
+
+    fn snapshot(&self) -> Self::State {
+        self.as_ref().snapshot()
+    }
+
+    fn restore(&mut self, state: Self::State) {
+        self.as_mut().restore(state)
+    }
+}
+
+#[cfg(all(test, feature = "array-vec"))]
+mod tests {
+    use super::*;
+    use crate::{
+        facet_t, map_t, tumbling, IndicatorIteratorExt, Operator, OperatorExt, Period, TickValue,
+    };
+    use arrayvec::ArrayVec;
+    use time::{macros::datetime, Duration};
+
+    fn op() -> impl Operator<TickValue<i64>, Output = TickValue<(i64, i64)>> + Snapshot {
+        tumbling(
+            Period::secs(2),
+            |q: &ArrayVec<i64, 2>, y: &mut Option<i64>, x| {
+                *y.get_or_insert(0) += x;
+                q.iter().sum::<i64>() + y.unwrap()
+            },
+        )
+        .then(facet_t(map_t(|x| x), map_t(|x: i64| -x)))
+        .map(|x| x)
+    }
+
+    #[test]
+    fn resume() {
+        let base = datetime!(2023-01-01 00:00:00 UTC);
+        let data = (0..10)
+            .map(|x| TickValue::new(base + Duration::seconds(x), x))
+            .collect::<Vec<_>>();
+        let expected = data.iter().copied().indicator(op()).collect::<Vec<_>>();
+
+        let mut first = op();
+        let mut outputs = data[..5].iter().map(|x| first.next(*x)).collect::<Vec<_>>();
+        let json = serde_json::to_string(&first.snapshot()).unwrap();
+        let mut second = op();
+        second.restore(serde_json::from_str(&json).unwrap());
+        outputs.extend(data[5..].iter().copied().indicator(second));
+        assert_eq!(outputs, expected);
+    }
+}
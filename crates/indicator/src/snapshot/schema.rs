@@ -0,0 +1,59 @@
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::context::Map;
+
+/// A list of types to save from a context [`Map`].
+///
+/// It is implemented for `()` and the tuples of types (up to 8 elements).
+pub trait Schema {
+    /// The saved entries.
+    type State: Serialize + DeserializeOwned;
+
+    /// Save the entries of the listed types.
+    fn save(map: &Map) -> Self::State;
+
+    /// Load the saved entries into the map.
+    fn load(map: &mut Map, state: Self::State);
+}
+
+impl Schema for () {
+    type State = ();
+
+    fn save(_map: &Map) -> Self::State {}
+
+    fn load(_map: &mut Map, _state: Self::State) {}
+}
+
+macro_rules! impl_schema {
+    ($($t:ident),+) => {
+        impl<$($t),+> Schema for ($($t,)+)
+        where
+            $($t: Clone + Serialize + DeserializeOwned + Send + Sync + 'static),+
+        {
+            type State = ($(Option<$t>,)+);
+
+            fn save(map: &Map) -> Self::State {
+                ($(map.get::<$t>().cloned(),)+)
+            }
+
+            #[allow(non_snake_case)]
+            fn load(map: &mut Map, state: Self::State) {
+                let ($($t,)+) = state;
+                $(
+                    if let Some(value) = $t {
+                        map.insert(value);
+                    }
+                )+
+            }
+        }
+    };
+}
+
+impl_schema!(T1);
+impl_schema!(T1, T2);
+impl_schema!(T1, T2, T3);
+impl_schema!(T1, T2, T3, T4);
+impl_schema!(T1, T2, T3, T4, T5);
+impl_schema!(T1, T2, T3, T4, T5, T6);
+impl_schema!(T1, T2, T3, T4, T5, T6, T7);
+impl_schema!(T1, T2, T3, T4, T5, T6, T7, T8);
@@ -1,4 +1,10 @@
-use crate::Operator;
+use crate::{
+    flat::FlatOperator,
+    readiness::{Marked, Readiness},
+    try_operator::TryOperator,
+    Operator,
+};
+use alloc::collections::VecDeque;
 use futures::task::{Context, Poll};
 use futures::{ready, Stream};
 use pin_project_lite::pin_project;
@@ -34,6 +40,184 @@ where
     }
 }
 
+pin_project! {
+    /// Flat-operated.
+    ///
+    /// The outputs of an input are queued, reusing the queue across the inputs.
+    #[derive(Debug, Clone)]
+    pub struct FlatOperated<St, P, O> {
+        #[pin]
+        source: St,
+        op: P,
+        pending: VecDeque<O>,
+    }
+}
+
+impl<St, P> Stream for FlatOperated<St, P, P::Item>
+where
+    St: Stream,
+    P: FlatOperator<St::Item>,
+{
+    type Item = P::Item;
+
+    fn poll_next(
+        self: core::pin::Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            if let Some(output) = this.pending.pop_front() {
+                return Poll::Ready(Some(output));
+            }
+            match ready!(this.source.as_mut().poll_next(cx)) {
+                Some(input) => {
+                    let pending = &mut *this.pending;
+                    this.op
+                        .for_each(input, &mut |output| pending.push_back(output));
+                }
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+pin_project! {
+    /// Try-operated, stopping after the first error.
+    #[derive(Debug, Clone, Copy)]
+    pub struct TryOperated<St, P> {
+        #[pin]
+        source: St,
+        op: P,
+        done: bool,
+    }
+}
+
+impl<St, P> Stream for TryOperated<St, P>
+where
+    St: Stream,
+    P: TryOperator<St::Item>,
+{
+    type Item = Result<P::Output, P::Error>;
+
+    fn poll_next(
+        self: core::pin::Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        if *this.done {
+            return Poll::Ready(None);
+        }
+        let res = ready!(this.source.as_mut().poll_next(cx)).map(|x| this.op.try_next(x));
+        *this.done = !matches!(res, Some(Ok(_)));
+        Poll::Ready(res)
+    }
+}
+
+pin_project! {
+    /// Try-operated, skipping the errors.
+    #[derive(Debug, Clone, Copy)]
+    pub struct SkipErrOperated<St, P> {
+        #[pin]
+        source: St,
+        op: P,
+    }
+}
+
+impl<St, P> Stream for SkipErrOperated<St, P>
+where
+    St: Stream,
+    P: TryOperator<St::Item>,
+{
+    type Item = P::Output;
+
+    fn poll_next(
+        self: core::pin::Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            match ready!(this.source.as_mut().poll_next(cx)) {
+                Some(input) => {
+                    if let Ok(output) = this.op.try_next(input) {
+                        return Poll::Ready(Some(output));
+                    }
+                }
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+pin_project! {
+    /// Marked-operated, marking the outputs with the readiness of the operator.
+    #[derive(Debug, Clone, Copy)]
+    pub struct MarkedOperated<St, P> {
+        #[pin]
+        source: St,
+        op: P,
+    }
+}
+
+impl<St, P> Stream for MarkedOperated<St, P>
+where
+    St: Stream,
+    P: Operator<St::Item> + Readiness,
+{
+    type Item = Marked<P::Output>;
+
+    fn poll_next(
+        self: core::pin::Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        let res = ready!(this.source.as_mut().poll_next(cx)).map(|x| {
+            let output = this.op.next(x);
+            Marked::new(output, this.op.is_ready())
+        });
+        Poll::Ready(res)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.source.size_hint()
+    }
+}
+
+pin_project! {
+    /// Warmed-operated, skipping the outputs produced before the operator is ready.
+    #[derive(Debug, Clone, Copy)]
+    pub struct WarmedOperated<St, P> {
+        #[pin]
+        source: St,
+        op: P,
+    }
+}
+
+impl<St, P> Stream for WarmedOperated<St, P>
+where
+    St: Stream,
+    P: Operator<St::Item> + Readiness,
+{
+    type Item = P::Output;
+
+    fn poll_next(
+        self: core::pin::Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            match ready!(this.source.as_mut().poll_next(cx)) {
+                Some(input) => {
+                    let output = this.op.next(input);
+                    if this.op.is_ready() {
+                        return Poll::Ready(Some(output));
+                    }
+                }
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
 #[cfg(feature = "async")]
 /// Async version of [`Operated`].
 pub mod async_operated {
@@ -98,6 +282,59 @@ pub trait IndicatorStreamExt: Stream {
         Operated { source: self, op }
     }
 
+    /// Apply a [`FlatOperator`] on the stream, flattening the outputs.
+    fn flat_indicator<P>(self, op: P) -> FlatOperated<Self, P, P::Item>
+    where
+        Self: Sized,
+        P: FlatOperator<Self::Item>,
+    {
+        FlatOperated {
+            source: self,
+            op,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Apply a [`TryOperator`] on the stream, stopping after the first error.
+    fn try_indicator<P>(self, op: P) -> TryOperated<Self, P>
+    where
+        Self: Sized,
+        P: TryOperator<Self::Item>,
+    {
+        TryOperated {
+            source: self,
+            op,
+            done: false,
+        }
+    }
+
+    /// Apply a [`TryOperator`] on the stream, skipping the errors.
+    fn skip_err_indicator<P>(self, op: P) -> SkipErrOperated<Self, P>
+    where
+        Self: Sized,
+        P: TryOperator<Self::Item>,
+    {
+        SkipErrOperated { source: self, op }
+    }
+
+    /// Apply an [`Operator`] on the stream, marking the outputs with its readiness.
+    fn marked_indicator<P>(self, op: P) -> MarkedOperated<Self, P>
+    where
+        Self: Sized,
+        P: Operator<Self::Item> + Readiness,
+    {
+        MarkedOperated { source: self, op }
+    }
+
+    /// Apply an [`Operator`] on the stream, skipping the outputs produced before it is ready.
+    fn warmed_indicator<P>(self, op: P) -> WarmedOperated<Self, P>
+    where
+        Self: Sized,
+        P: Operator<Self::Item> + Readiness,
+    {
+        WarmedOperated { source: self, op }
+    }
+
     #[cfg(feature = "async")]
     /// Apply an [`AsyncOperator`] on the stream.
     fn async_indicator<P>(self, op: P) -> async_operated::Operated<Self, P>
@@ -1,4 +1,7 @@
-use crate::{Operator, TickValue, Tickable};
+use crate::{
+    reset::{Reset, Seed},
+    Operator, TickValue, Tickable,
+};
 use arrayvec::ArrayVec;
 
 /// [`Array`] combinator.
@@ -29,6 +32,26 @@ where
     }
 }
 
+impl<I, P, const LEN: usize> Reset for Array<I, P, LEN>
+where
+    P: Reset,
+{
+    fn reset(&mut self) {
+        self.ops.iter_mut().for_each(P::reset);
+    }
+}
+
+impl<I, S, P, const LEN: usize> Seed<[S; LEN]> for Array<I, P, LEN>
+where
+    P: Seed<S>,
+{
+    fn seed(&mut self, seed: [S; LEN]) {
+        for (op, s) in self.ops.iter_mut().zip(seed) {
+            op.seed(s);
+        }
+    }
+}
+
 /// Apply a ticked operator on an array input to get an array of output.
 /// ```
 /// use indicator::*;
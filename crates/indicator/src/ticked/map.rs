@@ -1,4 +1,13 @@
-use crate::{Operator, TickValue, Tickable};
+use alloc::vec::Vec;
+
+use crate::{
+    readiness::Readiness,
+    reset::{Reset, Seed},
+    Operator, TickValue, Tickable,
+};
+
+#[cfg(feature = "snapshot")]
+use crate::snapshot::Snapshot;
 
 /// [`Map`] operator for [`map_t`].
 #[derive(Debug, Clone, Copy)]
@@ -26,4 +35,51 @@ where
             value: (self.f)(value),
         }
     }
+
+    fn next_batch(&mut self, inputs: &[I], outputs: &mut Vec<Self::Output>)
+    where
+        I: Clone,
+    {
+        outputs.extend(
+            inputs
+                .iter()
+                .cloned()
+                .map(|input| input.into_tick_value().map(&mut self.f)),
+        );
+    }
+
+    fn drain_batch(&mut self, inputs: &mut Vec<I>, outputs: &mut Vec<Self::Output>) {
+        outputs.extend(
+            inputs
+                .drain(..)
+                .map(|input| input.into_tick_value().map(&mut self.f)),
+        );
+    }
+}
+
+impl<F> Readiness for Map<F> {
+    fn warm_up(&self) -> usize {
+        0
+    }
+
+    fn is_ready(&self) -> bool {
+        true
+    }
+}
+
+impl<F> Reset for Map<F> {
+    fn reset(&mut self) {}
+}
+
+impl<F> Seed<()> for Map<F> {
+    fn seed(&mut self, _seed: ()) {}
+}
+
+#[cfg(feature = "snapshot")]
+impl<F> Snapshot for Map<F> {
+    type State = ();
+
+    fn snapshot(&self) -> Self::State {}
+
+    fn restore(&mut self, _state: Self::State) {}
 }
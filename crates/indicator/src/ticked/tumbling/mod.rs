@@ -7,7 +7,11 @@ pub mod cached;
 /// Iterated operation.
 pub mod iterated;
 
-use crate::{Operator, TickValue, Tickable, TumblingWindow};
+use crate::{
+    readiness::Readiness,
+    reset::{Reset, Seed},
+    Operator, TickValue, Tickable, TumblingWindow,
+};
 pub use cached::{cached, Cached, CachedOperation};
 pub use iterated::{iterated, Iterated, IteratedOperation};
 pub use queue::{QueueCapAtLeast, TumblingQueue};
@@ -15,6 +19,13 @@ pub use queue::{QueueCapAtLeast, TumblingQueue};
 #[cfg(feature = "std")]
 pub use cached::shared_map::{shared, SharedMap};
 
+#[cfg(feature = "snapshot")]
+use crate::snapshot::Snapshot;
+#[cfg(feature = "snapshot")]
+pub use queue::TumblingQueueState;
+#[cfg(feature = "snapshot")]
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
 /// Tumbling operation.
 pub trait TumblingOperation<I, Q: QueueCapAtLeast<LEN>, const LEN: usize> {
     /// Output type.
@@ -72,6 +83,78 @@ impl<
     }
 }
 
+/// The operator is ready when the queue holds `LEN` windows.
+impl<M: TumblingWindow, Q: QueueCapAtLeast<LEN>, P, const LEN: usize> Readiness
+    for TumblingOperator<M, Q, P, LEN>
+{
+    fn warm_up(&self) -> usize {
+        LEN
+    }
+
+    fn is_ready(&self) -> bool {
+        self.queue.queue.is_reach()
+    }
+}
+
+impl<M: TumblingWindow, Q: QueueCapAtLeast<LEN>, P, const LEN: usize> Reset
+    for TumblingOperator<M, Q, P, LEN>
+{
+    fn reset(&mut self) {
+        self.queue.reset();
+        self.acc = None;
+    }
+}
+
+/// Seed with the items of the completed windows, from the oldest to the latest.
+///
+/// The next input will start a new window.
+impl<M, Q, P, H, const LEN: usize> Seed<H> for TumblingOperator<M, Q, P, LEN>
+where
+    M: TumblingWindow,
+    Q: QueueCapAtLeast<LEN>,
+    H: IntoIterator<Item = Q::Item>,
+{
+    fn seed(&mut self, seed: H) {
+        self.reset();
+        for item in seed {
+            self.queue.queue.enque_and_deque_overflow(item);
+        }
+    }
+}
+
+/// The state of a [`TumblingOperator`].
+#[cfg(feature = "snapshot")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TumblingOperatorState<T> {
+    /// The state of the queue.
+    pub queue: TumblingQueueState<T>,
+    /// The item of the current window.
+    pub acc: Option<T>,
+}
+
+/// The operation is assumed stateless and is not included in the snapshot.
+#[cfg(feature = "snapshot")]
+impl<M, Q, P, const LEN: usize> Snapshot for TumblingOperator<M, Q, P, LEN>
+where
+    M: TumblingWindow,
+    Q: QueueCapAtLeast<LEN>,
+    Q::Item: Clone + Serialize + DeserializeOwned,
+{
+    type State = TumblingOperatorState<Q::Item>;
+
+    fn snapshot(&self) -> Self::State {
+        TumblingOperatorState {
+            queue: self.queue.snapshot(),
+            acc: self.acc.clone(),
+        }
+    }
+
+    fn restore(&mut self, state: Self::State) {
+        self.queue.restore(state.queue);
+        self.acc = state.acc;
+    }
+}
+
 /// Create a tumbling operator from a tumbling operation.
 pub fn tumbling<I, P, Q: QueueCapAtLeast<LEN>, M: TumblingWindow, const LEN: usize>(
     mode: M,
@@ -4,6 +4,13 @@ pub mod arrayvec;
 
 use crate::{Tick, TumblingWindow};
 
+#[cfg(feature = "snapshot")]
+use crate::snapshot::Snapshot;
+#[cfg(feature = "snapshot")]
+use alloc::vec::Vec;
+#[cfg(feature = "snapshot")]
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
 /// Queue that can hold at least `LEN` items.
 pub trait QueueCapAtLeast<const LEN: usize> {
     /// Item type.
@@ -71,6 +78,12 @@ impl<M: TumblingWindow, Q: QueueCapAtLeast<LEN>, const LEN: usize> TumblingQueue
         }
     }
 
+    /// Clear the queue, the next input will start a new window.
+    pub(crate) fn reset(&mut self) {
+        self.last_tick = Tick::BIG_BANG;
+        self.queue = Q::empty();
+    }
+
     /// Push or ignore.
     pub(crate) fn enque_or_ignore(
         &mut self,
@@ -89,3 +102,41 @@ impl<M: TumblingWindow, Q: QueueCapAtLeast<LEN>, const LEN: usize> TumblingQueue
         }
     }
 }
+
+/// The state of a [`TumblingQueue`].
+#[cfg(feature = "snapshot")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TumblingQueueState<T> {
+    /// The tick of the last window.
+    pub last_tick: Tick,
+    /// The items, from the oldest to the latest.
+    pub items: Vec<T>,
+}
+
+#[cfg(feature = "snapshot")]
+impl<M, Q, const LEN: usize> Snapshot for TumblingQueue<M, Q, LEN>
+where
+    M: TumblingWindow,
+    Q: QueueCapAtLeast<LEN>,
+    Q::Item: Clone + Serialize + DeserializeOwned,
+{
+    type State = TumblingQueueState<Q::Item>;
+
+    fn snapshot(&self) -> Self::State {
+        TumblingQueueState {
+            last_tick: self.last_tick,
+            items: (0..self.queue.len())
+                .rev()
+                .filter_map(|n| self.queue.get_latest(n).cloned())
+                .collect(),
+        }
+    }
+
+    fn restore(&mut self, state: Self::State) {
+        self.last_tick = state.last_tick;
+        self.queue = Q::empty();
+        for item in state.items {
+            self.queue.enque_and_deque_overflow(item);
+        }
+    }
+}
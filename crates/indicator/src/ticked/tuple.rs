@@ -1,4 +1,11 @@
-use crate::{Operator, TickValue, Tickable};
+use crate::{
+    readiness::Readiness,
+    reset::{Reset, Seed},
+    Operator, TickValue, Tickable,
+};
+
+#[cfg(feature = "snapshot")]
+use crate::snapshot::Snapshot;
 
 /// [`Tuple`] combinator.
 #[derive(Debug, Clone, Copy)]
@@ -57,3 +64,57 @@ where
 {
     Tuple(p1, p2, core::marker::PhantomData)
 }
+
+impl<I, P1, P2> Readiness for Tuple<I, P1, P2>
+where
+    P1: Readiness,
+    P2: Readiness,
+{
+    fn warm_up(&self) -> usize {
+        self.0.warm_up().max(self.1.warm_up())
+    }
+
+    fn is_ready(&self) -> bool {
+        self.0.is_ready() && self.1.is_ready()
+    }
+}
+
+impl<I, P1, P2> Reset for Tuple<I, P1, P2>
+where
+    P1: Reset,
+    P2: Reset,
+{
+    fn reset(&mut self) {
+        self.0.reset();
+        self.1.reset();
+    }
+}
+
+impl<I, S1, S2, P1, P2> Seed<(S1, S2)> for Tuple<I, P1, P2>
+where
+    P1: Seed<S1>,
+    P2: Seed<S2>,
+{
+    fn seed(&mut self, seed: (S1, S2)) {
+        self.0.seed(seed.0);
+        self.1.seed(seed.1);
+    }
+}
+
+#[cfg(feature = "snapshot")]
+impl<I, P1, P2> Snapshot for Tuple<I, P1, P2>
+where
+    P1: Snapshot,
+    P2: Snapshot,
+{
+    type State = (P1::State, P2::State);
+
+    fn snapshot(&self) -> Self::State {
+        (self.0.snapshot(), self.1.snapshot())
+    }
+
+    fn restore(&mut self, state: Self::State) {
+        self.0.restore(state.0);
+        self.1.restore(state.1);
+    }
+}
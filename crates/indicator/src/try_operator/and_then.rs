@@ -0,0 +1,50 @@
+use core::marker::PhantomData;
+
+use crate::{
+    reset::{Reset, Seed},
+    Operator,
+};
+
+use super::TryOperator;
+
+/// [`AndThen`] combinator.
+#[derive(Debug, Clone, Copy)]
+pub struct AndThen<I, P1, P2>(
+    pub(super) P1,
+    pub(super) P2,
+    pub(super) PhantomData<fn() -> I>,
+);
+
+impl<I, E, P1, P2> Operator<I> for AndThen<I, P1, P2>
+where
+    P1: TryOperator<I, Error = E>,
+    P2: TryOperator<P1::Output, Error = E>,
+{
+    type Output = Result<P2::Output, E>;
+
+    fn next(&mut self, input: I) -> Self::Output {
+        self.1.try_next(self.0.try_next(input)?)
+    }
+}
+
+impl<I, P1, P2> Reset for AndThen<I, P1, P2>
+where
+    P1: Reset,
+    P2: Reset,
+{
+    fn reset(&mut self) {
+        self.0.reset();
+        self.1.reset();
+    }
+}
+
+impl<I, S1, S2, P1, P2> Seed<(S1, S2)> for AndThen<I, P1, P2>
+where
+    P1: Seed<S1>,
+    P2: Seed<S2>,
+{
+    fn seed(&mut self, seed: (S1, S2)) {
+        self.0.seed(seed.0);
+        self.1.seed(seed.1);
+    }
+}
@@ -0,0 +1,57 @@
+use core::marker::PhantomData;
+
+use crate::{
+    reset::{Reset, Seed},
+    Operator,
+};
+
+use super::TryOperator;
+
+/// [`TryFacet`] combinator.
+#[derive(Debug, Clone, Copy)]
+pub struct TryFacet<I, P1, P2>(
+    pub(super) P1,
+    pub(super) P2,
+    pub(super) PhantomData<fn() -> I>,
+);
+
+/// Combine two fallible operators into a [`TryFacet`] operator.
+///
+/// The second operator is skipped if the first one fails.
+pub fn try_facet<I, P1, P2>(op1: P1, op2: P2) -> TryFacet<I, P1, P2> {
+    TryFacet(op1, op2, PhantomData)
+}
+
+impl<I: Clone, E, P1, P2> Operator<I> for TryFacet<I, P1, P2>
+where
+    P1: TryOperator<I, Error = E>,
+    P2: TryOperator<I, Error = E>,
+{
+    type Output = Result<(P1::Output, P2::Output), E>;
+
+    fn next(&mut self, input: I) -> Self::Output {
+        Ok((self.0.try_next(input.clone())?, self.1.try_next(input)?))
+    }
+}
+
+impl<I, P1, P2> Reset for TryFacet<I, P1, P2>
+where
+    P1: Reset,
+    P2: Reset,
+{
+    fn reset(&mut self) {
+        self.0.reset();
+        self.1.reset();
+    }
+}
+
+impl<I, S1, S2, P1, P2> Seed<(S1, S2)> for TryFacet<I, P1, P2>
+where
+    P1: Seed<S1>,
+    P2: Seed<S2>,
+{
+    fn seed(&mut self, seed: (S1, S2)) {
+        self.0.seed(seed.0);
+        self.1.seed(seed.1);
+    }
+}
@@ -0,0 +1,37 @@
+use crate::{
+    reset::{Reset, Seed},
+    Operator,
+};
+
+use super::TryOperator;
+
+/// Convert Error.
+#[derive(Debug, Clone, Copy)]
+pub struct MapErr<F, P> {
+    pub(super) inner: P,
+    pub(super) f: F,
+}
+
+impl<I, E, F, P> Operator<I> for MapErr<F, P>
+where
+    P: TryOperator<I>,
+    F: FnMut(P::Error) -> E,
+{
+    type Output = Result<P::Output, E>;
+
+    fn next(&mut self, input: I) -> Self::Output {
+        self.inner.try_next(input).map_err(&mut self.f)
+    }
+}
+
+impl<F, P: Reset> Reset for MapErr<F, P> {
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+impl<S, F, P: Seed<S>> Seed<S> for MapErr<F, P> {
+    fn seed(&mut self, seed: S) {
+        self.inner.seed(seed);
+    }
+}
@@ -0,0 +1,124 @@
+use crate::Operator;
+use core::marker::PhantomData;
+
+/// And then.
+pub mod and_then;
+
+/// Map error.
+pub mod map_err;
+
+/// Short-circuiting facet.
+pub mod facet;
+
+pub use and_then::AndThen;
+pub use facet::{try_facet, TryFacet};
+pub use map_err::MapErr;
+
+/// Fallible operator.
+///
+/// It is implemented for every [`Operator`] whose output is a [`Result`].
+pub trait TryOperator<I> {
+    /// Output type.
+    type Output;
+
+    /// Error type.
+    type Error;
+
+    /// Try to produce the next output.
+    fn try_next(&mut self, input: I) -> Result<Self::Output, Self::Error>;
+}
+
+impl<I, T, E, P> TryOperator<I> for P
+where
+    P: Operator<I, Output = Result<T, E>> + ?Sized,
+{
+    type Output = T;
+
+    type Error = E;
+
+    fn try_next(&mut self, input: I) -> Result<Self::Output, Self::Error> {
+        self.next(input)
+    }
+}
+
+/// Extension trait for fallible operators.
+pub trait TryOperatorExt<I>: TryOperator<I> {
+    /// Combine with another fallible operator that uses `Self::Output` as input type.
+    ///
+    /// The `other` operator is skipped if `self` fails.
+    fn and_then<P2>(self, other: P2) -> AndThen<I, Self, P2>
+    where
+        Self: Sized,
+        P2: TryOperator<Self::Output, Error = Self::Error>,
+    {
+        AndThen(self, other, PhantomData)
+    }
+
+    /// Convert error.
+    fn map_err<E, F>(self, f: F) -> MapErr<F, Self>
+    where
+        Self: Sized,
+        F: FnMut(Self::Error) -> E,
+    {
+        MapErr { inner: self, f }
+    }
+
+    /// Combine with another fallible operator with the same input type.
+    ///
+    /// The `other` operator is skipped if `self` fails.
+    fn try_facet<P2>(self, other: P2) -> TryFacet<I, Self, P2>
+    where
+        Self: Sized,
+        P2: TryOperator<I, Error = Self::Error>,
+    {
+        try_facet(self, other)
+    }
+}
+
+impl<I, P> TryOperatorExt<I> for P where P: TryOperator<I> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{map, IndicatorIteratorExt, OperatorExt};
+
+    fn parse() -> impl Operator<&'static str, Output = Result<i64, &'static str>> {
+        map(|x: &str| x.parse::<i64>().map_err(|_| "malformed"))
+    }
+
+    #[test]
+    fn and_then() {
+        let mut op = parse().and_then(map(|x: i64| if x > 0 { Ok(x) } else { Err("negative") }));
+        assert_eq!(op.try_next("1"), Ok(1));
+        assert_eq!(op.try_next("x"), Err("malformed"));
+        assert_eq!(op.try_next("-1"), Err("negative"));
+    }
+
+    #[test]
+    fn facet() {
+        let mut count = 0;
+        let mut op = parse()
+            .map_err(|_| 0)
+            .try_facet(map(|_: &str| {
+                count += 1;
+                Ok(count)
+            }))
+            .map(|x| x.map(|(x, count)| x * count));
+        assert_eq!(op.next("2"), Ok(2));
+        assert_eq!(op.next("x"), Err(0));
+        assert_eq!(op.next("3"), Ok(6));
+    }
+
+    #[test]
+    fn iter() {
+        let data = ["1", "x", "3"];
+        let mut stopped = data.into_iter().try_indicator(parse());
+        assert_eq!(stopped.next(), Some(Ok(1)));
+        assert_eq!(stopped.next(), Some(Err("malformed")));
+        assert_eq!(stopped.next(), None);
+        let mut skipped = data.into_iter().skip_err_indicator(parse());
+        assert_eq!(skipped.next(), Some(1));
+        assert_eq!(skipped.next(), Some(3));
+        assert_eq!(skipped.next(), None);
+    }
+}
@@ -33,11 +33,9 @@ impl Hash for PeriodKind {
         match self {
             Self::Year => {
                 state.write(&[0x00]);
-                state.finish();
             }
             Self::Month => {
                 state.write(&[0x01]);
-                state.finish();
             }
             Self::Duration(d) => {
                 state.write(&[0x02]);
@@ -88,6 +88,43 @@ impl<T> Borrow<T> for TickValue<T> {
     }
 }
 
+impl<C: IntoIterator> IntoIterator for TickValue<C> {
+    type Item = TickValue<C::Item>;
+
+    type IntoIter = IntoIter<C::IntoIter>;
+
+    /// Iterate over the values, each of them with the same tick.
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            tick: self.tick,
+            inner: self.value.into_iter(),
+        }
+    }
+}
+
+/// Iterator over the values of a [`TickValue`], each of them with the same tick.
+#[derive(Debug, Clone)]
+pub struct IntoIter<I> {
+    tick: Tick,
+    inner: I,
+}
+
+impl<I: Iterator> Iterator for IntoIter<I> {
+    type Item = TickValue<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.inner.next()?;
+        Some(TickValue {
+            tick: self.tick,
+            value,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,6 +137,21 @@ mod tests {
         assert_eq!(tick_value.into_tick_value(), tick_value);
     }
 
+    #[test]
+    fn test_into_iter() {
+        let tick_value = TickValue::new(OffsetDateTime::UNIX_EPOCH, [1, 2]);
+        let mut iter = tick_value.into_iter();
+        assert_eq!(
+            iter.next(),
+            Some(TickValue::new(OffsetDateTime::UNIX_EPOCH, 1))
+        );
+        assert_eq!(
+            iter.next(),
+            Some(TickValue::new(OffsetDateTime::UNIX_EPOCH, 2))
+        );
+        assert_eq!(iter.next(), None);
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn serialize_tick_value() {
@@ -0,0 +1,213 @@
+use std::path::{Path, PathBuf};
+
+use indicator::{
+    io::{Mapping, TimestampFormat},
+    pipeline::PipelineConfig,
+};
+use serde::Deserialize;
+
+/// Pipeline configuration.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Config {
+    /// Input.
+    #[serde(default)]
+    pub input: Input,
+    /// Output.
+    #[serde(default)]
+    pub output: Output,
+    /// The nodes of the pipeline, and its outputs written as columns in order.
+    #[serde(flatten)]
+    pub pipeline: PipelineConfig,
+}
+
+impl Config {
+    /// Load the config from a TOML or JSON file (by extension).
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        match extension(path) {
+            Some("json") => Ok(serde_json::from_str(&text)?),
+            _ => Ok(toml::from_str(&text)?),
+        }
+    }
+}
+
+/// File formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    /// CSV with headers.
+    Csv,
+    /// Newline-delimited JSON.
+    Jsonl,
+}
+
+impl Format {
+    /// Resolve the format, defaulting to the extension of the path.
+    pub fn resolve(format: Option<Self>, path: Option<&Path>) -> Self {
+        format.unwrap_or(match path.and_then(extension) {
+            Some("jsonl" | "ndjson" | "json") => Self::Jsonl,
+            _ => Self::Csv,
+        })
+    }
+}
+
+fn extension(path: &Path) -> Option<&str> {
+    path.extension().and_then(|ext| ext.to_str())
+}
+
+/// Timestamp formats.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Timestamp {
+    /// RFC3339.
+    #[default]
+    Rfc3339,
+    /// Seconds since the Unix epoch.
+    UnixSecs,
+    /// Milliseconds since the Unix epoch.
+    UnixMillis,
+    /// Nanoseconds since the Unix epoch.
+    UnixNanos,
+    /// Custom format description.
+    Custom(String),
+}
+
+impl Timestamp {
+    fn build(&self) -> anyhow::Result<TimestampFormat> {
+        Ok(match self {
+            Self::Rfc3339 => TimestampFormat::Rfc3339,
+            Self::UnixSecs => TimestampFormat::UnixSecs,
+            Self::UnixMillis => TimestampFormat::UnixMillis,
+            Self::UnixNanos => TimestampFormat::UnixNanos,
+            Self::Custom(description) => TimestampFormat::custom(description)?,
+        })
+    }
+}
+
+fn ts() -> String {
+    "ts".into()
+}
+
+/// The kind of input data.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Data {
+    /// OHLCV bars, with columns `open`, `high`, `low`, `close` and optional `volume`.
+    #[default]
+    Bars,
+    /// Ticks, with columns `price` and optional `size`.
+    Ticks,
+}
+
+/// Input.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Input {
+    /// Path, read from stdin if not given.
+    pub path: Option<PathBuf>,
+    /// Format.
+    pub format: Option<Format>,
+    /// Kind of data.
+    #[serde(default)]
+    pub data: Data,
+    /// Timestamp column.
+    #[serde(default = "ts")]
+    pub timestamp: String,
+    /// Timestamp format.
+    #[serde(default)]
+    pub timestamp_format: Timestamp,
+}
+
+impl Default for Input {
+    fn default() -> Self {
+        Self {
+            path: None,
+            format: None,
+            data: Data::default(),
+            timestamp: ts(),
+            timestamp_format: Timestamp::default(),
+        }
+    }
+}
+
+impl Input {
+    /// Build the column mapping.
+    pub fn mapping(&self) -> anyhow::Result<Mapping> {
+        Ok(Mapping::new()
+            .timestamp(&self.timestamp)
+            .format(self.timestamp_format.build()?))
+    }
+}
+
+/// Output.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Output {
+    /// Path, written to stdout if not given.
+    pub path: Option<PathBuf>,
+    /// Format.
+    pub format: Option<Format>,
+    /// Timestamp column.
+    #[serde(default = "ts")]
+    pub timestamp: String,
+    /// Timestamp format.
+    #[serde(default)]
+    pub timestamp_format: Timestamp,
+}
+
+impl Default for Output {
+    fn default() -> Self {
+        Self {
+            path: None,
+            format: None,
+            timestamp: ts(),
+            timestamp_format: Timestamp::default(),
+        }
+    }
+}
+
+impl Output {
+    /// Build the column mapping.
+    pub fn mapping(&self) -> anyhow::Result<Mapping> {
+        Ok(Mapping::new()
+            .timestamp(&self.timestamp)
+            .format(self.timestamp_format.build()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let config: Config = toml::from_str(
+            r#"
+            outputs = ["sma_20", "high"]
+
+            [input]
+            path = "bars.csv"
+            timestamp_format = "unix_millis"
+
+            [[nodes]]
+            name = "sma_20"
+            type = "sma"
+            inputs = ["close"]
+            params = { length = 20 }
+            period = "1h"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.input.timestamp, "ts");
+        assert_eq!(config.output.timestamp, "ts");
+        assert_eq!(config.input.timestamp_format, Timestamp::UnixMillis);
+        assert_eq!(
+            Format::resolve(config.input.format, config.input.path.as_deref()),
+            Format::Csv
+        );
+        assert_eq!(config.pipeline.nodes[0].kind, "sma");
+        assert_eq!(config.pipeline.nodes[0].params["length"], 20);
+        assert!(config.pipeline.nodes[0].period.is_some());
+        assert_eq!(config.pipeline.outputs, ["sma_20", "high"]);
+    }
+}
@@ -0,0 +1,142 @@
+//! Run indicator pipelines over market data files.
+//!
+//! The pipeline is described by a TOML (or JSON) config, for example:
+//!
+//! ```toml
+//! outputs = ["close", "ema_12", "volume_1h"]
+//!
+//! [input]
+//! path = "bars.csv"
+//! timestamp_format = "unix_millis"
+//!
+//! [output]
+//! path = "output.jsonl"
+//!
+//! [[nodes]]
+//! name = "ema_12"
+//! type = "ema"
+//! inputs = ["close"]
+//! params = { length = 12 }
+//!
+//! [[nodes]]
+//! name = "volume_1h"
+//! type = "sum"
+//! inputs = ["volume"]
+//! params = { length = 24 }
+//! period = "1h"
+//! ```
+//!
+//! The nodes are the built-in node types of [`Registry::builtin`](indicator::pipeline::Registry::builtin),
+//! over the fields `open`, `high`, `low`, `close` and `volume` of the rows,
+//! and the `outputs` (nodes or fields) are written as columns in order.
+
+use std::{
+    io::{self, BufReader, Read, Write},
+    path::PathBuf,
+};
+
+use clap::Parser;
+use indicator::{
+    io::{CsvReader, CsvWriter, Error, JsonlReader, JsonlWriter, Mapping},
+    Operator, TickValue,
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+use config::{Config, Data, Format};
+use pipeline::{Bar, Pipeline, Trade};
+
+mod config;
+mod pipeline;
+
+/// Run an indicator pipeline over market data files.
+#[derive(Debug, Parser)]
+#[command(name = "indicator", version)]
+struct Args {
+    /// The pipeline config (TOML or JSON).
+    config: PathBuf,
+    /// Override the input path (`-` for stdin).
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+    /// Override the output path (`-` for stdout).
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+type Rows = Box<dyn Iterator<Item = Result<TickValue<Bar>, Error>>>;
+
+fn read<T>(format: Format, reader: Box<dyn Read>, mapping: Mapping) -> anyhow::Result<Rows>
+where
+    T: DeserializeOwned + Into<Bar> + 'static,
+{
+    let rows: Box<dyn Iterator<Item = Result<TickValue<T>, Error>>> = match format {
+        Format::Csv => Box::new(CsvReader::new(reader, mapping)?),
+        Format::Jsonl => Box::new(JsonlReader::new(BufReader::new(reader), mapping)),
+    };
+    Ok(Box::new(rows.map(|row| row.map(|row| row.map(Into::into)))))
+}
+
+enum Writer {
+    Csv(Box<CsvWriter<Box<dyn Write>>>),
+    Jsonl(JsonlWriter<Box<dyn Write>>),
+}
+
+impl Writer {
+    fn write<T: Serialize>(&mut self, row: &TickValue<T>) -> Result<(), Error> {
+        match self {
+            Self::Csv(writer) => writer.write(row),
+            Self::Jsonl(writer) => writer.write(row),
+        }
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        match self {
+            Self::Csv(writer) => writer.flush(),
+            Self::Jsonl(writer) => writer.flush(),
+        }
+    }
+}
+
+fn stdio(path: Option<PathBuf>) -> Option<PathBuf> {
+    path.filter(|path| path.as_os_str() != "-")
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let mut config = Config::load(&args.config)?;
+    if let Some(path) = args.input {
+        config.input.path = Some(path);
+    }
+    if let Some(path) = args.output {
+        config.output.path = Some(path);
+    }
+    let input_path = stdio(config.input.path.clone());
+    let output_path = stdio(config.output.path.clone());
+
+    let reader: Box<dyn Read> = match &input_path {
+        Some(path) => Box::new(std::fs::File::open(path)?),
+        None => Box::new(io::stdin().lock()),
+    };
+    let format = Format::resolve(config.input.format, input_path.as_deref());
+    let mapping = config.input.mapping()?;
+    let rows = match config.input.data {
+        Data::Bars => read::<Bar>(format, reader, mapping)?,
+        Data::Ticks => read::<Trade>(format, reader, mapping)?,
+    };
+
+    let writer: Box<dyn Write> = match &output_path {
+        Some(path) => Box::new(io::BufWriter::new(std::fs::File::create(path)?)),
+        None => Box::new(io::stdout().lock()),
+    };
+    let mapping = config.output.mapping()?;
+    let mut writer = match Format::resolve(config.output.format, output_path.as_deref()) {
+        Format::Csv => Writer::Csv(Box::new(CsvWriter::new(writer, mapping))),
+        Format::Jsonl => Writer::Jsonl(JsonlWriter::new(writer, mapping)),
+    };
+
+    let mut pipeline = Pipeline::new(&config.pipeline)?;
+    for row in rows {
+        writer.write(&pipeline.next(row?))?;
+    }
+    writer.flush()?;
+    Ok(())
+}
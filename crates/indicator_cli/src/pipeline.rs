@@ -0,0 +1,152 @@
+use indicator::{
+    pipeline::{Error, Fields, PipelineConfig, Registry},
+    Operator, TickValue,
+};
+use serde::Deserialize;
+use serde_json::{Map, Number, Value};
+
+/// A row of the input, in the shape of a bar.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct Bar {
+    /// Open.
+    pub open: f64,
+    /// High.
+    pub high: f64,
+    /// Low.
+    pub low: f64,
+    /// Close.
+    pub close: f64,
+    /// Volume.
+    #[serde(default)]
+    pub volume: f64,
+}
+
+/// A trade tick.
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct Trade {
+    /// Price.
+    pub price: f64,
+    /// Size.
+    #[serde(default)]
+    pub size: f64,
+}
+
+impl From<Trade> for Bar {
+    fn from(trade: Trade) -> Self {
+        Self {
+            open: trade.price,
+            high: trade.price,
+            low: trade.price,
+            close: trade.price,
+            volume: trade.size,
+        }
+    }
+}
+
+impl Fields<f64> for Bar {
+    fn names() -> &'static [&'static str] {
+        &["open", "high", "low", "close", "volume"]
+    }
+
+    fn get(&self, idx: usize) -> f64 {
+        match idx {
+            0 => self.open,
+            1 => self.high,
+            2 => self.low,
+            3 => self.close,
+            _ => self.volume,
+        }
+    }
+}
+
+/// The pipeline of the config, built with the built-in node types,
+/// of which the outputs are the columns of the rows.
+#[derive(Debug)]
+pub struct Pipeline {
+    names: Vec<String>,
+    inner: indicator::pipeline::Pipeline<Bar, f64>,
+}
+
+impl Pipeline {
+    /// Build the pipeline.
+    pub fn new(config: &PipelineConfig) -> Result<Self, Error> {
+        Ok(Self {
+            names: config.outputs.clone(),
+            inner: Registry::builtin().build(config)?,
+        })
+    }
+}
+
+impl Operator<TickValue<Bar>> for Pipeline {
+    type Output = TickValue<Map<String, Value>>;
+
+    fn next(&mut self, input: TickValue<Bar>) -> Self::Output {
+        self.inner.next(input).map(|outputs| {
+            self.names
+                .iter()
+                .zip(outputs)
+                .map(|(name, value)| {
+                    let value = value
+                        .and_then(Number::from_f64)
+                        .map_or(Value::Null, Value::Number);
+                    (name.clone(), value)
+                })
+                .collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indicator::IndicatorIteratorExt;
+    use time::{macros::datetime, Duration};
+
+    #[test]
+    fn pipeline() {
+        let config: PipelineConfig = toml::from_str(
+            r#"
+            outputs = ["sma", "sum_2m", "high"]
+
+            [[nodes]]
+            name = "sma"
+            type = "sma"
+            inputs = ["close"]
+            params = { length = 2 }
+
+            [[nodes]]
+            name = "sum_2m"
+            type = "sum"
+            inputs = ["close"]
+            params = { length = 2 }
+            period = "2m"
+            "#,
+        )
+        .unwrap();
+        let base = datetime!(2023-01-01 00:00:00 UTC);
+        let rows = [1.0, 3.0, 2.0, 4.0]
+            .into_iter()
+            .enumerate()
+            .map(|(idx, price)| {
+                let bar = Bar::from(Trade { price, size: 1.0 });
+                TickValue::new(base + Duration::minutes(idx as i64), bar)
+            })
+            .indicator(Pipeline::new(&config).unwrap())
+            .map(|row| serde_json::to_value(row.value).unwrap())
+            .collect::<Vec<_>>();
+        let expected = [
+            serde_json::json!({"sma": null, "sum_2m": null, "high": 1.0}),
+            serde_json::json!({"sma": 2.0, "sum_2m": null, "high": 3.0}),
+            serde_json::json!({"sma": 2.5, "sum_2m": 5.0, "high": 2.0}),
+            serde_json::json!({"sma": 3.0, "sum_2m": 7.0, "high": 4.0}),
+        ];
+        assert_eq!(rows, expected);
+
+        let mut unknown = config;
+        unknown.nodes[0].kind = "rsi".into();
+        assert!(matches!(
+            Pipeline::new(&unknown),
+            Err(Error::UnknownType(_))
+        ));
+    }
+}
@@ -0,0 +1,219 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote,
+    punctuated::Punctuated,
+    spanned::Spanned,
+    Data, DeriveInput, Fields, Ident, Index, Member, Result, Token, Type,
+};
+
+use super::indicator;
+
+pub(super) fn derive_entries(input: TokenStream) -> Result<TokenStream2> {
+    let input = syn::parse::<DeriveInput>(input)?;
+    Ok(Entries::parse(input)?.expand())
+}
+
+/// A struct deriving `Entries`.
+struct Entries {
+    input: DeriveInput,
+    entries: Vec<Entry>,
+}
+
+/// A field inserted as an entry.
+struct Entry {
+    member: Member,
+    ty: Type,
+    key: Option<Type>,
+}
+
+impl Entry {
+    /// The identity of the inserted type.
+    fn id(&self) -> String {
+        match &self.key {
+            Some(key) => format!("key {}", key.to_token_stream()),
+            None => self.ty.to_token_stream().to_string(),
+        }
+    }
+}
+
+impl Entries {
+    fn parse(input: DeriveInput) -> Result<Self> {
+        let Data::Struct(data) = &input.data else {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "`Entries` can only be derived for structs",
+            ));
+        };
+        let fields = match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            Fields::Unnamed(fields) => &fields.unnamed,
+            Fields::Unit => {
+                return Err(syn::Error::new(
+                    input.ident.span(),
+                    "`Entries` cannot be derived for unit structs, there is no field to insert",
+                ))
+            }
+        };
+        let mut entries = Vec::with_capacity(fields.len());
+        for (idx, field) in fields.iter().enumerate() {
+            let mut options = EntryOptions::default();
+            for attr in field
+                .attrs
+                .iter()
+                .filter(|attr| attr.path().is_ident("entry"))
+            {
+                let parsed =
+                    attr.parse_args_with(Punctuated::<EntryOption, Token![,]>::parse_terminated)?;
+                for option in parsed {
+                    options.set(option)?;
+                }
+            }
+            if options.skip.is_some() {
+                continue;
+            }
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(Index {
+                    index: idx as u32,
+                    span: field.ty.span(),
+                }),
+            };
+            let entry = Entry {
+                member,
+                ty: field.ty.clone(),
+                key: options.key,
+            };
+            if let Some(other) = entries
+                .iter()
+                .find(|other: &&Entry| other.id() == entry.id())
+            {
+                return Err(syn::Error::new(
+                    field.ty.span(),
+                    format!(
+                        "The entry is already inserted by the field `{}`, use `#[entry(key = K)]` to insert it under a key",
+                        other.member.to_token_stream(),
+                    ),
+                ));
+            }
+            entries.push(entry);
+        }
+        Ok(Self { input, entries })
+    }
+
+    fn expand(self) -> TokenStream2 {
+        let indicator = indicator();
+        let name = &self.input.ident;
+        let mut generics = self.input.generics.clone();
+        let where_clause = generics.make_where_clause();
+        where_clause
+            .predicates
+            .push(parse_quote!(Self: Send + Sync + 'static));
+        let mut inserts = Vec::with_capacity(self.entries.len());
+        let mut provides = Vec::with_capacity(self.entries.len());
+        for Entry { member, ty, key } in &self.entries {
+            where_clause
+                .predicates
+                .push(parse_quote!(#ty: Clone + Send + Sync + 'static));
+            let entry_ty = match key {
+                Some(key) => {
+                    where_clause
+                        .predicates
+                        .push(parse_quote!(#key: #indicator::context::Key<Value = #ty>));
+                    inserts.push(quote! {
+                        map.insert(#indicator::context::Keyed::<#key>::new(::core::clone::Clone::clone(&self.#member)));
+                    });
+                    quote!(#indicator::context::Keyed<#key>)
+                }
+                None => {
+                    inserts.push(quote! {
+                        map.insert::<#ty>(::core::clone::Clone::clone(&self.#member));
+                    });
+                    quote!(#ty)
+                }
+            };
+            provides.push(quote! {
+                deps.provide_env::<#entry_ty>();
+            });
+        }
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        quote! {
+            impl #impl_generics #indicator::context::Entries for #name #ty_generics #where_clause {
+                fn insert_entries(&self, map: &mut #indicator::context::Map) {
+                    #(#inserts)*
+                }
+
+                fn provide_entries(deps: &mut #indicator::context::Dependencies) {
+                    #(#provides)*
+                }
+            }
+        }
+    }
+}
+
+/// Options of a field, e.g. `#[entry(key = Upper)]`.
+#[derive(Default)]
+struct EntryOptions {
+    skip: Option<Ident>,
+    key: Option<Type>,
+}
+
+impl EntryOptions {
+    fn set(&mut self, option: EntryOption) -> Result<()> {
+        match option {
+            EntryOption::Skip(skip) => {
+                if let Some(key) = &self.key {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        "`key` cannot be used with `skip`",
+                    ));
+                }
+                if self.skip.replace(skip.clone()).is_some() {
+                    return Err(syn::Error::new(
+                        skip.span(),
+                        "The option is given more than once",
+                    ));
+                }
+            }
+            EntryOption::Key(key) => {
+                if let Some(skip) = &self.skip {
+                    return Err(syn::Error::new(
+                        skip.span(),
+                        "`skip` cannot be used with `key`",
+                    ));
+                }
+                let span = key.span();
+                if self.key.replace(key).is_some() {
+                    return Err(syn::Error::new(span, "The option is given more than once"));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+enum EntryOption {
+    Skip(Ident),
+    Key(Type),
+}
+
+impl Parse for EntryOption {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ident = input
+            .parse::<Ident>()
+            .map_err(|err| syn::Error::new(err.span(), "Expecting `skip` or `key = K`"))?;
+        match ident.to_string().as_str() {
+            "skip" => Ok(Self::Skip(ident)),
+            "key" => {
+                input.parse::<Token![=]>()?;
+                Ok(Self::Key(input.parse()?))
+            }
+            _ => Err(syn::Error::new(
+                ident.span(),
+                format!("Unknown option: `{ident}`, expecting `skip` or `key = K`"),
+            )),
+        }
+    }
+}
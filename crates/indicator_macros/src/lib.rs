@@ -4,6 +4,7 @@ use proc_macro_crate::{crate_name, FoundCrate};
 use quote::quote;
 use syn::Ident;
 
+mod entries;
 mod operator;
 
 fn indicator() -> TokenStream2 {
@@ -22,10 +23,10 @@ fn indicator() -> TokenStream2 {
 ///
 /// ## Note
 /// - When using with the `generate_out` flag, the output type will be converted to the same
-/// as `generate_out_with_data`, i.e. `(OutTy, Option<DataTy>)` where `DataTy = ()`
-/// is this case. So that it can be used in `insert` method (but not the `insert_env` method).
+///   as `generate_out_with_data`, i.e. `(OutTy, Option<DataTy>)` where `DataTy = ()`
+///   is this case. So that it can be used in `insert` method (but not the `insert_env` method).
 /// - But when using with the `generate_data` flag, the output type is kept as `Option<DataTy>`,
-/// so that it can be used in `insert_data` method.
+///   so that it can be used in `insert_data` method.
 ///
 /// ## Example
 /// The following example is a basic usage of `#[operator]` attribute.
@@ -70,15 +71,137 @@ fn indicator() -> TokenStream2 {
 /// ```
 /// Here we use the `#[input]` attribute and the `generate_out` to achieve our goal.
 /// - The `#[input]` attribute is used to mark the input parameter.
-/// It will replace the `value: &T` parameter with `In(value): In<&I>` in the generated code,
-/// and introduce a bound `I: Borrow<T>` so that it can be converted back to `&T` before calling.
+///   It will replace the `value: &T` parameter with `In(value): In<&I>` in the generated code,
+///   and introduce a bound `I: Borrow<T>` so that it can be converted back to `&T` before calling.
 /// - The `generate_out` flag in the `#[operator]` attribute is used to generate a generic output type.
-/// Like what the `#[input]` attribute does, it will introduce a bound `OutTy: From<T>`
-/// (here the `OutTy` is the generated output type) so that the output can be converted from `T`.
+///   Like what the `#[input]` attribute does, it will introduce a bound `OutTy: From<T>`
+///   (here the `OutTy` is the generated output type) so that the output can be converted from `T`.
 ///
 /// Also note that the operator generated with `generate_out` flag is meant to be used with `insert` method,
 /// because the output type is wrapped in a tuple `(OutTy, Option<DataTy>)` where `DataTy = ()` is this case.
 /// If you want to costomize the `DataTy`, you can use the `generate_out_with_data` flag instead.
+///
+/// ## History
+/// The values of the previous contexts kept by the `Cache` layer can be extracted with
+/// `#[prev(depth = N)]` for the `N`-th previous value (`#[prev]` is the same as `depth = 1`),
+/// and `#[history(len = N)]` for an iterator over the last `N` values, from the latest to the oldest.
+/// ```rust
+/// use indicator::prelude::*;
+///
+/// /// The change since `2` evaluations ago.
+/// #[operator(input = f64)]
+/// fn change(In(x): In<&f64>, #[prev(depth = 2)] prev: Option<&f64>) -> f64 {
+///     prev.map_or(0.0, |prev| x - prev)
+/// }
+///
+/// /// The highest of the last `3` values.
+/// #[operator(input = f64)]
+/// fn highest(#[history(len = 3)] values: impl Iterator<Item = &f64>) -> Option<f64> {
+///     values.copied().reduce(f64::max)
+/// }
+/// ```
+///
+/// ## Options
+/// The extractor attributes accept the following options besides `borrow`, `as_ref` and `as_ref?`:
+/// - `default = expr` (`env`, `data` and `prev`): the value to use when the type is missing,
+///   so that the argument is `&T` and the type is not required from the context.
+/// - `or_insert_with = f` (`env`, `data` and `prev`): the same as `default`, but the value is
+///   computed by `f` the first time the type is missing, and cached in the operator until it is reset.
+/// - `rename = Name`: the name of the generated type parameter, instead of the one
+///   derived from the argument.
+/// - `key = K`: extract `Keyed<K>` instead of a generated type parameter.
+///
+/// With `default` or `or_insert_with`, the target type itself is extracted instead of
+/// a generated type parameter, so they cannot be used with `as_ref` and `rename`.
+/// ```rust
+/// use indicator::{prelude::*, key, IndicatorIteratorExt};
+///
+/// key!(Alpha: f64;);
+///
+/// #[operator(input = f64)]
+/// fn scaled(
+///     In(x): In<&f64>,
+///     #[data(key = Alpha, default = 0.5)] alpha: &f64,
+///     #[env(or_insert_with = f64::default)] offset: &f64,
+/// ) -> f64 {
+///     x * alpha + offset
+/// }
+///
+/// let op = insert_env_and_output(scaled).finish();
+/// assert_eq!([2.0, 4.0].into_iter().indicator(op).collect::<Vec<_>>(), [1.0, 2.0]);
+/// ```
+///
+/// ## Stateful operators
+/// `#[operator]` can also be put on an inherent `impl` block, to make a `RefOperator` of the struct
+/// with its fields as the persistent state. The step is the method marked with `#[step]`, which takes
+/// `&mut self` (or `&self`) followed by the extractors, as in the function form.
+/// ```rust
+/// use indicator::{prelude::*, IndicatorIteratorExt};
+///
+/// /// Exponential moving average.
+/// struct Ema {
+///     alpha: f64,
+///     last: Option<f64>,
+/// }
+///
+/// #[operator(input = f64)]
+/// impl Ema {
+///     #[step]
+///     fn step(&mut self, In(x): In<&f64>) -> f64 {
+///         let last = self.last.unwrap_or(*x);
+///         let ema = self.alpha * x + (1.0 - self.alpha) * last;
+///         self.last = Some(ema);
+///         ema
+///     }
+/// }
+///
+/// let op = insert_env_and_output(|| Ema { alpha: 0.5, last: None }).finish();
+/// assert_eq!([1.0, 3.0, 5.0].into_iter().indicator(op).collect::<Vec<_>>(), [1.0, 2.0, 3.5]);
+/// ```
+/// The struct is given, so the type parameters of the extractor attributes are only generated
+/// when the `impl` block declares them, e.g. carried by a `PhantomData` field of the struct.
+/// Otherwise `#[env]`, `#[data]`, `#[prev]` and `#[history]` extract the target types themselves
+/// (e.g. `#[prev] prev: Option<&f64>` extracts `Prev<&f64>`), so `as_ref` and `rename`
+/// require the parameter to be declared. The `generate_*` flags are not supported.
+/// ```rust
+/// use core::marker::PhantomData;
+/// use indicator::prelude::*;
+///
+/// /// The sum of the inputs, weighted by a type borrowing `f64` in the data context.
+/// struct Weighted<Weight> {
+///     sum: f64,
+///     _weight: PhantomData<fn() -> Weight>,
+/// }
+///
+/// #[operator(input = f64)]
+/// impl<Weight> Weighted<Weight> {
+///     #[step]
+///     fn step(&mut self, In(x): In<&f64>, #[data] weight: &f64) -> f64 {
+///         self.sum += x * weight;
+///         self.sum
+///     }
+///
+///     #[reset]
+///     fn clear(&mut self) {
+///         self.sum = 0.0;
+///     }
+/// }
+///
+/// let op = insert_env_and_output(|| Weighted::<f64> { sum: 0.0, _weight: PhantomData })
+///     .provide(0.5)
+///     .finish();
+/// ```
+/// The struct cannot be reset by the layers unless a method is marked with `#[reset]`,
+/// which takes `&mut self` only and implements `Reset`.
+///
+/// ## Dependencies
+/// The generated operator implements `Dependent`, declaring the types its extractors require
+/// from the context, so that a missing dependency is reported by `try_finish` when the
+/// operator is built, instead of a panic at the first evaluation.
+///
+/// The operators generated from functions also implement `Reset`, which is required to reset
+/// the layers inserting their outputs. It clears the values cached by `or_insert_with`.
+/// The ones of `impl` blocks implement it with the `#[reset]` method.
 #[proc_macro_attribute]
 pub fn operator(args: TokenStream, input: TokenStream) -> TokenStream {
     match self::operator::generate_operator(args, input) {
@@ -86,3 +209,53 @@ pub fn operator(args: TokenStream, input: TokenStream) -> TokenStream {
         Err(err) => err.to_compile_error().into(),
     }
 }
+
+/// Derive `Entries` for a struct, so that its fields are inserted into the env context
+/// as separate entries when it is inserted by `insert_env_entries` or `insert_entries`.
+///
+/// Each field is inserted as an entry of its own type, unless it is marked with:
+/// - `#[entry(key = K)]`: insert it as `Keyed<K>`, for the fields sharing the same type.
+/// - `#[entry(skip)]`: do not insert it.
+///
+/// The fields are cloned, the struct itself is kept in the env context.
+/// ```rust
+/// use indicator::{prelude::*, key, IndicatorIteratorExt};
+///
+/// key! {
+///     Upper: f64;
+///     Lower: f64;
+/// }
+///
+/// #[derive(Clone, Entries)]
+/// struct Bands {
+///     #[entry(key = Upper)]
+///     upper: f64,
+///     #[entry(key = Lower)]
+///     lower: f64,
+///     #[entry(skip)]
+///     width: f64,
+/// }
+///
+/// #[operator(input = f64)]
+/// fn bands(In(x): In<&f64>) -> Bands {
+///     Bands { upper: x + 1.0, lower: x - 1.0, width: 2.0 }
+/// }
+///
+/// #[operator(input = f64)]
+/// fn above(In(x): In<&f64>, #[env(key = Upper)] upper: &f64) -> bool {
+///     x > upper
+/// }
+///
+/// let op = insert_env_and_output(above)
+///     .insert_env_entries(bands)
+///     .try_finish()
+///     .unwrap();
+/// assert_eq!([1.0, 2.0].into_iter().indicator(op).collect::<Vec<_>>(), [false, false]);
+/// ```
+#[proc_macro_derive(Entries, attributes(entry))]
+pub fn derive_entries(input: TokenStream) -> TokenStream {
+    match self::entries::derive_entries(input) {
+        Ok(output) => output.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
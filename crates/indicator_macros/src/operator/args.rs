@@ -2,7 +2,7 @@ use proc_macro2::Span;
 use syn::{
     parse::{Parse, ParseStream},
     punctuated::Punctuated,
-    Expr, ExprAssign, ExprPath, Ident, Result, Token, Type, TypePath,
+    Ident, Result, Token, Type,
 };
 
 pub(crate) struct OperatorArgs {
@@ -25,7 +25,7 @@ impl Parse for OperatorArgs {
         for arg in args {
             match arg {
                 Args::GenerateOut(out) => generate_out = Some(out),
-                Args::InputType(ty) => input_type = Some(ty),
+                Args::InputType(ty) => input_type = Some(*ty),
             }
         }
         let input_type = input_type
@@ -38,52 +38,46 @@ impl Parse for OperatorArgs {
 }
 
 enum Args {
-    InputType(Type),
+    InputType(Box<Type>),
     GenerateOut(GenerateOut),
 }
 
-fn get_ident(expr: &Expr) -> Result<&Ident> {
-    let Expr::Path(ExprPath { path, .. }) = expr else {
-        return Err(syn::Error::new(
-            Span::call_site(),
-            "Expecting an identifier",
-        ));
-    };
-    let ident = path
-        .get_ident()
-        .ok_or_else(|| syn::Error::new(Span::call_site(), "Expecting an identifier"))?;
-    Ok(ident)
-}
-
 impl Parse for Args {
     fn parse(input: ParseStream) -> Result<Self> {
-        let expr: Expr = input.parse()?;
-        match expr {
-            Expr::Assign(ExprAssign { left, right, .. }) => {
-                let ident = get_ident(&left)?;
-                if ident == "input" {
-                    let Expr::Path(ExprPath { path, .. }) = *right else {
-                        return Err(syn::Error::new(Span::call_site(), "Expecting a type"));
-                    };
-                    Ok(Self::InputType(Type::Path(TypePath { qself: None, path })))
-                } else {
-                    Err(syn::Error::new(
-                        ident.span(),
-                        format!("Unknown argument: `{ident}`, expecting `input = T`"),
-                    ))
-                }
+        let ident = input.parse::<Ident>().map_err(|err| {
+            syn::Error::new(
+                err.span(),
+                "Expecting `input = T` or a flag among `generate_out`, `generate_data` and `generate_out_with_data`",
+            )
+        })?;
+        if input.peek(Token![=]) {
+            let eq = input.parse::<Token![=]>()?;
+            if ident != "input" {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    format!("Unknown argument: `{ident}`, expecting `input = T`"),
+                ));
             }
-            expr => {
-                let ident = get_ident(&expr)?;
-                match ident.to_string().as_str() {
-                    "generate_out" => Ok(Self::GenerateOut(GenerateOut::Out)),
-                    "generate_data" => Ok(Self::GenerateOut(GenerateOut::Data)),
-                    "generate_out_with_data" => Ok(Self::GenerateOut(GenerateOut::WithData)),
-                    _ => Err(syn::Error::new(
-                        ident.span(),
-                        format!("Unknown argument: `{ident}`, expecting `generate_out`, `generate_out_data` or `generate_out_with_data`"),
-                    )),
-                }
+            let ty = input.parse::<Type>().map_err(|err| {
+                let mut error =
+                    syn::Error::new(eq.span, "Expecting the input type, e.g. `input = f64`");
+                error.combine(err);
+                error
+            })?;
+            Ok(Self::InputType(Box::new(ty)))
+        } else {
+            match ident.to_string().as_str() {
+                "generate_out" => Ok(Self::GenerateOut(GenerateOut::Out)),
+                "generate_data" => Ok(Self::GenerateOut(GenerateOut::Data)),
+                "generate_out_with_data" => Ok(Self::GenerateOut(GenerateOut::WithData)),
+                "input" => Err(syn::Error::new(
+                    ident.span(),
+                    "Expecting the input type, e.g. `input = f64`",
+                )),
+                _ => Err(syn::Error::new(
+                    ident.span(),
+                    format!("Unknown argument: `{ident}`, expecting `generate_out`, `generate_data` or `generate_out_with_data`"),
+                )),
             }
         }
     }
@@ -1,12 +1,13 @@
 use convert_case::{Case, Casing};
-use proc_macro2::{Ident, TokenStream};
+use proc_macro2::{Ident, Span, TokenStream};
 use syn::{
     parse::{Parse, ParseStream},
     parse_quote,
     punctuated::Punctuated,
     spanned::Spanned,
     token::Comma,
-    Attribute, FnArg, GenericParam, Meta, Pat, PatType, Result, Token, Type,
+    Attribute, Expr, FnArg, GenericParam, LitInt, Meta, Pat, PatType, Result, Token, Type,
+    WherePredicate,
 };
 
 use quote::quote;
@@ -14,48 +15,94 @@ use quote::quote;
 use super::{
     args::OperatorArgs,
     indicator,
-    utils::{get_type_inside_option, get_type_under_reference},
+    utils::{get_item_of_iterator, get_type_inside_option, get_type_under_reference},
 };
 
 pub(super) struct Extractors {
     pub(super) generics: Vec<GenericParam>,
     pub(super) extractors: Vec<Extractor>,
+    /// Bounds to add to the function.
+    pub(super) predicates: Vec<WherePredicate>,
+}
+
+/// The item the extractors are parsed from.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub(super) enum Form {
+    /// A function, of which the operator struct is generated.
+    #[default]
+    Fn,
+    /// A method of an `impl` block, of which the struct is given,
+    /// with the type parameters declared by the block.
+    /// The extractors only extract the generated type parameters that are declared,
+    /// and extract the target types themselves otherwise.
+    Method(Vec<Ident>),
+}
+
+impl Form {
+    fn is_method(&self) -> bool {
+        matches!(self, Self::Method(_))
+    }
 }
 
 impl Extractors {
     /// Parse extractors from the function inputs and clear the attributes.
+    /// The receiver of a method is skipped.
     pub(super) fn parse_with(
         args: &mut Punctuated<FnArg, Comma>,
         options: &OperatorArgs,
+        form: Form,
     ) -> Result<Self> {
-        let mut ctx = Ctx::default();
+        let is_method = form.is_method();
+        let mut ctx = Ctx {
+            form,
+            ..Default::default()
+        };
         let mut generics = Vec::default();
         let mut extractors = Vec::default();
+        let mut predicates = Vec::default();
         for arg in args.iter_mut() {
-            let FnArg::Typed(arg) = arg else {
-                return Err(syn::Error::new(arg.span(), "Expecting a typed argument"));
+            let arg = match arg {
+                FnArg::Typed(arg) => arg,
+                FnArg::Receiver(_) if is_method => continue,
+                FnArg::Receiver(_) => {
+                    return Err(syn::Error::new(
+                        arg.span(),
+                        "`self` is only supported by the `#[step]` method of an `impl` block",
+                    ));
+                }
             };
             let ExtractorWithGenerics {
                 generics: extra,
                 extractor,
+                ty,
+                predicates: extra_predicates,
             } = ExtractorWithGenerics::parse_with(arg, options, &mut ctx)?;
             generics.extend(extra);
+            predicates.extend(extra_predicates);
+            if let Some(ty) = ty {
+                arg.ty = ty;
+            }
             extractors.push(extractor);
             arg.attrs.clear();
         }
         Ok(Self {
             generics,
             extractors,
+            predicates,
         })
     }
 }
 
 #[derive(Default)]
 struct Ctx {
+    form: Form,
     input: usize,
     env: usize,
     data: usize,
     prev: usize,
+    history: usize,
+    /// The number of the values cached in the operator.
+    cached: usize,
 }
 
 impl Ctx {
@@ -68,24 +115,83 @@ impl Ctx {
 
     fn next_env(&mut self, pat: &Pat) -> String {
         let name = get_variable_name(pat).map(|ident| ident.to_string());
-        let name = name.unwrap_or_else(|| format!("env{}", self.input));
+        let name = name.unwrap_or_else(|| format!("env{}", self.env));
         self.env += 1;
         name
     }
 
     fn next_data(&mut self, pat: &Pat) -> String {
         let name = get_variable_name(pat).map(|ident| ident.to_string());
-        let name = name.unwrap_or_else(|| format!("data{}", self.input));
+        let name = name.unwrap_or_else(|| format!("data{}", self.data));
         self.data += 1;
         name
     }
 
     fn next_prev(&mut self, pat: &Pat) -> String {
         let name = get_variable_name(pat).map(|ident| ident.to_string());
-        let name = name.unwrap_or_else(|| format!("prev{}", self.input));
+        let name = name.unwrap_or_else(|| format!("prev{}", self.prev));
         self.prev += 1;
         name
     }
+
+    fn next_history(&mut self, pat: &Pat) -> String {
+        let name = get_variable_name(pat).map(|ident| ident.to_string());
+        let name = name.unwrap_or_else(|| format!("history{}", self.history));
+        self.history += 1;
+        name
+    }
+}
+
+impl Ctx {
+    /// The type to extract for the target type of the argument and the way to convert it,
+    /// adding the generic parameter that introduces it.
+    fn extracted_ty(
+        &self,
+        name: &str,
+        target_ty: &Type,
+        lookup: &Lookup,
+        arg: &PatType,
+        generics: &mut Vec<GenericParam>,
+    ) -> Result<(TokenStream, Way)> {
+        if let Some(key) = &lookup.key {
+            let indicator = indicator();
+            return Ok((quote!(#indicator::context::Keyed<#key>), Way::AsRef));
+        }
+        // The type of the fallback value must be known, so the target type is extracted.
+        if lookup.fallback.is_some() {
+            return Ok((quote!(#target_ty), Way::Borrow));
+        }
+        let way = lookup.way;
+        let ty = Ident::new(&name.to_case(Case::Pascal), arg.span());
+        match &self.form {
+            Form::Method(declared) if !declared.contains(&ty) => {
+                if let Some(rename) = &lookup.rename {
+                    return Err(syn::Error::new(
+                        rename.span(),
+                        format!("`rename` names the type parameter `{ty}`, which must be declared on the `impl` block"),
+                    ));
+                }
+                if !way.is_borrow() {
+                    return Err(syn::Error::new(
+                        arg.span(),
+                        format!("`as_ref` extracts the type parameter `{ty}`, which must be declared on the `impl` block, e.g. carried by a `PhantomData<fn() -> {ty}>` field"),
+                    ));
+                }
+                return Ok((quote!(#target_ty), way));
+            }
+            _ => {}
+        }
+        if way.is_borrow() {
+            generics.push(syn::parse2(
+                quote!(#ty: core::borrow::Borrow<#target_ty> + Send + Sync + 'static),
+            )?);
+        } else {
+            generics.push(syn::parse2(
+                quote!(#ty: AsRef<#target_ty> + Send + Sync + 'static),
+            )?);
+        }
+        Ok((quote!(#ty), way))
+    }
 }
 
 fn get_variable_name(pat: &Pat) -> Option<&Ident> {
@@ -101,12 +207,108 @@ pub(super) enum Extractor {
     Plain(Box<Type>),
     Borrow(Ident, FnArg, Optional),
     AsRef(Ident, FnArg, Optional),
+    Iter(Ident, FnArg, TokenStream),
+    /// An optional extractor falling back to a default value, which is kept in the slot.
+    Default(Box<Extractor>, Ident, TokenStream),
+    /// An optional extractor falling back to the value computed once by the function
+    /// and cached in the field of the operator, of which the type is the target type.
+    Cached(Box<Extractor>, syn::Index, Box<Type>, TokenStream),
 }
 
 impl Extractor {
+    fn new(way: Way, name: Ident, pat: FnArg, optional: Optional) -> Self {
+        if way.is_borrow() {
+            Self::Borrow(name, pat, optional)
+        } else {
+            Self::AsRef(name, pat, optional)
+        }
+    }
+
+    /// Fall back to the given value if the value is missing.
+    fn with_fallback(
+        self,
+        name: &Ident,
+        fallback: Option<(Ident, Fallback)>,
+        target_ty: &Type,
+        ctx: &mut Ctx,
+    ) -> Result<Self> {
+        match fallback {
+            Some((_, Fallback::Value(default))) => {
+                let slot = Ident::new(&format!("__{name}_default"), name.span());
+                Ok(Self::Default(Box::new(self), slot, default))
+            }
+            Some((option, Fallback::Cached(f))) => {
+                if ctx.form.is_method() {
+                    return Err(syn::Error::new(
+                        option.span(),
+                        "`or_insert_with` is not supported in an `impl` block, there is no field to cache the value",
+                    ));
+                }
+                // The first field of the operator is the `PhantomData`.
+                ctx.cached += 1;
+                let field = syn::Index::from(ctx.cached);
+                Ok(Self::Cached(
+                    Box::new(self),
+                    field,
+                    Box::new(target_ty.clone()),
+                    f,
+                ))
+            }
+            None => Ok(self),
+        }
+    }
+
+    /// The type of the value cached in the operator, if any.
+    pub(super) fn cached_ty(&self) -> Option<(&syn::Index, &Type)> {
+        match self {
+            Self::Cached(_, field, ty, _) => Some((field, ty)),
+            _ => None,
+        }
+    }
+
+    /// The type of the extractor.
+    pub(super) fn ty(&self) -> &Type {
+        match self {
+            Self::Plain(ty) => ty,
+            Self::Default(inner, ..) | Self::Cached(inner, ..) => inner.ty(),
+            Self::Borrow(_, arg, _) | Self::AsRef(_, arg, _) | Self::Iter(_, arg, _) => match arg {
+                FnArg::Typed(arg) => &arg.ty,
+                FnArg::Receiver(_) => unreachable!("extractors are typed arguments"),
+            },
+        }
+    }
+
+    /// The statements to run before the extraction.
+    pub(super) fn setup(&self) -> TokenStream {
+        match self {
+            Self::Default(_, slot, _) => quote! {
+                let mut #slot = None;
+            },
+            _ => TokenStream::new(),
+        }
+    }
+
     pub(super) fn expand(&self) -> TokenStream {
         let indicator = indicator();
         match self {
+            Self::Default(inner, slot, default) => {
+                let inner = inner.expand();
+                quote! {
+                    match #inner {
+                        Some(__value) => __value,
+                        None => &*#slot.insert(#default),
+                    }
+                }
+            }
+            Self::Cached(inner, field, _, f) => {
+                let inner = inner.expand();
+                quote! {
+                    match #inner {
+                        Some(__value) => __value,
+                        None => &*self.#field.get_or_insert_with(#f),
+                    }
+                }
+            }
             Self::Plain(ty) => {
                 quote! {
                     {
@@ -147,19 +349,35 @@ impl Extractor {
                     }
                 }
             }
+            Self::Iter(name, arg, convert) => {
+                quote! {
+                    {
+                        let #arg = #indicator::context::extractor::FromValueRef::from_value_ref(&__input);
+                        #name.map(#convert)
+                    }
+                }
+            }
         }
     }
 }
 
+/// The lifetime of the items of the `history` extractors.
+pub(super) const HISTORY_LIFETIME: &str = "'__history";
+
 struct ExtractorWithGenerics {
     generics: Vec<GenericParam>,
     extractor: Extractor,
+    /// The type to replace the argument type with.
+    ty: Option<Box<Type>>,
+    predicates: Vec<WherePredicate>,
 }
 
 impl ExtractorWithGenerics {
     fn parse_with(arg: &PatType, options: &OperatorArgs, ctx: &mut Ctx) -> Result<Self> {
         let indicator = indicator();
         let mut generics = Vec::new();
+        let mut replaced = None;
+        let mut predicates = Vec::new();
         let extractor = match arg.attrs.len() {
             0 => Extractor::Plain(arg.ty.clone()),
             1 => {
@@ -178,94 +396,82 @@ impl ExtractorWithGenerics {
                             Extractor::AsRef(name, pat, false)
                         }
                     }
-                    Attr::Env(way, false) => {
-                        let target_ty = get_type_under_reference(&arg.ty)?;
-                        let name = ctx.next_env(&arg.pat);
-                        let ty = Ident::new(&name.to_case(Case::Pascal), arg.span());
+                    Attr::Env(lookup) => {
+                        let target_ty = lookup.target_ty(&arg.ty)?;
+                        let name = lookup.name(ctx.next_env(&arg.pat));
+                        let (ty, way) =
+                            ctx.extracted_ty(&name, target_ty, &lookup, arg, &mut generics)?;
                         let name = Ident::new(&name, arg.span());
-                        let pat = parse_quote!(#indicator::context::Env(#name): #indicator::context::Env<&#ty>);
-                        if way.is_borrow() {
-                            generics.push(syn::parse2(quote!(#ty: core::borrow::Borrow<#target_ty> + Send + Sync + 'static))?);
-                            Extractor::Borrow(name, pat, false)
+                        let pat = if lookup.is_optional() {
+                            parse_quote!(#indicator::context::Env(#name): #indicator::context::Env<Option<&#ty>>)
                         } else {
-                            generics.push(syn::parse2(
-                                quote!(#ty: AsRef<#target_ty> + Send + Sync + 'static),
-                            )?);
-                            Extractor::AsRef(name, pat, false)
-                        }
+                            parse_quote!(#indicator::context::Env(#name): #indicator::context::Env<&#ty>)
+                        };
+                        lookup.extractor(way, name, pat, target_ty, ctx)?
                     }
-                    Attr::Env(way, true) => {
-                        let target_ref = get_type_inside_option(&arg.ty)?;
-                        let target_ty = get_type_under_reference(target_ref)?;
-                        let name = ctx.next_env(&arg.pat);
-                        let ty = Ident::new(&name.to_case(Case::Pascal), arg.span());
+                    Attr::Data(lookup) => {
+                        let target_ty = lookup.target_ty(&arg.ty)?;
+                        let name = lookup.name(ctx.next_data(&arg.pat));
+                        let (ty, way) =
+                            ctx.extracted_ty(&name, target_ty, &lookup, arg, &mut generics)?;
                         let name = Ident::new(&name, arg.span());
-                        let pat = parse_quote!(#indicator::context::Env(#name): #indicator::context::Env<Option<&#ty>>);
-                        if way.is_borrow() {
-                            generics.push(syn::parse2(quote!(#ty: core::borrow::Borrow<#target_ty> + Send + Sync + 'static))?);
-                            Extractor::Borrow(name, pat, true)
+                        let pat = if lookup.is_optional() {
+                            parse_quote!(#indicator::context::Data(#name): #indicator::context::Data<Option<&#ty>>)
                         } else {
-                            generics.push(syn::parse2(
-                                quote!(#ty: AsRef<#target_ty> + Send + Sync + 'static),
-                            )?);
-                            Extractor::AsRef(name, pat, true)
-                        }
+                            parse_quote!(#indicator::context::Data(#name): #indicator::context::Data<&#ty>)
+                        };
+                        lookup.extractor(way, name, pat, target_ty, ctx)?
                     }
-                    Attr::Data(way, false) => {
-                        let target_ty = get_type_under_reference(&arg.ty)?;
-                        let name = ctx.next_data(&arg.pat);
-                        let ty = Ident::new(&name.to_case(Case::Pascal), arg.span());
+                    Attr::Prev(lookup, depth) => {
+                        let target_ty = lookup.target_ty(&arg.ty)?;
+                        let name = lookup.name(ctx.next_prev(&arg.pat));
+                        let (ty, way) =
+                            ctx.extracted_ty(&name, target_ty, &lookup, arg, &mut generics)?;
                         let name = Ident::new(&name, arg.span());
-                        let pat = parse_quote!(#indicator::context::Data(#name): #indicator::context::Data<&#ty>);
-                        if way.is_borrow() {
-                            generics.push(syn::parse2(quote!(#ty: core::borrow::Borrow<#target_ty> + Send + Sync + 'static))?);
-                            Extractor::Borrow(name, pat, false)
-                        } else {
-                            generics.push(syn::parse2(
-                                quote!(#ty: AsRef<#target_ty> + Send + Sync + 'static),
-                            )?);
-                            Extractor::AsRef(name, pat, false)
-                        }
+                        let pat = match depth {
+                            Some(depth) => {
+                                parse_quote!(#indicator::context::PrevN(#name): #indicator::context::PrevN<&#ty, #depth>)
+                            }
+                            None => {
+                                parse_quote!(#indicator::context::Prev(#name): #indicator::context::Prev<&#ty>)
+                            }
+                        };
+                        // The previous values are always looked up as an `Option`.
+                        Extractor::new(way, name.clone(), pat, true).with_fallback(
+                            &name,
+                            lookup.fallback,
+                            target_ty,
+                            ctx,
+                        )?
                     }
-                    Attr::Data(way, true) => {
-                        let target_ref = get_type_inside_option(&arg.ty)?;
-                        let target_ty = get_type_under_reference(target_ref)?;
-                        let name = ctx.next_data(&arg.pat);
-                        let ty = Ident::new(&name.to_case(Case::Pascal), arg.span());
-                        let name = Ident::new(&name, arg.span());
-                        let pat = parse_quote!(#indicator::context::Data(#name): #indicator::context::Data<Option<&#ty>>);
-                        if way.is_borrow() {
-                            generics.push(syn::parse2(quote!(#ty: core::borrow::Borrow<#target_ty> + Send + Sync + 'static))?);
-                            Extractor::Borrow(name, pat, true)
-                        } else {
-                            generics.push(syn::parse2(
-                                quote!(#ty: AsRef<#target_ty> + Send + Sync + 'static),
-                            )?);
-                            Extractor::AsRef(name, pat, true)
+                    Attr::History(lookup, len) => {
+                        // Name the lifetime of the items, which cannot be elided in `impl Trait`.
+                        let mut replaced_ty = arg.ty.clone();
+                        let target_ref = get_item_of_iterator(&mut replaced_ty)?;
+                        let target_ty = get_type_under_reference(target_ref)?.clone();
+                        let lifetime = syn::Lifetime::new(HISTORY_LIFETIME, arg.span());
+                        if let Type::Reference(target_ref) = target_ref {
+                            target_ref.lifetime = Some(lifetime.clone());
                         }
-                    }
-                    Attr::Prev(way) => {
-                        let target_ref = get_type_inside_option(&arg.ty)?;
-                        let target_ty = get_type_under_reference(target_ref)?;
-                        let name = ctx.next_prev(&arg.pat);
-                        let ty = Ident::new(&name.to_case(Case::Pascal), arg.span());
+                        predicates.push(parse_quote!(#target_ty: #lifetime));
+                        replaced = Some(replaced_ty);
+                        let name = lookup.name(ctx.next_history(&arg.pat));
+                        let (ty, way) =
+                            ctx.extracted_ty(&name, &target_ty, &lookup, arg, &mut generics)?;
                         let name = Ident::new(&name, arg.span());
-                        let pat = parse_quote!(#indicator::context::Prev(#name): #indicator::context::Prev<&#ty>);
-                        if way.is_borrow() {
-                            generics.push(syn::parse2(quote!(#ty: core::borrow::Borrow<#target_ty> + Send + Sync + 'static))?);
-                            Extractor::Borrow(name, pat, true)
+                        let pat = parse_quote!(#indicator::context::History(#name): #indicator::context::History<#ty, #len>);
+                        let convert = if way.is_borrow() {
+                            quote!(<#ty as core::borrow::Borrow<#target_ty>>::borrow)
                         } else {
-                            generics.push(syn::parse2(
-                                quote!(#ty: AsRef<#target_ty> + Send + Sync + 'static),
-                            )?);
-                            Extractor::AsRef(name, pat, true)
-                        }
+                            quote!(<#ty as AsRef<#target_ty>>::as_ref)
+                        };
+                        Extractor::Iter(name, pat, convert)
                     }
                 }
             }
             _ => {
                 return Err(syn::Error::new(
-                    arg.span(),
+                    arg.attrs[1].span(),
                     "Expecting at most one attribute",
                 ));
             }
@@ -273,10 +479,13 @@ impl ExtractorWithGenerics {
         Ok(Self {
             generics,
             extractor,
+            ty: replaced,
+            predicates,
         })
     }
 }
 
+#[derive(Clone, Copy)]
 enum Way {
     Borrow,
     AsRef,
@@ -289,6 +498,7 @@ impl Way {
 }
 
 struct WayWithOptional {
+    span: Span,
     way: Way,
     question_mark: Option<Token![?]>,
 }
@@ -308,11 +518,13 @@ impl Parse for WayWithOptional {
         };
         if input.peek(Token![?]) {
             Ok(Self {
+                span: ident.span(),
                 way,
                 question_mark: Some(input.parse()?),
             })
         } else {
             Ok(Self {
+                span: ident.span(),
                 way,
                 question_mark: None,
             })
@@ -320,62 +532,331 @@ impl Parse for WayWithOptional {
     }
 }
 
+/// Options of an extractor attribute, e.g. `#[prev(as_ref, depth = 2)]`.
+#[derive(Default)]
+struct AttrOptions {
+    way: Option<WayWithOptional>,
+    depth: Option<LitInt>,
+    len: Option<LitInt>,
+    fallback: Option<(Ident, Fallback)>,
+    rename: Option<Ident>,
+    key: Option<Type>,
+}
+
+/// The value to use when the value is missing.
+enum Fallback {
+    /// `default = expr`, evaluated each time the value is missing.
+    Value(TokenStream),
+    /// `or_insert_with = f`, called the first time the value is missing
+    /// and cached in the operator.
+    Cached(TokenStream),
+}
+
+enum AttrOption {
+    Way(WayWithOptional),
+    Depth(LitInt),
+    Len(LitInt),
+    Fallback(Ident, Fallback),
+    Rename(Ident),
+    Key(Type),
+}
+
+impl Parse for AttrOption {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(syn::Ident) && input.peek2(Token![=]) {
+            let ident = input.parse::<Ident>()?;
+            input.parse::<Token![=]>()?;
+            match ident.to_string().as_str() {
+                "depth" => Ok(Self::Depth(input.parse()?)),
+                "len" => Ok(Self::Len(input.parse()?)),
+                "default" => {
+                    let expr = input.parse::<Expr>()?;
+                    Ok(Self::Fallback(ident, Fallback::Value(quote!(#expr))))
+                }
+                "or_insert_with" => {
+                    let expr = input.parse::<Expr>()?;
+                    Ok(Self::Fallback(ident, Fallback::Cached(quote!(#expr))))
+                }
+                "rename" => Ok(Self::Rename(input.parse()?)),
+                "key" => Ok(Self::Key(input.parse()?)),
+                _ => Err(syn::Error::new(
+                    ident.span(),
+                    format!("Unknown option: `{ident}`, expecting `depth`, `len`, `default`, `or_insert_with`, `rename` or `key`"),
+                )),
+            }
+        } else {
+            Ok(Self::Way(input.parse()?))
+        }
+    }
+}
+
+impl Parse for AttrOptions {
+    fn parse(input: ParseStream) -> Result<Self> {
+        fn set<T>(slot: &mut Option<T>, value: T, span: Span) -> Result<()> {
+            if slot.replace(value).is_some() {
+                return Err(syn::Error::new(span, "The option is given more than once"));
+            }
+            Ok(())
+        }
+
+        let mut options = Self::default();
+        for option in Punctuated::<AttrOption, Token![,]>::parse_terminated(input)? {
+            match option {
+                AttrOption::Way(way) => {
+                    let span = way.span;
+                    set(&mut options.way, way, span)?
+                }
+                AttrOption::Depth(depth) => {
+                    let span = depth.span();
+                    set(&mut options.depth, depth, span)?
+                }
+                AttrOption::Len(len) => {
+                    let span = len.span();
+                    set(&mut options.len, len, span)?
+                }
+                AttrOption::Fallback(ident, fallback) => {
+                    if let Some((given, _)) = options
+                        .fallback
+                        .as_ref()
+                        .filter(|(given, _)| *given != ident)
+                    {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            format!("`{ident}` cannot be used with `{given}`"),
+                        ));
+                    }
+                    let span = ident.span();
+                    set(&mut options.fallback, (ident, fallback), span)?
+                }
+                AttrOption::Rename(rename) => {
+                    let span = rename.span();
+                    set(&mut options.rename, rename, span)?
+                }
+                AttrOption::Key(key) => {
+                    let span = key.span();
+                    set(&mut options.key, key, span)?
+                }
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// Options of the extractors looking up the context.
+struct Lookup {
+    way: Way,
+    /// Whether the argument is an `Option`.
+    optional: Optional,
+    /// The value to use when the value is missing, with the name of the option.
+    fallback: Option<(Ident, Fallback)>,
+    rename: Option<Ident>,
+    key: Option<Type>,
+}
+
+impl Lookup {
+    /// Whether the value is looked up as an `Option`.
+    fn is_optional(&self) -> bool {
+        self.optional || self.fallback.is_some()
+    }
+
+    /// The target type of the argument type.
+    fn target_ty<'a>(&self, ty: &'a Type) -> Result<&'a Type> {
+        if self.optional {
+            get_type_under_reference(get_type_inside_option(ty)?)
+        } else {
+            get_type_under_reference(ty)
+        }
+    }
+
+    /// The name of the binding, from which the type parameter is named.
+    fn name(&self, name: String) -> String {
+        self.rename
+            .as_ref()
+            .map(|rename| rename.to_string().to_case(Case::Snake))
+            .unwrap_or(name)
+    }
+
+    fn extractor(
+        self,
+        way: Way,
+        name: Ident,
+        pat: FnArg,
+        target_ty: &Type,
+        ctx: &mut Ctx,
+    ) -> Result<Extractor> {
+        let optional = self.is_optional();
+        Extractor::new(way, name.clone(), pat, optional).with_fallback(
+            &name,
+            self.fallback,
+            target_ty,
+            ctx,
+        )
+    }
+}
+
 enum Attr {
     Input(Way),
-    Env(Way, Optional),
-    Data(Way, Optional),
-    Prev(Way),
+    Env(Lookup),
+    Data(Lookup),
+    Prev(Lookup, Option<LitInt>),
+    History(Lookup, LitInt),
 }
 
 impl Attr {
     fn parse(attr: &Attribute) -> Result<Self> {
         let (ident, tokens) = match &attr.meta {
             Meta::Path(path) => {
-                let ident = path
-                    .get_ident()
-                    .ok_or_else(|| syn::Error::new(attr.span(), "Expecting an identifier"))?;
+                let ident = path.get_ident().ok_or_else(|| {
+                    syn::Error::new_spanned(path, "Expecting an extractor attribute, e.g. `#[env]`")
+                })?;
                 (ident, None)
             }
             Meta::List(list) => {
-                let ident = list
-                    .path
-                    .get_ident()
-                    .ok_or_else(|| syn::Error::new(attr.span(), "Expecting an identifier"))?;
+                let ident = list.path.get_ident().ok_or_else(|| {
+                    syn::Error::new_spanned(
+                        &list.path,
+                        "Expecting an extractor attribute, e.g. `#[env]`",
+                    )
+                })?;
                 (ident, Some(&list.tokens))
             }
-            _ => return Err(syn::Error::new(attr.span(), "Unsupported attribute format")),
+            Meta::NameValue(meta) => {
+                return Err(syn::Error::new_spanned(
+                    meta.eq_token,
+                    "Unsupported attribute format, expecting `#[name]` or `#[name(options)]`",
+                ))
+            }
         };
-        let (way, optional) = tokens
+        let AttrOptions {
+            way,
+            depth,
+            len,
+            fallback,
+            rename,
+            key,
+        } = tokens
             .map(|tokens| syn::parse2(tokens.clone()))
             .transpose()?
-            .map(|WayWithOptional { way, question_mark }| (way, question_mark.is_some()))
-            .unwrap_or_else(|| (Way::Borrow, false));
-        match ident.to_string().as_str() {
+            .unwrap_or_default();
+        if let (Some((option, _)), Some(way)) = (&fallback, &way) {
+            if !way.way.is_borrow() {
+                return Err(syn::Error::new(
+                    way.span,
+                    format!(
+                        "`as_ref` cannot be used with `{option}`, the target type is extracted"
+                    ),
+                ));
+            }
+        }
+        let (way, optional) = way
+            .map(
+                |WayWithOptional {
+                     way, question_mark, ..
+                 }| (way, question_mark),
+            )
+            .unwrap_or_else(|| (Way::Borrow, None));
+        let name = ident.to_string();
+        if let Some(depth) = depth.as_ref().filter(|_| name != "prev") {
+            return Err(syn::Error::new(
+                depth.span(),
+                "The `depth` option is only supported by the `prev` attribute",
+            ));
+        }
+        if let Some(len) = len.as_ref().filter(|_| name != "history") {
+            return Err(syn::Error::new(
+                len.span(),
+                "The `len` option is only supported by the `history` attribute",
+            ));
+        }
+        if let Some((option, _)) = fallback.as_ref() {
+            if !matches!(name.as_str(), "env" | "data" | "prev") {
+                return Err(syn::Error::new(
+                    option.span(),
+                    format!("The `{option}` option is only supported by the `env`, `data` and `prev` attributes"),
+                ));
+            }
+            if let Some(question_mark) = optional {
+                return Err(syn::Error::new(
+                    question_mark.span,
+                    format!("The value cannot be optional with the `{option}` option"),
+                ));
+            }
+            if let Some(rename) = &rename {
+                return Err(syn::Error::new(
+                    rename.span(),
+                    format!("`rename` cannot be used with `{option}`, there is no type parameter to name"),
+                ));
+            }
+        }
+        if name == "input" {
+            if let Some(span) = rename
+                .as_ref()
+                .map(Ident::span)
+                .or_else(|| key.as_ref().map(Spanned::span))
+            {
+                return Err(syn::Error::new(
+                    span,
+                    "The `input` attribute does not support `rename` and `key`",
+                ));
+            }
+        }
+        if let (Some(rename), Some(_)) = (&rename, &key) {
+            return Err(syn::Error::new(
+                rename.span(),
+                "`rename` cannot be used with `key`, there is no type parameter to name",
+            ));
+        }
+        let has_default = fallback.is_some();
+        let lookup = |optional: Optional| Lookup {
+            way,
+            optional,
+            fallback,
+            rename,
+            key,
+        };
+        match name.as_str() {
             "input" => {
-                if optional {
+                if let Some(question_mark) = optional {
                     Err(syn::Error::new(
-                        attr.span(),
+                        question_mark.span,
                         "The `input` attribute cannot be optional",
                     ))
                 } else {
                     Ok(Self::Input(way))
                 }
             }
-            "env" => Ok(Self::Env(way, optional)),
-            "data" => Ok(Self::Data(way, optional)),
+            "env" => Ok(Self::Env(lookup(optional.is_some()))),
+            "data" => Ok(Self::Data(lookup(optional.is_some()))),
             "prev" => {
-                if optional {
+                if let Some(question_mark) = optional {
                     Err(syn::Error::new(
-                        attr.span(),
+                        question_mark.span,
                         "The `prev` attribute is always optional",
                     ))
                 } else {
-                    Ok(Self::Prev(way))
+                    // The argument is an `Option` unless there is a default value.
+                    Ok(Self::Prev(lookup(!has_default), depth))
+                }
+            }
+            "history" => {
+                if let Some(question_mark) = optional {
+                    Err(syn::Error::new(
+                        question_mark.span,
+                        "The `history` attribute cannot be optional",
+                    ))
+                } else {
+                    let len = len.ok_or_else(|| {
+                        syn::Error::new(
+                            attr.span(),
+                            "The `history` attribute requires the `len` option",
+                        )
+                    })?;
+                    Ok(Self::History(lookup(false), len))
                 }
             }
             _ => Err(syn::Error::new(
                 ident.span(),
-                format!("Unknown attribute: `{ident}`, expecting `input`, `env`, `data` or `prev`",),
+                format!("Unknown attribute: `{ident}`, expecting `input`, `env`, `data`, `prev` or `history`",),
             )),
         }
     }
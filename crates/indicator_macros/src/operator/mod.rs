@@ -1,7 +1,7 @@
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 
-use self::{args::OperatorArgs, operator_fn::OperatorFn};
+use self::{args::OperatorArgs, operator_fn::OperatorFn, operator_impl::OperatorImpl};
 use super::indicator;
 
 /// Arguments for generating operator.
@@ -10,6 +10,9 @@ mod args;
 /// Operator Fn.
 mod operator_fn;
 
+/// Operator `impl` block.
+mod operator_impl;
+
 mod utils;
 
 /// Extractor.
@@ -20,6 +23,10 @@ pub(super) fn generate_operator(
     input: TokenStream,
 ) -> syn::Result<TokenStream2> {
     let args = syn::parse::<OperatorArgs>(args)?;
-    let expanded = OperatorFn::parse_with(input.clone().into(), &args)?.expand();
+    let input = TokenStream2::from(input);
+    let expanded = match syn::parse2::<syn::Item>(input.clone())? {
+        syn::Item::Impl(_) => OperatorImpl::parse_with(input, &args)?.expand(),
+        _ => OperatorFn::parse_with(input, &args)?.expand(),
+    };
     Ok(expanded)
 }
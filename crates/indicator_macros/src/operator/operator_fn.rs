@@ -2,15 +2,15 @@ use convert_case::{Case, Casing};
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
 use syn::{
-    punctuated::Punctuated, Attribute, GenericParam, Generics, ItemFn, Result, ReturnType, Token,
-    Type, TypeTuple, Visibility,
+    parse_quote, punctuated::Punctuated, Attribute, GenericParam, Generics, ItemFn, Result,
+    ReturnType, Token, Type, TypeTuple, Visibility,
 };
 
 use crate::{indicator, operator::args::GenerateOut};
 
 use super::{
     args::OperatorArgs,
-    extractor::{Extractor, Extractors},
+    extractor::{Extractor, Extractors, Form, HISTORY_LIFETIME},
     utils::get_type_inside_option,
 };
 
@@ -54,8 +54,26 @@ impl OperatorFn {
         let Extractors {
             generics: extra,
             extractors,
-        } = Extractors::parse_with(&mut original.sig.inputs, options)?;
+            predicates,
+        } = Extractors::parse_with(&mut original.sig.inputs, options, Form::Fn)?;
         generics.params.extend(extra);
+        if extractors
+            .iter()
+            .any(|extractor| matches!(extractor, Extractor::Iter(..)))
+        {
+            let lifetime = syn::Lifetime::new(HISTORY_LIFETIME, Span::call_site());
+            original
+                .sig
+                .generics
+                .params
+                .insert(0, parse_quote!(#lifetime));
+        }
+        original
+            .sig
+            .generics
+            .make_where_clause()
+            .predicates
+            .extend(predicates);
 
         // Parse output.
         let OutputTy {
@@ -92,7 +110,14 @@ impl OperatorFn {
         let gen = self.generics_with_lifetime();
         let impl_generics = gen.split_for_impl().0;
 
+        let setup = self.extractors.iter().map(|extractor| extractor.setup());
         let return_stmt = self.expand_stmt();
+        let extractor_tys = self.extractors.iter().map(|extractor| extractor.ty());
+        let cached = self
+            .extractors
+            .iter()
+            .filter_map(|extractor| extractor.cached_ty())
+            .map(|(field, _)| field);
         quote! {
             #struct_def
 
@@ -102,10 +127,25 @@ impl OperatorFn {
                 fn next(&mut self, __input: #indicator::context::ValueRef<'value, #input_ty>) -> Self::Output {
                     #[allow(clippy::extra_unused_type_parameters)]
                     #next_fn
+                    #(#setup)*
                     #return_stmt
                 }
             }
 
+            impl #orig_impl_generics #indicator::context::Dependent<#input_ty> for #name #type_generics #where_clause {
+                fn dependencies(&self, __deps: &mut #indicator::context::Dependencies) {
+                    #(
+                        <#extractor_tys as #indicator::context::extractor::FromValueRef<'_, #input_ty>>::dependencies(__deps);
+                    )*
+                }
+            }
+
+            impl #orig_impl_generics #indicator::Reset for #name #type_generics #where_clause {
+                fn reset(&mut self) {
+                    #(self.#cached = None;)*
+                }
+            }
+
             #(#docs)*
             #vis fn #fn_name #orig_impl_generics() -> #name #type_generics #where_clause {
                 #name::default()
@@ -131,8 +171,14 @@ impl OperatorFn {
         let name = self.struct_name();
         let generics = &self.generics;
         let docs = &self.docs;
+        let cached = self
+            .extractors
+            .iter()
+            .filter_map(|extractor| extractor.cached_ty())
+            .map(|(_, ty)| ty)
+            .collect::<Vec<_>>();
 
-        if generics.params.is_empty() {
+        if generics.params.is_empty() && cached.is_empty() {
             return quote! {
                 #[derive(Default)]
                 #[allow(non_camel_case_types)]
@@ -141,25 +187,41 @@ impl OperatorFn {
         }
         let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
         let phantom_data_type = generate_phantom_data_type(generics);
+        let nones = cached.iter().map(|_| quote!(None));
         quote! {
             #(#docs)*
             #[allow(non_camel_case_types)]
-            #vis struct #name #impl_generics (core::marker::PhantomData<#phantom_data_type> ) #where_clause;
+            #vis struct #name #impl_generics (
+                core::marker::PhantomData<#phantom_data_type>,
+                #(Option<#cached>,)*
+            ) #where_clause;
 
             impl #impl_generics Default for #name #type_generics #where_clause {
                 fn default() -> Self {
-                    Self(core::marker::PhantomData)
+                    Self(core::marker::PhantomData, #(#nones,)*)
                 }
             }
         }
     }
 
     fn expand_call_name(&self) -> TokenStream {
-        let call_generics = if self.next_fn.sig.generics.params.is_empty() {
+        // The lifetimes are late bound, so only the types and consts are given.
+        let params = self
+            .next_fn
+            .sig
+            .generics
+            .params
+            .iter()
+            .filter_map(|param| match param {
+                GenericParam::Type(param) => Some(&param.ident),
+                GenericParam::Const(param) => Some(&param.ident),
+                GenericParam::Lifetime(_) => None,
+            })
+            .collect::<Vec<_>>();
+        let call_generics = if params.is_empty() {
             quote!()
         } else {
-            let (_, type_generics, _) = self.next_fn.sig.generics.split_for_impl();
-            quote!(::#type_generics)
+            quote!(::<#(#params),*>)
         };
         quote! {
             __next #call_generics
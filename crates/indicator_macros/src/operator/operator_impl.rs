@@ -0,0 +1,261 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    parse_quote, spanned::Spanned, FnArg, GenericParam, Generics, ImplItem, ImplItemFn, ItemImpl,
+    Result, ReturnType, Type, TypeTuple,
+};
+
+use crate::indicator;
+
+use super::{
+    args::OperatorArgs,
+    extractor::{Extractor, Extractors, Form, HISTORY_LIFETIME},
+};
+
+/// The attribute marking the step method.
+const STEP: &str = "step";
+
+/// The attribute marking the reset method.
+const RESET: &str = "reset";
+
+pub(super) struct OperatorImpl {
+    original: ItemImpl,
+    step: syn::Ident,
+    reset: Option<syn::Ident>,
+    extractors: Vec<Extractor>,
+    generics: Generics,
+    output_ty: Type,
+    input_ty: Type,
+}
+
+impl OperatorImpl {
+    pub(super) fn parse_with(input: TokenStream, options: &OperatorArgs) -> Result<Self> {
+        let mut original: ItemImpl = syn::parse2(input)?;
+        if let Some((_, path, _)) = &original.trait_ {
+            return Err(syn::Error::new_spanned(
+                path,
+                "`#[operator]` is only supported on inherent `impl` blocks",
+            ));
+        }
+        if options.generate_out.is_some() {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "the `generate_*` flags are not supported in an `impl` block",
+            ));
+        }
+
+        let reset = parse_reset(&mut original)?;
+
+        // Find the step method.
+        let impl_generics = original.generics.clone();
+        let mut steps = original.items.iter_mut().filter_map(|item| match item {
+            ImplItem::Fn(method) if method.attrs.iter().any(|attr| attr.path().is_ident(STEP)) => {
+                Some(method)
+            }
+            _ => None,
+        });
+        let step = steps.next().ok_or_else(|| {
+            syn::Error::new(
+                original.self_ty.span(),
+                "expecting a method marked with `#[step]`",
+            )
+        })?;
+        if let Some(other) = steps.next() {
+            return Err(syn::Error::new(
+                other.sig.ident.span(),
+                "only one method can be marked with `#[step]`",
+            ));
+        }
+        let step = parse_step(step, impl_generics, options)?;
+        Ok(Self {
+            original,
+            step: step.name,
+            reset,
+            extractors: step.extractors,
+            generics: step.generics,
+            output_ty: step.output_ty,
+            input_ty: options.input_type.clone(),
+        })
+    }
+
+    pub(super) fn expand(&self) -> TokenStream {
+        let indicator = indicator();
+        let original = &self.original;
+        let self_ty = &original.self_ty;
+        let input_ty = &self.input_ty;
+        let output_ty = &self.output_ty;
+        let step = &self.step;
+
+        let (orig_impl_generics, _, where_clause) = self.generics.split_for_impl();
+        let mut gen = self.generics.clone();
+        gen.params.push(parse_quote!('value));
+        let impl_generics = gen.split_for_impl().0;
+
+        let setup = self.extractors.iter().map(|extractor| extractor.setup());
+        let extractors = self.extractors.iter().map(|extractor| extractor.expand());
+        let extractor_tys = self.extractors.iter().map(|extractor| extractor.ty());
+        let reset = self.reset.as_ref().map(|reset| {
+            let (impl_generics, _, where_clause) = original.generics.split_for_impl();
+            quote! {
+                impl #impl_generics #indicator::Reset for #self_ty #where_clause {
+                    fn reset(&mut self) {
+                        Self::#reset(self)
+                    }
+                }
+            }
+        });
+        quote! {
+            #original
+
+            impl #impl_generics #indicator::context::RefOperator<'value, #input_ty> for #self_ty #where_clause {
+                type Output = #output_ty;
+
+                fn next(&mut self, __input: #indicator::context::ValueRef<'value, #input_ty>) -> Self::Output {
+                    #(#setup)*
+                    Self::#step(self, #(#extractors),*)
+                }
+            }
+
+            impl #orig_impl_generics #indicator::context::Dependent<#input_ty> for #self_ty #where_clause {
+                fn dependencies(&self, __deps: &mut #indicator::context::Dependencies) {
+                    #(
+                        <#extractor_tys as #indicator::context::extractor::FromValueRef<'_, #input_ty>>::dependencies(__deps);
+                    )*
+                }
+            }
+
+            #reset
+        }
+    }
+}
+
+/// Find the method marked with `#[reset]`, if any, and clear the attribute.
+fn parse_reset(original: &mut ItemImpl) -> Result<Option<syn::Ident>> {
+    let mut resets = original.items.iter_mut().filter_map(|item| match item {
+        ImplItem::Fn(method) if method.attrs.iter().any(|attr| attr.path().is_ident(RESET)) => {
+            Some(method)
+        }
+        _ => None,
+    });
+    let Some(reset) = resets.next() else {
+        return Ok(None);
+    };
+    reset.attrs.retain(|attr| !attr.path().is_ident(RESET));
+    let name = reset.sig.ident.clone();
+    if let Some(other) = resets.next() {
+        return Err(syn::Error::new(
+            other.sig.ident.span(),
+            "only one method can be marked with `#[reset]`",
+        ));
+    }
+    match reset.sig.inputs.first() {
+        Some(FnArg::Receiver(receiver))
+            if receiver.reference.is_some()
+                && receiver.mutability.is_some()
+                && reset.sig.inputs.len() == 1 => {}
+        _ => {
+            return Err(syn::Error::new(
+                name.span(),
+                "the reset method must only take `&mut self`",
+            ))
+        }
+    }
+    Ok(Some(name))
+}
+
+/// The parsed step method.
+struct Step {
+    name: syn::Ident,
+    extractors: Vec<Extractor>,
+    /// The generics of the `impl` block with the ones introduced by the extractors.
+    generics: Generics,
+    output_ty: Type,
+}
+
+fn parse_step(
+    method: &mut ImplItemFn,
+    mut generics: Generics,
+    options: &OperatorArgs,
+) -> Result<Step> {
+    method.attrs.retain(|attr| !attr.path().is_ident(STEP));
+    let sig = &mut method.sig;
+    match sig.inputs.first() {
+        Some(FnArg::Receiver(receiver)) if receiver.reference.is_some() => {}
+        _ => {
+            return Err(syn::Error::new(
+                sig.ident.span(),
+                "the step method must take `&mut self` or `&self`",
+            ))
+        }
+    }
+    if let Some(param) = sig
+        .generics
+        .params
+        .iter()
+        .find(|param| !matches!(param, GenericParam::Lifetime(_)))
+    {
+        return Err(syn::Error::new(
+            param.span(),
+            "the step method cannot have type or const parameters, declare them on the `impl` block instead",
+        ));
+    }
+
+    let Extractors {
+        generics: extra,
+        extractors,
+        predicates,
+    } = Extractors::parse_with(
+        &mut sig.inputs,
+        options,
+        Form::Method(
+            generics
+                .type_params()
+                .map(|param| param.ident.clone())
+                .collect(),
+        ),
+    )?;
+    if extractors
+        .iter()
+        .any(|extractor| matches!(extractor, Extractor::Iter(..)))
+    {
+        let lifetime = syn::Lifetime::new(HISTORY_LIFETIME, Span::call_site());
+        sig.generics.params.insert(0, parse_quote!(#lifetime));
+    }
+    sig.generics
+        .make_where_clause()
+        .predicates
+        .extend(predicates);
+
+    // Only the input type can be introduced (by `#[input]`), which is constrained by the trait.
+    // It becomes a bound if it is already declared by the `impl` block.
+    for param in extra {
+        match param {
+            GenericParam::Type(param)
+                if generics
+                    .type_params()
+                    .any(|declared| declared.ident == param.ident) =>
+            {
+                let (ident, bounds) = (&param.ident, &param.bounds);
+                generics
+                    .make_where_clause()
+                    .predicates
+                    .push(parse_quote!(#ident: #bounds));
+            }
+            param => generics.params.push(param),
+        }
+    }
+
+    let output_ty = match &sig.output {
+        ReturnType::Default => Type::Tuple(TypeTuple {
+            paren_token: Default::default(),
+            elems: Default::default(),
+        }),
+        ReturnType::Type(_, ty) => (**ty).clone(),
+    };
+    Ok(Step {
+        name: sig.ident.clone(),
+        extractors,
+        generics,
+        output_ty,
+    })
+}
@@ -1,4 +1,4 @@
-use syn::{Result, Type, TypeReference};
+use syn::{GenericArgument, PathArguments, Result, Type, TypeParamBound, TypeReference};
 
 pub(super) fn get_type_inside_option(ty: &Type) -> Result<&Type> {
     let path = match ty {
@@ -37,3 +37,30 @@ pub(super) fn get_type_under_reference(ty: &Type) -> Result<&Type> {
     };
     Ok(target_ty)
 }
+
+pub(super) fn get_item_of_iterator(ty: &mut Type) -> Result<&mut Type> {
+    let error = syn::Error::new_spanned(&*ty, "expected `impl Iterator<Item = &T>`");
+    let Type::ImplTrait(impl_trait) = ty else {
+        return Err(error);
+    };
+    impl_trait
+        .bounds
+        .iter_mut()
+        .find_map(|bound| {
+            let TypeParamBound::Trait(bound) = bound else {
+                return None;
+            };
+            let segment = bound.path.segments.last_mut()?;
+            if segment.ident != "Iterator" {
+                return None;
+            }
+            let PathArguments::AngleBracketed(args) = &mut segment.arguments else {
+                return None;
+            };
+            args.args.iter_mut().find_map(|arg| match arg {
+                GenericArgument::AssocType(assoc) if assoc.ident == "Item" => Some(&mut assoc.ty),
+                _ => None,
+            })
+        })
+        .ok_or(error)
+}
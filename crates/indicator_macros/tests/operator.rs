@@ -0,0 +1,212 @@
+use core::{
+    borrow::Borrow,
+    marker::PhantomData,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use indicator::{
+    context::{Dependency, Map, Scope},
+    key,
+    prelude::*,
+    IndicatorIteratorExt, Reset,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Scale(f64);
+
+key! {
+    Factor: f64;
+}
+
+#[operator(input = f64)]
+fn double(In(x): In<&f64>) -> i64 {
+    (x * 2.0) as i64
+}
+
+#[operator(input = f64)]
+fn defaulted(
+    #[env(default = 1)] x: &i64,
+    #[data(default = Scale(1.0))] scale: &Scale,
+    #[env(key = Factor, default = 1.0)] factor: &f64,
+    #[prev(default = 0)] prev: &i64,
+) -> f64 {
+    (x + prev) as f64 * scale.0 * factor
+}
+
+#[test]
+fn defaults() {
+    let op = insert_env_and_output(defaulted)
+        .insert_env(double)
+        .cache(1)
+        .try_finish()
+        .unwrap();
+    assert_eq!(
+        [1.0, 2.0, 4.0]
+            .into_iter()
+            .indicator(op)
+            .collect::<Vec<_>>(),
+        [2.0, 6.0, 12.0]
+    );
+    // Only the cache is required by `#[prev]`, the defaulted values are not.
+    let op = input::<f64>()
+        .insert_env(defaulted)
+        .cache(1)
+        .try_finish()
+        .unwrap();
+    assert_eq!([1.0].into_iter().indicator(op).collect::<Vec<_>>(), [1.0]);
+}
+
+static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+fn unit_scale() -> Scale {
+    CALLS.fetch_add(1, Ordering::SeqCst);
+    Scale(1.0)
+}
+
+#[operator(input = f64)]
+fn scaled(In(x): In<&f64>, #[data(or_insert_with = unit_scale)] scale: &Scale) -> f64 {
+    x * scale.0
+}
+
+#[test]
+fn or_insert_with() {
+    let mut op = insert_env_and_output(scaled).try_finish().unwrap();
+    let outputs = [1.0, 2.0, 3.0].map(|x| op.next(x));
+    assert_eq!(outputs, [1.0, 2.0, 3.0]);
+    // The value is computed once.
+    assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+    // The cached value is cleared on reset.
+    op.reset();
+    assert_eq!(op.next(4.0), 4.0);
+    assert_eq!(CALLS.load(Ordering::SeqCst), 2);
+
+    // The value in the context is used if any.
+    let mut data = Map::new();
+    data.insert(Scale(2.0));
+    let op = insert_env_and_output(scaled).finish_with_data(data);
+    assert_eq!(
+        [1.0, 2.0].into_iter().indicator(op).collect::<Vec<_>>(),
+        [2.0, 4.0]
+    );
+    assert_eq!(CALLS.load(Ordering::SeqCst), 2);
+}
+
+struct Weighted {
+    count: i64,
+}
+
+#[operator(input = I)]
+impl Weighted {
+    #[step]
+    fn step(
+        &mut self,
+        #[input] x: &i32,
+        #[env] doubled: &i32,
+        #[history(len = 1)] prev: impl Iterator<Item = &i32>,
+    ) -> i64 {
+        self.count += 1;
+        i64::from(x + doubled + prev.sum::<i32>()) * self.count
+    }
+}
+
+#[operator(input = i32)]
+fn double_i32(In(x): In<&i32>) -> i32 {
+    x * 2
+}
+
+#[test]
+fn stateful() {
+    let op = insert_env_and_output::<i32, _, _, _>(|| Weighted { count: 0 })
+        .insert_env(double_i32)
+        .cache(1)
+        .finish();
+    assert_eq!(
+        [1, 2, 4].into_iter().indicator(op).collect::<Vec<_>>(),
+        [3, 16, 48]
+    );
+
+    // The input type is generic, so it is given explicitly.
+    let op = insert_env_and_output::<i32, _, _, _>(|| Weighted { count: 0 });
+    let op = ContextOperatorExt::<i32>::cache(op, 1);
+    let Err(err) = ContextOperatorExt::<i32>::try_finish(op) else {
+        panic!("the dependencies should be missing");
+    };
+    assert_eq!(err.dependencies(), [Dependency::of::<i32>(Scope::Env)]);
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Shift(f64);
+
+impl AsRef<f64> for Shift {
+    fn as_ref(&self) -> &f64 {
+        &self.0
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Percent(f64);
+
+impl Borrow<f64> for Percent {
+    fn borrow(&self) -> &f64 {
+        &self.0
+    }
+}
+
+/// The extractor types are carried by the struct.
+struct Accumulated<Offset, Rate> {
+    total: f64,
+    _types: PhantomData<fn() -> (Offset, Rate)>,
+}
+
+impl<Offset, Rate> Accumulated<Offset, Rate> {
+    fn new() -> Self {
+        Self {
+            total: 0.0,
+            _types: PhantomData,
+        }
+    }
+}
+
+#[operator(input = f64)]
+impl<Offset, Rate> Accumulated<Offset, Rate> {
+    #[step]
+    fn step(
+        &mut self,
+        In(x): In<&f64>,
+        #[env(as_ref)] offset: &f64,
+        #[data(rename = Rate)] rate: &f64,
+    ) -> f64 {
+        self.total += (x + offset) * rate;
+        self.total
+    }
+
+    #[reset]
+    fn clear(&mut self) {
+        self.total = 0.0;
+    }
+}
+
+#[operator(input = f64)]
+fn shift(In(x): In<&f64>) -> Shift {
+    Shift(*x)
+}
+
+#[test]
+fn declared_types() {
+    let mut op = insert_env_and_output(Accumulated::<Shift, Percent>::new)
+        .insert_env(shift)
+        .provide(Percent(0.5))
+        .try_finish()
+        .unwrap();
+    let outputs = [1.0, 2.0].map(|x| op.next(x));
+    assert_eq!(outputs, [1.0, 3.0]);
+    // The total is cleared by the `#[reset]` method.
+    op.reset();
+    assert_eq!(op.next(1.0), 1.0);
+
+    let op = insert_env_and_output(Accumulated::<Shift, Percent>::new).provide(Percent(0.5));
+    let Err(err) = op.try_finish() else {
+        panic!("the dependencies should be missing");
+    };
+    assert_eq!(err.dependencies(), [Dependency::of::<Shift>(Scope::Env)]);
+}
@@ -2,18 +2,24 @@ use indicator::prelude::*;
 use num::Num;
 use wasm_bindgen::prelude::*;
 
-#[operator(input = I, generate_out_with_data)]
-fn ma<T>(
-    #[input] x: &T,
-    #[data(as_ref?)] prev: Option<&T>,
-    #[data(as_ref)] alpha: &T,
-) -> (T, Option<T>)
+/// Moving average, keeping the last average as its state.
+struct MaState<T> {
+    alpha: T,
+    last: Option<T>,
+}
+
+#[operator(input = T)]
+impl<T> MaState<T>
 where
-    T: Num + Clone,
+    T: Num + Clone + Send + Sync + 'static,
 {
-    let prev = prev.cloned().unwrap_or_else(|| x.clone());
-    let out = x.clone() * alpha.clone() + prev.clone() * (T::one() - alpha.clone());
-    (out.clone(), Some(out))
+    #[step]
+    fn step(&mut self, In(x): In<&T>) -> T {
+        let prev = self.last.take().unwrap_or_else(|| x.clone());
+        let out = x.clone() * self.alpha.clone() + prev * (T::one() - self.alpha.clone());
+        self.last = Some(out.clone());
+        out
+    }
 }
 
 /// Wasm Ma Operator.
@@ -23,16 +29,7 @@ pub struct Ma(BoxOperator<'static, f64, f64>);
 #[wasm_bindgen]
 impl Ma {
     pub fn new(alpha: f64) -> Self {
-        use derive_more::{AsRef, From};
-
-        #[derive(AsRef, Clone)]
-        struct Alpha(f64);
-
-        #[derive(AsRef, From)]
-        struct State(f64);
-
-        let op = insert_and_output(ma::<f64, _, State, Alpha, f64, State>)
-            .provide(Alpha(alpha))
+        let op = insert_env_and_output(|| MaState { alpha, last: None })
             .finish()
             .boxed();
 